@@ -0,0 +1,156 @@
+// Upserting the export into an Airtable table, keyed on the plant name
+use anyhow::{anyhow, Context, Result};
+use reqwest::Url;
+use serde_json::{json, Map, Value};
+use tracing::warn;
+
+use crate::{
+    build_export_rows, create_http_client, export_record, ExportOptions, Units, CSV_HEADERS,
+};
+
+const AIRTABLE_API_URL: &str = "https://api.airtable.com/v0";
+// Airtable accepts at most this many records per request
+const BATCH_SIZE: usize = 10;
+
+// An export column sent to a differently named Airtable field, e.g. "Plant Name=Name"
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldMapping {
+    pub column: String,
+    pub field: String,
+}
+
+pub fn parse_field_mapping(text: &str) -> Result<FieldMapping, String> {
+    let (column, field) = text
+        .split_once('=')
+        .ok_or_else(|| format!("expected COLUMN=FIELD, got \"{}\"", text))?;
+    let column = column.trim();
+    if !CSV_HEADERS.contains(&column) {
+        return Err(format!("\"{}\" is not an export column", column));
+    }
+    Ok(FieldMapping {
+        column: column.to_string(),
+        field: field.trim().to_string(),
+    })
+}
+
+// The Airtable field an export column goes to, or None when a mapping leaves it out.
+// Unmapped columns keep the name they have in a CSV export.
+fn field_name(column: &str, mappings: &[FieldMapping], units: Units) -> Option<String> {
+    if mappings.is_empty() {
+        return Some(units.header(column));
+    }
+    mappings
+        .iter()
+        .find(|mapping| mapping.column == column)
+        .map(|mapping| mapping.field.clone())
+}
+
+// One export row as Airtable fields; NULL cells clear the field rather than writing "NULL"
+fn record_fields(values: &[String], mappings: &[FieldMapping], units: Units) -> Map<String, Value> {
+    CSV_HEADERS
+        .iter()
+        .zip(values)
+        .filter_map(|(column, value)| {
+            let field = field_name(column, mappings, units)?;
+            let value = if value == "NULL" {
+                Value::Null
+            } else {
+                json!(value)
+            };
+            Some((field, value))
+        })
+        .collect()
+}
+
+// Export the plants in the input CSV, creating Airtable records or updating the ones whose
+// plant name field already matches
+pub fn sync_airtable(
+    records: &[csv::StringRecord],
+    json_dir: &str,
+    options: &ExportOptions,
+    base_id: &str,
+    table: &str,
+    token: &str,
+    mappings: &[FieldMapping],
+) -> Result<()> {
+    let merge_field = field_name("Plant Name", mappings, options.units)
+        .ok_or_else(|| anyhow!("Map the \"Plant Name\" column, since records are matched on it"))?;
+
+    let rows = build_export_rows(records, json_dir, options)?;
+    let mut upserts = Vec::new();
+    for row in &rows {
+        if row.plant.is_none() {
+            warn!("Skipping {} - no JSON data", row.input.plant_name);
+            continue;
+        }
+        let fields = record_fields(&export_record(row), mappings, options.units);
+        upserts.push(json!({ "fields": fields }));
+    }
+
+    let mut url = Url::parse(AIRTABLE_API_URL)?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow!("Invalid Airtable API URL"))?
+        .push(base_id)
+        .push(table);
+    let client = create_http_client();
+    let mut created = 0;
+    let mut updated = 0;
+    for batch in upserts.chunks(BATCH_SIZE) {
+        let response = client
+            .patch(url.clone())
+            .bearer_auth(token)
+            .json(&json!({
+                "performUpsert": {"fieldsToMergeOn": [merge_field]},
+                "typecast": true,
+                "records": batch,
+            }))
+            .send()
+            .context("Failed to reach Airtable")?;
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Airtable upsert failed with {}: {}",
+                response.status(),
+                response.text().unwrap_or_default()
+            ));
+        }
+        let result: Value = response.json()?;
+        created += result["createdRecords"].as_array().map_or(0, Vec::len);
+        updated += result["updatedRecords"].as_array().map_or(0, Vec::len);
+    }
+
+    println!(
+        "Synced {} plants to Airtable: {} created, {} updated",
+        upserts.len(),
+        created,
+        updated
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CSV_FIELD_COUNT;
+
+    #[test]
+    fn test_record_fields() {
+        let mut values = vec!["NULL".to_string(); CSV_FIELD_COUNT];
+        values[0] = "Carrot".to_string();
+        values[2] = "Botanical Interests".to_string();
+
+        let mappings = [
+            parse_field_mapping("Plant Name=Name").unwrap(),
+            parse_field_mapping("Brand=Vendor").unwrap(),
+            parse_field_mapping("Notes=Notes").unwrap(),
+        ];
+        assert_eq!(
+            Value::Object(record_fields(&values, &mappings, Units::Imperial)),
+            json!({"Name": "Carrot", "Vendor": "Botanical Interests", "Notes": null})
+        );
+        assert_eq!(
+            record_fields(&values, &[], Units::Metric).len(),
+            CSV_FIELD_COUNT
+        );
+        assert!(parse_field_mapping("Cost=Price").is_err());
+    }
+}
@@ -0,0 +1,85 @@
+// Backing off from a store that answers 429 Too Many Requests, for as long as it asks, and
+// holding every later request to that store until then
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Retries of one request before its 429 is returned as the page
+pub const MAX_RETRIES: u32 = 3;
+// Waits asked for beyond this are cut short; a batch shouldn't stall for hours
+const MAX_WAIT: Duration = Duration::from_secs(600);
+// Doubled for each retry when the server doesn't say how long to wait
+const DEFAULT_WAIT: Duration = Duration::from_secs(30);
+
+// When each host may next be sent a request
+static RESUME_AT: LazyLock<Mutex<HashMap<String, Instant>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// Retry-After as a number of seconds or an HTTP date
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    Some((at.with_timezone(&Utc) - now).to_std().unwrap_or_default())
+}
+
+// How long to wait before the given retry of a request the server answered with 429
+pub fn retry_wait(headers: &HeaderMap, retry: u32) -> Duration {
+    headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| parse_retry_after(value, Utc::now()))
+        .unwrap_or(DEFAULT_WAIT * 2u32.pow(retry))
+        .min(MAX_WAIT)
+}
+
+// Hold requests to the host for the given time
+pub fn pause(host: &str, wait: Duration) {
+    let resume_at = Instant::now() + wait;
+    let mut hosts = RESUME_AT.lock().unwrap_or_else(|e| e.into_inner());
+    let entry = hosts.entry(host.to_string()).or_insert(resume_at);
+    *entry = (*entry).max(resume_at);
+}
+
+// Sleep until the host may be sent another request
+pub fn wait_for(host: &str) {
+    let resume_at = RESUME_AT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(host)
+        .copied();
+    if let Some(resume_at) = resume_at {
+        thread::sleep(resume_at.saturating_duration_since(Instant::now()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = DateTime::parse_from_rfc3339("2025-04-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Tue, 01 Apr 2025 12:01:30 GMT", now),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            parse_retry_after("Tue, 01 Apr 2025 11:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+        assert_eq!(retry_wait(&HeaderMap::new(), 2), Duration::from_secs(120));
+    }
+}
@@ -0,0 +1,346 @@
+// Packing a collection into one .tar.zst file to share it or move it to another machine
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+use tracing::warn;
+
+use crate::germination::GERMINATION_FILE;
+use crate::inventory::INVENTORY_FILE;
+use crate::names::{PlantIndex, INDEX_FILE};
+use crate::overrides::OVERRIDES_FILE;
+use crate::planting_log::PLANTING_LOG_FILE;
+use crate::schema::SCHEMA_VERSION;
+
+const MANIFEST_FILE: &str = "manifest.json";
+// Where the JSON directory's files and the input CSV go inside the archive
+const PLANTS_DIR: &str = "plants";
+const INPUT_FILE: &str = "input.csv";
+// Bumped when the archive's layout changes, so older versions can refuse what they can't read
+const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Manifest {
+    bundle_version: u32,
+    schema_version: u32,
+    created_at: DateTime<Utc>,
+    created_by: String,
+    plants: usize,
+    files: Vec<String>,
+    has_input: bool,
+}
+
+// The JSON directory's files worth carrying over: the plants, their index, the overrides, the
+// germination tests, the inventory, and the planting log
+fn collection_files(json_dir: &str) -> Result<Vec<String>> {
+    let mut files: Vec<String> = fs::read_dir(json_dir)
+        .context(format!("Failed to read directory: {}", json_dir))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.extension().and_then(|ext| ext.to_str()) == Some("json")
+                || path.ends_with(OVERRIDES_FILE)
+                || path.ends_with(GERMINATION_FILE)
+                || path.ends_with(INVENTORY_FILE)
+                || path.ends_with(PLANTING_LOG_FILE)
+        })
+        .filter_map(|path| path.file_name()?.to_str().map(String::from))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn append_bytes<W: std::io::Write>(
+    archive: &mut tar::Builder<W>,
+    path: &str,
+    bytes: &[u8],
+) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(Utc::now().timestamp().max(0) as u64);
+    archive.append_data(&mut header, path, bytes)?;
+    Ok(())
+}
+
+pub fn create_bundle(json_dir: &str, input_file: Option<&str>, output_file: &str) -> Result<()> {
+    let files = collection_files(json_dir)?;
+    let manifest = Manifest {
+        bundle_version: BUNDLE_VERSION,
+        schema_version: SCHEMA_VERSION,
+        created_at: Utc::now(),
+        created_by: format!("seeds-scraper {}", env!("CARGO_PKG_VERSION")),
+        plants: files
+            .iter()
+            .filter(|file| file.ends_with(".json") && *file != INDEX_FILE)
+            .count(),
+        files: files.clone(),
+        has_input: input_file.is_some(),
+    };
+
+    let output = File::create(output_file).context(format!("Failed to create {}", output_file))?;
+    let mut archive = tar::Builder::new(zstd::Encoder::new(output, 0)?.auto_finish());
+    // First, so an import can check it before unpacking anything
+    append_bytes(
+        &mut archive,
+        MANIFEST_FILE,
+        &serde_json::to_vec_pretty(&manifest)?,
+    )?;
+    for file in &files {
+        let path = Path::new(json_dir).join(file);
+        archive
+            .append_path_with_name(&path, format!("{}/{}", PLANTS_DIR, file))
+            .context(format!("Failed to add {}", path.display()))?;
+    }
+    if let Some(input_file) = input_file {
+        archive
+            .append_path_with_name(input_file, INPUT_FILE)
+            .context(format!("Failed to add {}", input_file))?;
+    }
+    archive.into_inner()?;
+
+    println!("Bundled {} plants into {}", manifest.plants, output_file);
+    Ok(())
+}
+
+// Write a bundled file, unless it's already there and isn't to be overwritten
+fn write_file(
+    target: &Path,
+    contents: &[u8],
+    overwrite: bool,
+    kept: &mut Vec<String>,
+) -> Result<bool> {
+    if target.exists() && !overwrite {
+        kept.push(target.display().to_string());
+        return Ok(false);
+    }
+    fs::write(target, contents).context(format!("Failed to write {}", target.display()))?;
+    Ok(true)
+}
+
+// Unpack a bundle's plants into the JSON directory, keeping any files already there unless
+// told to overwrite them, and its input CSV to input_file if one is given. The bundle's
+// index is merged into the local one, so plants keep their names and a slug another local
+// plant already has gets a suffix.
+pub fn import_bundle(
+    bundle_file: &str,
+    json_dir: &str,
+    input_file: Option<&str>,
+    overwrite: bool,
+) -> Result<()> {
+    let file = File::open(bundle_file).context(format!("Failed to open {}", bundle_file))?;
+    let mut archive = tar::Archive::new(zstd::Decoder::new(file)?);
+    let mut entries = archive.entries()?;
+
+    let mut manifest_entry = entries
+        .next()
+        .ok_or_else(|| anyhow!("{} is empty", bundle_file))??;
+    if manifest_entry.path()?.as_ref() != Path::new(MANIFEST_FILE) {
+        return Err(anyhow!(
+            "{} has no manifest, so it isn't a bundle",
+            bundle_file
+        ));
+    }
+    let mut manifest_json = String::new();
+    manifest_entry.read_to_string(&mut manifest_json)?;
+    let manifest: Manifest =
+        serde_json::from_str(&manifest_json).context("Invalid bundle manifest")?;
+    if manifest.bundle_version > BUNDLE_VERSION {
+        return Err(anyhow!(
+            "{} was made by a newer version ({}); upgrade to import it",
+            bundle_file,
+            manifest.created_by
+        ));
+    }
+
+    fs::create_dir_all(json_dir).context(format!("Failed to create directory: {}", json_dir))?;
+    let mut imported = 0;
+    let mut kept = Vec::new();
+    // Placed once the bundle's index has been read, wherever it is in the archive
+    let mut bundle_index: BTreeMap<String, String> = BTreeMap::new();
+    let mut plant_files = Vec::new();
+    for entry in entries {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents)?;
+        if path == Path::new(INPUT_FILE) {
+            match input_file {
+                Some(input_file) => {
+                    imported += usize::from(write_file(
+                        Path::new(input_file),
+                        &contents,
+                        overwrite,
+                        &mut kept,
+                    )?);
+                }
+                None => println!("The bundle has an input CSV; give --input-file to save it"),
+            }
+            continue;
+        }
+
+        // Only a bare file name under plants/, so nothing lands outside the JSON directory
+        let name = path
+            .strip_prefix(PLANTS_DIR)
+            .ok()
+            .filter(|name| name.components().count() == 1)
+            .and_then(|name| name.to_str());
+        match name {
+            Some(INDEX_FILE) => {
+                bundle_index =
+                    serde_json::from_slice(&contents).context("Invalid index in the bundle")?;
+            }
+            Some(name) if name.ends_with(".json") => plant_files.push((name.to_string(), contents)),
+            Some(name) => {
+                let target = Path::new(json_dir).join(name);
+                imported += usize::from(write_file(&target, &contents, overwrite, &mut kept)?);
+            }
+            None => warn!("Ignoring unexpected {} in the bundle", path.display()),
+        }
+    }
+
+    let mut index = PlantIndex::load(json_dir)?;
+    for (file, contents) in plant_files {
+        let stem = file.strip_suffix(".json").unwrap_or(&file);
+        let mut names = bundle_index
+            .iter()
+            .filter(|(_, indexed)| **indexed == file)
+            .map(|(name, _)| name.as_str());
+        let target = match names.next() {
+            Some(plant) => {
+                let target = if index.contains(plant) {
+                    index.path_for(plant)
+                } else {
+                    index.adopt(plant, &file)
+                };
+                // Other names sharing the file, as `share` stores them
+                for other in names {
+                    if !index.contains(other) {
+                        index.share(other, plant);
+                    }
+                }
+                target
+            }
+            // Named after its plant; only moved if a local plant already has the file
+            None => match index.plant_for_file(stem) {
+                Some(_) => index.adopt(stem, &file),
+                None => index.path_for(stem),
+            },
+        };
+        imported += usize::from(write_file(
+            Path::new(&target),
+            &contents,
+            overwrite,
+            &mut kept,
+        )?);
+    }
+    index.save()?;
+
+    println!(
+        "Imported {} files from {} ({} plants, bundled {})",
+        imported,
+        bundle_file,
+        manifest.plants,
+        manifest.created_at.format("%Y-%m-%d")
+    );
+    if !kept.is_empty() {
+        println!(
+            "Kept {} existing files; use --overwrite to replace them: {}",
+            kept.len(),
+            kept.join(", ")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let dir = std::env::temp_dir().join(format!("seed-scraper-bundle-{}", std::process::id()));
+        let source = dir.join("source");
+        let target = dir.join("target");
+        fs::create_dir_all(&source).unwrap();
+        fs::create_dir_all(&target).unwrap();
+        fs::write(source.join("Carrot.json"), r#"{"url": "http://x"}"#).unwrap();
+        fs::write(
+            source.join(OVERRIDES_FILE),
+            "[Carrot]\nfamily = \"Apiaceae\"\n",
+        )
+        .unwrap();
+        fs::write(source.join("notes.txt"), "left out").unwrap();
+        fs::write(source.join("in.csv"), "Plant Name,URL\nCarrot,http://x\n").unwrap();
+        fs::write(target.join("Carrot.json"), "{}").unwrap();
+        // Bundled as beet.json, which a different local plant already has
+        fs::write(source.join("beet.json"), r#"{"url": "http://x/beet"}"#).unwrap();
+        fs::write(
+            source.join(INDEX_FILE),
+            r#"{"Beet: Detroit": "beet.json", "Detroit Beet": "beet.json"}"#,
+        )
+        .unwrap();
+        fs::write(target.join("beet.json"), r#"{"url": "http://y/beet"}"#).unwrap();
+        fs::write(
+            target.join(INDEX_FILE),
+            r#"{"Beet: Chioggia": "beet.json"}"#,
+        )
+        .unwrap();
+
+        let source_dir = source.to_str().unwrap();
+        let bundle = dir.join("seeds.tar.zst");
+        let bundle = bundle.to_str().unwrap();
+        let input = source.join("in.csv");
+        create_bundle(source_dir, input.to_str(), bundle).unwrap();
+
+        let target_dir = target.to_str().unwrap();
+        let imported_input = target.join("in.csv");
+        import_bundle(bundle, target_dir, imported_input.to_str(), false).unwrap();
+        assert_eq!(
+            fs::read_to_string(target.join("Carrot.json")).unwrap(),
+            "{}"
+        );
+        assert!(target.join(OVERRIDES_FILE).exists());
+        assert!(!target.join("notes.txt").exists());
+        assert!(imported_input.exists());
+        let index = PlantIndex::load(target_dir).unwrap();
+        assert_eq!(
+            index.path_for("Beet: Chioggia"),
+            format!("{}/beet.json", target_dir)
+        );
+        assert_eq!(
+            index.path_for("Beet: Detroit"),
+            format!("{}/beet-2.json", target_dir)
+        );
+        assert_eq!(
+            index.path_for("Detroit Beet"),
+            format!("{}/beet-2.json", target_dir)
+        );
+        assert_eq!(
+            fs::read_to_string(target.join("beet.json")).unwrap(),
+            r#"{"url": "http://y/beet"}"#
+        );
+
+        import_bundle(bundle, target_dir, None, true).unwrap();
+        assert_eq!(
+            fs::read_to_string(target.join("Carrot.json")).unwrap(),
+            r#"{"url": "http://x"}"#
+        );
+        // Overwriting replaces the bundled plants' files, not the local index or other plants
+        assert_eq!(
+            fs::read_to_string(target.join("beet.json")).unwrap(),
+            r#"{"url": "http://y/beet"}"#
+        );
+        assert_eq!(
+            fs::read_to_string(target.join("beet-2.json")).unwrap(),
+            r#"{"url": "http://x/beet"}"#
+        );
+        assert!(PlantIndex::load(target_dir)
+            .unwrap()
+            .contains("Beet: Chioggia"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,188 @@
+use anyhow::{Context, Result};
+use chrono::{Days, NaiveDate, Utc};
+use std::fs;
+
+use crate::{
+    calculate_dates, determine_sowing_strategy, get_when_to_seed_start, load_json_dir, names,
+    PlantInfo, SowingStrategy,
+};
+
+// A single all-day event in the garden calendar
+#[derive(Debug)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub summary: String,
+    pub description: String,
+    pub start: NaiveDate,
+    // Exclusive, as in iCalendar's DTEND for all-day events
+    pub end: NaiveDate,
+}
+
+// Build the sowing, transplant, and harvest events for one plant
+pub fn plant_events(name: &str, info: &PlantInfo, frost_date: NaiveDate) -> Vec<CalendarEvent> {
+    let sowing_strategy = determine_sowing_strategy(info, None);
+    let when_to_start = get_when_to_seed_start(info, None);
+    let dates = calculate_dates(info, sowing_strategy, &when_to_start, frost_date);
+    let slug = event_slug(name);
+    let mut events = Vec::new();
+
+    if let Some(start) = dates.start {
+        let (summary, instructions) = match sowing_strategy {
+            Some(SowingStrategy::Inside) => (
+                format!("Start {} indoors", name),
+                &info.when_to_start_inside,
+            ),
+            _ => (format!("Sow {} outside", name), &info.when_to_sow_outside),
+        };
+        events.push(CalendarEvent {
+            uid: format!("{}-sow", slug),
+            summary,
+            description: instructions.clone().unwrap_or_default(),
+            start,
+            end: start + Days::new(1),
+        });
+    }
+
+    if let Some(transplant) = dates.transplant {
+        events.push(CalendarEvent {
+            uid: format!("{}-transplant", slug),
+            summary: format!("Transplant {}", name),
+            description: String::new(),
+            start: transplant,
+            end: transplant + Days::new(1),
+        });
+    }
+
+    if let (Some(first), Some(last)) = (dates.first_harvest, dates.last_harvest) {
+        events.push(CalendarEvent {
+            uid: format!("{}-harvest", slug),
+            summary: format!("Harvest {}", name),
+            description: info.days_to_maturity.clone().unwrap_or_default(),
+            start: first,
+            end: last + Days::new(1),
+        });
+    }
+
+    events
+}
+
+// Lowercase alphanumeric identifier for a plant, stable across runs; a hash of the name when
+// it has no ASCII letters or digits, so such plants don't share one
+pub fn event_slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() {
+        names::name_hash(name)
+    } else {
+        slug.to_string()
+    }
+}
+
+// Escape text according to RFC 5545 section 3.3.11
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+// Fold content lines longer than 75 octets, as required by RFC 5545
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut line_len = 0;
+    for c in line.chars() {
+        if line_len + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            line_len = 1;
+        }
+        folded.push(c);
+        line_len += c.len_utf8();
+    }
+    folded
+}
+
+pub fn render_ics(events: &[CalendarEvent]) -> String {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//seeds-scraper//Garden Calendar//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}@seeds-scraper", event.uid));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!(
+            "DTSTART;VALUE=DATE:{}",
+            event.start.format("%Y%m%d")
+        ));
+        lines.push(format!("DTEND;VALUE=DATE:{}", event.end.format("%Y%m%d")));
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if !event.description.is_empty() {
+            lines.push(format!("DESCRIPTION:{}", escape_text(&event.description)));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line) + "\r\n").collect()
+}
+
+pub fn export_calendar(json_dir: &str, frost_date: NaiveDate, output_file: &str) -> Result<()> {
+    let plants = load_json_dir(json_dir)?;
+    let events: Vec<CalendarEvent> = plants
+        .iter()
+        .flat_map(|(name, info)| plant_events(name, info, frost_date))
+        .collect();
+
+    fs::write(output_file, render_ics(&events))
+        .context(format!("Failed to write calendar to {}", output_file))?;
+    println!(
+        "Wrote {} events for {} plants to {}",
+        events.len(),
+        plants.len(),
+        output_file
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_slug() {
+        assert_eq!(event_slug("Danvers 126 Carrot"), "danvers-126-carrot");
+        assert_eq!(event_slug("Pea, Sugar Snap (Bush)"), "pea-sugar-snap-bush");
+        assert_eq!(event_slug("ñ").len(), 12);
+        assert_ne!(event_slug("紫蘇"), event_slug("大根"));
+    }
+
+    #[test]
+    fn test_render_ics() {
+        let events = vec![CalendarEvent {
+            uid: "carrot-sow".to_string(),
+            summary: "Sow Carrot outside".to_string(),
+            description: "2 to 4 weeks before, ideally; warm".to_string(),
+            start: NaiveDate::from_ymd_opt(2025, 4, 26).unwrap(),
+            end: NaiveDate::from_ymd_opt(2025, 4, 27).unwrap(),
+        }];
+        let ics = render_ics(&events);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.contains("UID:carrot-sow@seeds-scraper\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20250426\r\n"));
+        assert!(ics.contains("DTEND;VALUE=DATE:20250427\r\n"));
+        assert!(ics.contains("DESCRIPTION:2 to 4 weeks before\\, ideally\\; warm\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    }
+}
@@ -0,0 +1,57 @@
+// Decoding pages from smaller stores that aren't served as UTF-8, e.g. ISO-8859-1
+use encoding_rs::{Encoding, UTF_8};
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::error::{compiled, regex, Pattern};
+
+// Browsers only look this far into the page for a meta charset
+const META_SCAN_BYTES: usize = 1024;
+
+// The charset parameter of a Content-Type such as "text/html; charset=ISO-8859-1"
+fn header_charset(content_type: &str) -> Option<&'static Encoding> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        if !name.trim().eq_ignore_ascii_case("charset") {
+            return None;
+        }
+        Encoding::for_label(value.trim().trim_matches('"').as_bytes())
+    })
+}
+
+// <meta charset="..."> or <meta http-equiv="Content-Type" content="...; charset=...">
+fn meta_charset(body: &[u8]) -> Option<&'static Encoding> {
+    static META: Pattern<Regex> =
+        LazyLock::new(|| regex(r#"(?i)<meta[^>]+charset\s*=\s*["']?([\w-]+)"#));
+    let head = String::from_utf8_lossy(&body[..body.len().min(META_SCAN_BYTES)]);
+    let label = compiled(&META).ok()?.captures(&head)?.get(1)?.as_str();
+    Encoding::for_label(label.as_bytes())
+}
+
+// The page as text, going by a byte order mark, then the Content-Type header, then a meta
+// tag, and falling back to UTF-8
+pub fn decode_page(body: &[u8], content_type: Option<&str>) -> String {
+    let encoding = Encoding::for_bom(body)
+        .map(|(encoding, _)| encoding)
+        .or_else(|| content_type.and_then(header_charset))
+        .or_else(|| meta_charset(body))
+        .unwrap_or(UTF_8);
+    encoding.decode_with_bom_removal(body).0.into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_page() {
+        let latin1 =
+            b"<html><head><meta charset=\"iso-8859-1\"></head><h1>Cr\xe8me Br\xfbl\xe9e</h1>";
+        assert!(decode_page(latin1, None).contains("Crème Brûlée"));
+        assert!(
+            decode_page(b"<h1>Cr\xe8me</h1>", Some("text/html; charset=ISO-8859-1"))
+                .contains("Crème")
+        );
+        assert_eq!(decode_page("Crème".as_bytes(), Some("text/html")), "Crème");
+    }
+}
@@ -0,0 +1,90 @@
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::fs;
+use tracing::warn;
+
+use crate::columns::ColumnMap;
+use crate::formats::jsonl::plant_object;
+use crate::names::PlantIndex;
+use crate::{load_json_dir, ExportRow, InputRecord, PlantExport};
+
+// Input CSV rows keyed by the plant name load_json_dir reports for them
+fn read_input_records(
+    input_file: &str,
+    index: &PlantIndex,
+) -> Result<HashMap<String, csv::StringRecord>> {
+    let mut reader = csv::Reader::from_path(input_file)
+        .context(format!("Failed to read input CSV file: {}", input_file))?;
+
+    let columns = ColumnMap::from_headers(reader.headers()?);
+
+    let mut records = HashMap::new();
+    for result in reader.records() {
+        match result {
+            Ok(record) => {
+                let record = columns.normalize(&record);
+                let input = InputRecord::from_csv_record(&record);
+                // Files from before the index are only known by their file name
+                let name = if index.contains(input.plant_name) {
+                    input.plant_name.to_string()
+                } else {
+                    input.plant_name.replace("/", "_")
+                };
+                records.insert(name, record);
+            }
+            Err(e) => warn!("Error reading CSV record: {}", e),
+        }
+    }
+    Ok(records)
+}
+
+// Merge every plant JSON file into one array, joined with input CSV columns when available
+pub fn export_collection(
+    json_dir: &str,
+    input_file: Option<&str>,
+    frost_date: NaiveDate,
+    output_file: &str,
+) -> Result<()> {
+    let plants = load_json_dir(json_dir)?;
+    let mut input_records = match input_file {
+        Some(input_file) => read_input_records(input_file, &PlantIndex::load(json_dir)?)?,
+        None => HashMap::new(),
+    };
+
+    // Plants missing from the input CSV get a record with just their name and URL
+    let mut joined = 0;
+    let records: Vec<csv::StringRecord> = plants
+        .iter()
+        .map(|(name, info)| match input_records.remove(name) {
+            Some(record) => {
+                joined += 1;
+                record
+            }
+            None => csv::StringRecord::from(vec![name.as_str(), info.url.as_str()]),
+        })
+        .collect();
+
+    let objects: Vec<serde_json::Value> = records
+        .iter()
+        .zip(plants)
+        .map(|(record, (_, info))| {
+            let input = InputRecord::from_csv_record(record);
+            let plant = PlantExport::for_input(info, &input, input.user_strategy, frost_date);
+            plant_object(&ExportRow {
+                input,
+                plant: Some(plant),
+            })
+        })
+        .collect();
+
+    fs::write(output_file, serde_json::to_string_pretty(&objects)?)
+        .context(format!("Failed to write collection to {}", output_file))?;
+    println!(
+        "Wrote {} plants ({} with input CSV metadata) to {}",
+        objects.len(),
+        joined,
+        output_file
+    );
+    Ok(())
+}
@@ -0,0 +1,200 @@
+// Finding the input CSV's columns by their header names rather than their positions
+use std::sync::OnceLock;
+use tracing::warn;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Plant,
+    Url,
+    Brand,
+    Year,
+    Notes,
+    Strategy,
+    FrostDate,
+    StartDate,
+}
+
+// Every input column, in the order the original spreadsheet layout puts them, followed by
+// the optional ones it never had
+const COLUMNS: [Column; 8] = [
+    Column::Plant,
+    Column::Url,
+    Column::Brand,
+    Column::Year,
+    Column::Notes,
+    Column::Strategy,
+    Column::FrostDate,
+    Column::StartDate,
+];
+
+impl Column {
+    fn key(self) -> &'static str {
+        match self {
+            Column::Plant => "plant",
+            Column::Url => "url",
+            Column::Brand => "brand",
+            Column::Year => "year",
+            Column::Notes => "notes",
+            Column::Strategy => "strategy",
+            Column::FrostDate => "frost_date",
+            Column::StartDate => "start_date",
+        }
+    }
+
+    // Header names recognized without any configuration, compared ignoring case
+    fn default_headers(self) -> &'static [&'static str] {
+        match self {
+            Column::Plant => &["plant name", "plant", "name", "variety"],
+            Column::Url => &["url", "link", "product url"],
+            Column::Brand => &["brand", "vendor", "company", "seed company"],
+            Column::Year => &["purchase year", "year", "purchased"],
+            Column::Notes => &["notes", "note", "comments"],
+            Column::Strategy => &["strategy", "sowing strategy", "user strategy"],
+            Column::FrostDate => &["frost date", "last frost", "last frost date"],
+            Column::StartDate => &["start date", "sow date", "sowing date"],
+        }
+    }
+}
+
+// An extra header name for a column, e.g. "brand=Supplier"
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnAlias {
+    pub column: Column,
+    pub header: String,
+}
+
+pub fn parse_column_alias(text: &str) -> Result<ColumnAlias, String> {
+    let (key, header) = text
+        .split_once('=')
+        .ok_or_else(|| format!("expected COLUMN=HEADER, got \"{}\"", text))?;
+    let key = key.trim().to_lowercase();
+    let column = COLUMNS
+        .into_iter()
+        .find(|column| column.key() == key)
+        .ok_or_else(|| {
+            let keys: Vec<&str> = COLUMNS.iter().map(|column| column.key()).collect();
+            format!(
+                "unknown column \"{}\", expected one of {}",
+                key,
+                keys.join(", ")
+            )
+        })?;
+    Ok(ColumnAlias {
+        column,
+        header: header.trim().to_string(),
+    })
+}
+
+static ALIASES: OnceLock<Vec<ColumnAlias>> = OnceLock::new();
+
+// Set once from the global --column flags
+pub fn set_aliases(aliases: Vec<ColumnAlias>) {
+    let _ = ALIASES.set(aliases);
+}
+
+fn matches_header(column: Column, header: &str, aliases: &[ColumnAlias]) -> bool {
+    let header = header.trim();
+    aliases
+        .iter()
+        .any(|alias| alias.column == column && alias.header.eq_ignore_ascii_case(header))
+        || column
+            .default_headers()
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(header))
+}
+
+// Which field of a CSV record holds each input column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnMap {
+    positions: [Option<usize>; 8],
+}
+
+impl Default for ColumnMap {
+    // The original fixed layout: Plant, URL, Brand, Purchase Year, Notes, Strategy
+    fn default() -> Self {
+        ColumnMap {
+            positions: [
+                Some(0),
+                Some(1),
+                Some(2),
+                Some(3),
+                Some(4),
+                Some(5),
+                None,
+                None,
+            ],
+        }
+    }
+}
+
+impl ColumnMap {
+    pub fn from_headers(headers: &csv::StringRecord) -> Self {
+        Self::with_aliases(headers, ALIASES.get().map_or(&[], Vec::as_slice))
+    }
+
+    fn with_aliases(headers: &csv::StringRecord, aliases: &[ColumnAlias]) -> Self {
+        let positions = COLUMNS.map(|column| {
+            headers
+                .iter()
+                .position(|header| matches_header(column, header, aliases))
+        });
+
+        // Headers that name none of the columns are probably just the old layout with
+        // different wording, so keep reading it by position
+        if positions.iter().all(Option::is_none) {
+            return ColumnMap::default();
+        }
+        for column in [Column::Plant, Column::Url] {
+            if positions[column as usize].is_none() {
+                warn!(
+                    "No {} column found in the input CSV header; add one or map it with --column {}=HEADER",
+                    column.key(),
+                    column.key()
+                );
+            }
+        }
+        ColumnMap { positions }
+    }
+
+    // The record rearranged into the original layout, which is what InputRecord reads
+    pub fn normalize(&self, record: &csv::StringRecord) -> csv::StringRecord {
+        self.positions
+            .iter()
+            .map(|position| {
+                position
+                    .and_then(|position| record.get(position))
+                    .unwrap_or("")
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_map_from_headers() {
+        let headers = csv::StringRecord::from(vec!["Notes", "Supplier", "Link", "Variety"]);
+        let aliases = [parse_column_alias("brand=supplier").unwrap()];
+        let columns = ColumnMap::with_aliases(&headers, &aliases);
+        let record = csv::StringRecord::from(vec!["Thin early", "BI", "http://x", "Carrot"]);
+        assert_eq!(
+            columns.normalize(&record),
+            csv::StringRecord::from(vec![
+                "Carrot",
+                "http://x",
+                "BI",
+                "",
+                "Thin early",
+                "",
+                "",
+                ""
+            ])
+        );
+
+        let unknown = csv::StringRecord::from(vec!["Seed", "Where"]);
+        assert_eq!(ColumnMap::with_aliases(&unknown, &[]), ColumnMap::default());
+        assert!(parse_column_alias("price=Cost").is_err());
+    }
+}
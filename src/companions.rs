@@ -0,0 +1,297 @@
+// Companion planting: which plants grow well, or badly, next to each other
+use anyhow::{bail, Result};
+
+use crate::{load_json_dir, ExportRow, PlantInfo};
+
+// Each entry is keyed by a common name or a botanical family, and lists the keys it likes
+// and dislikes growing beside; a pairing counts whichever side of it is listed
+type Companion = (
+    &'static str,
+    &'static [&'static str],
+    &'static [&'static str],
+);
+
+const COMPANIONS: [Companion; 25] = [
+    (
+        "tomato",
+        &[
+            "basil", "carrot", "onion", "garlic", "chive", "parsley", "marigold", "borage",
+        ],
+        &["fennel", "potato", "corn", "dill", "Brassicaceae"],
+    ),
+    (
+        "pepper",
+        &["basil", "onion", "carrot", "tomato"],
+        &["fennel"],
+    ),
+    (
+        "potato",
+        &["Fabaceae", "corn", "marigold"],
+        &["Cucurbitaceae", "sunflower", "tomato"],
+    ),
+    (
+        "carrot",
+        &[
+            "onion", "leek", "chive", "lettuce", "radish", "rosemary", "sage",
+        ],
+        &["dill", "parsnip"],
+    ),
+    ("corn", &["Fabaceae", "Cucurbitaceae"], &[]),
+    (
+        "lettuce",
+        &["carrot", "radish", "strawberry", "chive", "cucumber"],
+        &[],
+    ),
+    (
+        "radish",
+        &["carrot", "lettuce", "cucumber", "pea", "spinach"],
+        &[],
+    ),
+    ("beet", &["onion", "lettuce", "Brassicaceae"], &[]),
+    ("spinach", &["strawberry", "pea", "radish"], &[]),
+    (
+        "strawberry",
+        &["lettuce", "spinach", "onion", "Fabaceae", "borage"],
+        &["Brassicaceae"],
+    ),
+    ("basil", &["tomato", "pepper"], &["sage", "rue"]),
+    (
+        "dill",
+        &["Brassicaceae", "lettuce", "cucumber"],
+        &["carrot", "tomato"],
+    ),
+    (
+        "fennel",
+        &[],
+        &["tomato", "pepper", "Fabaceae", "coriander", "cilantro"],
+    ),
+    (
+        "marigold",
+        &["tomato", "pepper", "potato", "Cucurbitaceae", "Fabaceae"],
+        &[],
+    ),
+    (
+        "nasturtium",
+        &["Brassicaceae", "Cucurbitaceae", "tomato"],
+        &[],
+    ),
+    ("sunflower", &["cucumber"], &["potato"]),
+    ("borage", &["tomato", "strawberry", "Cucurbitaceae"], &[]),
+    (
+        "asparagus",
+        &["tomato", "parsley", "basil"],
+        &["onion", "garlic"],
+    ),
+    ("celery", &["Brassicaceae", "Fabaceae", "tomato"], &[]),
+    (
+        "Fabaceae",
+        &["corn", "Cucurbitaceae", "carrot", "radish", "marigold"],
+        &["Amaryllidaceae"],
+    ),
+    (
+        "Amaryllidaceae",
+        &[
+            "carrot",
+            "tomato",
+            "beet",
+            "lettuce",
+            "pepper",
+            "strawberry",
+            "Brassicaceae",
+        ],
+        &["Fabaceae", "sage"],
+    ),
+    (
+        "Brassicaceae",
+        &[
+            "dill", "onion", "beet", "celery", "rosemary", "sage", "thyme", "mint",
+        ],
+        &["strawberry", "tomato"],
+    ),
+    (
+        "Cucurbitaceae",
+        &["corn", "Fabaceae", "radish", "nasturtium", "marigold"],
+        &["potato", "sage"],
+    ),
+    ("Apiaceae", &["Amaryllidaceae", "rosemary", "sage"], &[]),
+    ("Lamiaceae", &["Brassicaceae", "carrot"], &[]),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Relationship {
+    Good,
+    Bad,
+}
+
+// Whether a plant answers to a table key: its family, or its name or title as a word,
+// so "Tomatoes" and "Cherokee Purple Tomato Seeds" are both tomatoes
+fn has_key(name: &str, info: &PlantInfo, key: &str) -> bool {
+    if key.starts_with(char::is_uppercase) {
+        return info
+            .family
+            .as_deref()
+            .is_some_and(|family| family.trim().eq_ignore_ascii_case(key));
+    }
+    let text = format!("{} {}", name, info.title.as_deref().unwrap_or("")).to_lowercase();
+    text.split(|c: char| !c.is_alphanumeric()).any(|word| {
+        word == key || word.strip_suffix('s') == Some(key) || word.strip_suffix("es") == Some(key)
+    })
+}
+
+fn keys(name: &str, info: &PlantInfo) -> Vec<&'static str> {
+    let mut keys: Vec<&str> = COMPANIONS
+        .iter()
+        .flat_map(|(key, good, bad)| std::iter::once(key).chain(good.iter()).chain(bad.iter()))
+        .copied()
+        .filter(|key| has_key(name, info, key))
+        .collect();
+    keys.sort_unstable();
+    keys.dedup();
+    keys
+}
+
+fn listed(a: &[&str], b: &[&str], pick: fn(&Companion) -> &[&str]) -> bool {
+    COMPANIONS.iter().any(|entry| {
+        (a.contains(&entry.0) && pick(entry).iter().any(|key| b.contains(key)))
+            || (b.contains(&entry.0) && pick(entry).iter().any(|key| a.contains(key)))
+    })
+}
+
+// How two plants get along; a bad pairing outweighs a good one
+pub fn relationship(a: (&str, &PlantInfo), b: (&str, &PlantInfo)) -> Option<Relationship> {
+    let a = keys(a.0, a.1);
+    let b = keys(b.0, b.1);
+    if listed(&a, &b, |entry| entry.2) {
+        Some(Relationship::Bad)
+    } else if listed(&a, &b, |entry| entry.1) {
+        Some(Relationship::Good)
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Neighbors {
+    pub good: Vec<String>,
+    pub bad: Vec<String>,
+}
+
+// Each plant's good and bad neighbors among the others
+pub fn find_neighbors(plants: &[(&str, &PlantInfo)]) -> Vec<Neighbors> {
+    plants
+        .iter()
+        .enumerate()
+        .map(|(i, plant)| {
+            let mut neighbors = Neighbors::default();
+            for (j, other) in plants.iter().enumerate() {
+                match relationship(*plant, *other) {
+                    _ if i == j => {}
+                    Some(Relationship::Good) => neighbors.good.push(other.0.to_string()),
+                    Some(Relationship::Bad) => neighbors.bad.push(other.0.to_string()),
+                    None => {}
+                }
+            }
+            neighbors
+        })
+        .collect()
+}
+
+// Record on each exported plant its good and bad neighbors among the rest of the export
+pub fn mark_neighbors(rows: &mut [ExportRow]) {
+    let indices: Vec<usize> = (0..rows.len())
+        .filter(|&i| rows[i].plant.is_some())
+        .collect();
+    let plants: Vec<(&str, &PlantInfo)> = indices
+        .iter()
+        .filter_map(|&i| Some((rows[i].input.plant_name, &rows[i].plant.as_ref()?.info)))
+        .collect();
+    let neighbors = find_neighbors(&plants);
+
+    for (i, neighbors) in indices.into_iter().zip(neighbors) {
+        if let Some(plant) = &mut rows[i].plant {
+            plant.neighbors = neighbors;
+        }
+    }
+}
+
+// Print good and bad neighbors for every plant in the collection, or for just one
+pub fn print_companions(json_dir: &str, plant: Option<&str>) -> Result<()> {
+    let plants = load_json_dir(json_dir)?;
+    let plants: Vec<(&str, &PlantInfo)> = plants
+        .iter()
+        .map(|(name, info)| (name.as_str(), info))
+        .collect();
+    let neighbors = find_neighbors(&plants);
+
+    if let Some(wanted) = plant {
+        if !plants
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case(wanted))
+        {
+            bail!("No plant named {} in {}", wanted, json_dir);
+        }
+    }
+
+    for ((name, _), neighbors) in plants.iter().zip(&neighbors) {
+        match plant {
+            Some(wanted) if !name.eq_ignore_ascii_case(wanted) => continue,
+            // Without a plant named, skip the ones with nothing to say
+            None if neighbors == &Neighbors::default() => continue,
+            _ => {}
+        }
+        println!("{}", name);
+        for (label, list) in [("Good", &neighbors.good), ("Bad", &neighbors.bad)] {
+            if !list.is_empty() {
+                println!("  {}: {}", label, list.join(", "));
+            }
+        }
+        if neighbors == &Neighbors::default() {
+            println!("  No known companions in this collection");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plant(title: &str, family: &str) -> PlantInfo {
+        PlantInfo {
+            title: Some(title.to_string()),
+            family: Some(family.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_relationship() {
+        let tomato = plant("Cherokee Purple Tomato Seeds", "Solanaceae");
+        let basil = plant("Genovese Basil", "Lamiaceae");
+        let bean = plant("Blue Lake Pole Bean", "Fabaceae");
+        let onion = plant("Walla Walla Onion", "Amaryllidaceae");
+        let kale = plant("Lacinato Kale", "Brassicaceae");
+
+        assert_eq!(
+            relationship(("Tomato", &tomato), ("Basil", &basil)),
+            Some(Relationship::Good)
+        );
+        assert_eq!(
+            relationship(("Basil", &basil), ("Tomato", &tomato)),
+            Some(Relationship::Good)
+        );
+        assert_eq!(
+            relationship(("Bean", &bean), ("Onion", &onion)),
+            Some(Relationship::Bad)
+        );
+        assert_eq!(
+            relationship(("Kale", &kale), ("Tomatoes", &tomato)),
+            Some(Relationship::Bad)
+        );
+        assert_eq!(relationship(("Bean", &bean), ("Basil", &basil)), None);
+
+        let neighbors = find_neighbors(&[("Tomato", &tomato), ("Basil", &basil), ("Kale", &kale)]);
+        assert_eq!(neighbors[0].good, vec!["Basil"]);
+        assert_eq!(neighbors[0].bad, vec!["Kale"]);
+    }
+}
@@ -0,0 +1,150 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use std::fs;
+
+use crate::names::PlantIndex;
+use crate::{load_json_dir, PlantInfo};
+
+// Bump when the schema below changes
+const SCHEMA_VERSION: i32 = 1;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS plants (
+    name TEXT PRIMARY KEY,
+    url TEXT NOT NULL,
+    title TEXT,
+    description TEXT,
+    days_to_maturity TEXT,
+    family TEXT,
+    plant_type TEXT,
+    native TEXT,
+    hardiness TEXT,
+    exposure TEXT,
+    plant_dimensions TEXT,
+    variety_info TEXT,
+    attributes TEXT,
+    when_to_sow_outside TEXT,
+    when_to_start_inside TEXT,
+    days_to_emerge TEXT,
+    seed_depth TEXT,
+    seed_spacing TEXT,
+    row_spacing TEXT,
+    thinning TEXT,
+    rating REAL,
+    votes INTEGER,
+    -- The complete JSON record, so fields without a column are never lost
+    data TEXT NOT NULL,
+    source_file TEXT,
+    scraped_at TEXT
+);
+";
+
+pub fn open(db_path: &str) -> Result<Connection> {
+    let conn =
+        Connection::open(db_path).context(format!("Failed to open database: {}", db_path))?;
+    conn.execute_batch(SCHEMA)?;
+    conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+    Ok(conn)
+}
+
+pub fn upsert_plant(
+    conn: &Connection,
+    name: &str,
+    info: &PlantInfo,
+    source_file: Option<&str>,
+    scraped_at: Option<DateTime<Utc>>,
+) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO plants (
+            name, url, title, description, days_to_maturity, family, plant_type, native,
+            hardiness, exposure, plant_dimensions, variety_info, attributes,
+            when_to_sow_outside, when_to_start_inside, days_to_emerge, seed_depth,
+            seed_spacing, row_spacing, thinning, rating, votes, data, source_file, scraped_at
+        ) VALUES (
+            ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
+            ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25
+        )",
+        params![
+            name,
+            info.url,
+            info.title,
+            info.description,
+            info.days_to_maturity,
+            info.family,
+            info.plant_type,
+            info.native,
+            info.hardiness,
+            info.exposure,
+            info.plant_dimensions,
+            info.variety_info,
+            info.attributes,
+            info.when_to_sow_outside,
+            info.when_to_start_inside,
+            info.days_to_emerge,
+            info.seed_depth,
+            info.seed_spacing,
+            info.row_spacing,
+            info.thinning,
+            info.rating,
+            info.votes,
+            serde_json::to_string(info)?,
+            source_file,
+            scraped_at.map(|t| t.to_rfc3339()),
+        ],
+    )?;
+    Ok(())
+}
+
+// Import every JSON file in a results directory into the database
+pub fn migrate_json_dir(json_dir: &str, db_path: &str) -> Result<()> {
+    let mut conn = open(db_path)?;
+    let plants = load_json_dir(json_dir)?;
+    let index = PlantIndex::load(json_dir)?;
+
+    let tx = conn.transaction()?;
+    for (name, info) in &plants {
+        let source_file = index.path_for(name);
+        // Older files predate scrape metadata, so fall back to their modification time
+        let scraped_at = info.scraped_at.or_else(|| {
+            fs::metadata(&source_file)
+                .and_then(|m| m.modified())
+                .ok()
+                .map(DateTime::<Utc>::from)
+        });
+        upsert_plant(&tx, name, info, Some(&source_file), scraped_at)
+            .context(format!("Failed to import {}", name))?;
+    }
+    tx.commit()?;
+
+    println!(
+        "Imported {} plants from {} into {}",
+        plants.len(),
+        json_dir,
+        db_path
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upsert_plant_replaces_existing_row() {
+        let conn = open(":memory:").unwrap();
+        let mut info: PlantInfo =
+            serde_json::from_str(r#"{"url": "http://example.com", "family": "Apiaceae"}"#).unwrap();
+        upsert_plant(&conn, "Carrot", &info, None, None).unwrap();
+        info.family = Some("Umbelliferae".to_string());
+        upsert_plant(&conn, "Carrot", &info, None, None).unwrap();
+
+        let (count, family): (i64, String) = conn
+            .query_row("SELECT COUNT(*), MAX(family) FROM plants", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(family, "Umbelliferae");
+    }
+}
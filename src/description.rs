@@ -0,0 +1,170 @@
+// The product description as Markdown, keeping the paragraphs, line breaks, and lists that
+// the plain-text description flattens
+use ego_tree::NodeRef;
+use scraper::{ElementRef, Node};
+
+// Collapse runs of whitespace to one space, as a browser shows inline text
+fn collapse(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            space = true;
+            continue;
+        }
+        if space {
+            out.push(' ');
+            space = false;
+        }
+        out.push(c);
+    }
+    if space {
+        out.push(' ');
+    }
+    out
+}
+
+fn is_list(element: ElementRef) -> bool {
+    matches!(element.value().name(), "ul" | "ol")
+}
+
+fn is_block(element: ElementRef) -> bool {
+    is_list(element)
+        || matches!(
+            element.value().name(),
+            "p" | "div" | "section" | "blockquote" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6"
+        )
+}
+
+// A node's text with its inline formatting; lists are left to list()
+fn inline(node: NodeRef<Node>) -> String {
+    if let Node::Text(text) = node.value() {
+        return collapse(text);
+    }
+    let Some(element) = ElementRef::wrap(node) else {
+        return String::new();
+    };
+    if is_list(element) {
+        return String::new();
+    }
+    let inner: String = element.children().map(inline).collect();
+    let trimmed = inner.trim();
+    match element.value().name() {
+        "br" => "\n".to_string(),
+        "strong" | "b" if !trimmed.is_empty() => format!("**{}**", trimmed),
+        "em" | "i" if !trimmed.is_empty() => format!("*{}*", trimmed),
+        "a" if !trimmed.is_empty() => match element.value().attr("href") {
+            Some(href) => format!("[{}]({})", trimmed, href),
+            None => inner,
+        },
+        _ => inner,
+    }
+}
+
+// Inline text as a paragraph of trimmed lines, or None when there's nothing to show
+fn paragraph(text: &str) -> Option<String> {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+// A list's items, one per line, with nested lists indented under their item
+fn list(element: ElementRef, depth: usize, lines: &mut Vec<String>) {
+    let ordered = element.value().name() == "ol";
+    let items = element
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter(|child| child.value().name() == "li");
+    for (i, item) in items.enumerate() {
+        let marker = if ordered {
+            format!("{}.", i + 1)
+        } else {
+            "-".to_string()
+        };
+        let text: String = item.children().map(inline).collect();
+        let text = paragraph(&text).unwrap_or_default().replace('\n', " ");
+        lines.push(format!("{}{} {}", "  ".repeat(depth), marker, text));
+        for nested in item.children().filter_map(ElementRef::wrap) {
+            if is_list(nested) {
+                list(nested, depth + 1, lines);
+            }
+        }
+    }
+}
+
+fn push_paragraph(text: &mut String, blocks: &mut Vec<String>) {
+    blocks.extend(paragraph(text));
+    text.clear();
+}
+
+fn collect_blocks(element: ElementRef, blocks: &mut Vec<String>) {
+    let mut text = String::new();
+    for child in element.children() {
+        let Some(child) = ElementRef::wrap(child).filter(|child| is_block(*child)) else {
+            text.push_str(&inline(child));
+            continue;
+        };
+        push_paragraph(&mut text, blocks);
+        let name = child.value().name();
+        if is_list(child) {
+            let mut lines = Vec::new();
+            list(child, 0, &mut lines);
+            if !lines.is_empty() {
+                blocks.push(lines.join("\n"));
+            }
+        } else if let Some(level) = name.strip_prefix('h').and_then(|n| n.parse().ok()) {
+            let heading: String = child.children().map(inline).collect();
+            if let Some(heading) = paragraph(&heading) {
+                blocks.push(format!(
+                    "{} {}",
+                    "#".repeat(level),
+                    heading.replace('\n', " ")
+                ));
+            }
+        } else {
+            collect_blocks(child, blocks);
+        }
+    }
+    push_paragraph(&mut text, blocks);
+}
+
+// The element's contents as Markdown, or None if it has no text
+pub fn to_markdown(element: ElementRef) -> Option<String> {
+    let mut blocks = Vec::new();
+    collect_blocks(element, &mut blocks);
+    (!blocks.is_empty()).then(|| blocks.join("\n\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scraper::Html;
+
+    #[test]
+    fn test_to_markdown() {
+        let html = Html::parse_fragment(
+            "<div>
+              <p>An <b>heirloom</b> from
+                 Massachusetts.<br>Stores well.</p>
+              <ul><li>Sweet</li><li>Crack <em>resistant</em><ol><li>mostly</li></ol></li></ul>
+              <h3>Tips</h3>Sow <a href=\"/sowing\">thinly</a>.
+            </div>",
+        );
+        let div = html
+            .root_element()
+            .first_child()
+            .and_then(ElementRef::wrap)
+            .unwrap();
+        assert_eq!(
+            to_markdown(div).as_deref(),
+            Some(
+                "An **heirloom** from Massachusetts.\nStores well.\n\n\
+                 - Sweet\n- Crack *resistant*\n  1. mostly\n\n\
+                 ### Tips\n\nSow [thinly](/sowing)."
+            )
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use std::fs;
+
+use crate::schema::parse_plant_json;
+use crate::PlantInfo;
+
+// Bookkeeping fields that differ between any two scrapes
+const IGNORED_FIELDS: [&str; 7] = [
+    "schema_version",
+    "scraped_at",
+    "http_status",
+    "parser_version",
+    "content_hash",
+    "etag",
+    "last_modified",
+];
+
+#[derive(Debug, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+fn fields(info: &PlantInfo) -> Map<String, Value> {
+    match serde_json::to_value(info) {
+        Ok(Value::Object(fields)) => fields,
+        _ => Map::new(),
+    }
+}
+
+// Every field whose value was added, removed, or changed between two scrapes
+pub fn diff_fields(old: &PlantInfo, new: &PlantInfo) -> Vec<FieldChange> {
+    let old = fields(old);
+    let new = fields(new);
+    let names: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+
+    names
+        .into_iter()
+        .filter(|name| !IGNORED_FIELDS.contains(&name.as_str()))
+        .filter_map(|name| {
+            let (old, new) = (old.get(name), new.get(name));
+            (old != new).then(|| FieldChange {
+                field: name.clone(),
+                old: old.cloned(),
+                new: new.cloned(),
+            })
+        })
+        .collect()
+}
+
+fn display_value(value: &Option<Value>) -> String {
+    match value {
+        Some(Value::String(text)) => text.clone(),
+        Some(value) => value.to_string(),
+        None => "(none)".to_string(),
+    }
+}
+
+fn read_plant(path: &str) -> Result<PlantInfo> {
+    let content = fs::read_to_string(path).context(format!("Failed to read {}", path))?;
+    parse_plant_json(&content).context(format!("Failed to parse {}", path))
+}
+
+// Print a field-level diff between two scrapes of the same plant
+pub fn diff_files(old_file: &str, new_file: &str) -> Result<()> {
+    let old = read_plant(old_file)?;
+    let new = read_plant(new_file)?;
+    let changes = diff_fields(&old, &new);
+
+    if changes.is_empty() {
+        println!("No differences between {} and {}", old_file, new_file);
+        return Ok(());
+    }
+
+    println!("--- {}", old_file);
+    println!("+++ {}", new_file);
+    for change in &changes {
+        println!("{}:", change.field);
+        if change.old.is_some() {
+            println!("  - {}", display_value(&change.old));
+        }
+        if change.new.is_some() {
+            println!("  + {}", display_value(&change.new));
+        }
+    }
+    println!("\n{} field(s) changed", changes.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_fields() {
+        let old: PlantInfo = serde_json::from_str(
+            r#"{"url": "u", "days_to_maturity": "65 days", "family": "Apiaceae", "http_status": 200}"#,
+        )
+        .unwrap();
+        let new: PlantInfo = serde_json::from_str(
+            r#"{"url": "u", "days_to_maturity": "70 days", "votes": 3, "http_status": 304}"#,
+        )
+        .unwrap();
+
+        let changes = diff_fields(&old, &new);
+        let names: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(names, vec!["days_to_maturity", "family", "votes"]);
+        assert_eq!(changes[1].new, None);
+        assert_eq!(changes[2].old, None);
+    }
+}
@@ -0,0 +1,162 @@
+// A weekly email of the garden to-do list and the scrapes that need another look, for cron
+use anyhow::{Context, Result};
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use crate::notify::{send_email, SmtpSettings};
+use crate::tasks::tasks_for_week;
+use crate::{load_json_dir, names, schema, PlantInfo, REMAINING_FILE};
+
+// Plants whose last scrape didn't give usable data: JSON files that no longer parse, and the
+// rows a batch stopped short of after repeated blocks
+fn failed_scrapes(json_dir: &str) -> Result<Vec<String>> {
+    let mut failed = Vec::new();
+    for entry in
+        fs::read_dir(json_dir).context(format!("Failed to read directory: {}", json_dir))?
+    {
+        let path = entry?.path();
+        let is_json = path.extension().and_then(|ext| ext.to_str()) == Some("json");
+        if !is_json || path.ends_with(names::INDEX_FILE) {
+            continue;
+        }
+        let parses = fs::read_to_string(&path)
+            .ok()
+            .is_some_and(|content| schema::parse_plant_json(&content).is_ok());
+        if let (false, Some(stem)) = (parses, path.file_stem().and_then(|stem| stem.to_str())) {
+            failed.push(format!("{} (unreadable JSON)", stem));
+        }
+    }
+
+    let remaining_path = Path::new(json_dir).join(REMAINING_FILE);
+    if remaining_path.exists() {
+        let mut reader = csv::Reader::from_path(&remaining_path)
+            .context(format!("Failed to read {}", remaining_path.display()))?;
+        for record in reader.records() {
+            if let Some(name) = record?.get(0).filter(|name| !name.is_empty()) {
+                failed.push(format!("{} (blocked, in {})", name, REMAINING_FILE));
+            }
+        }
+    }
+    failed.sort();
+    Ok(failed)
+}
+
+// The subject and plain text body of the digest
+fn build_digest(
+    plants: &[(String, PlantInfo)],
+    failed: &[String],
+    week_start: NaiveDate,
+    frost_date: NaiveDate,
+    stale_days: i64,
+    now: DateTime<Utc>,
+) -> (String, String) {
+    let week_end = week_start + Days::new(6);
+    let subject = format!(
+        "Garden tasks for {} to {}",
+        week_start.format("%b %-d"),
+        week_end.format("%b %-d, %Y")
+    );
+
+    let mut body = String::new();
+    let tasks = tasks_for_week(plants, week_start, frost_date);
+    if tasks.is_empty() {
+        body.push_str("Nothing to sow or transplant this week.\n");
+    }
+    let mut current = None;
+    for task in &tasks {
+        if current != Some(task.kind) {
+            if current.is_some() {
+                body.push('\n');
+            }
+            let _ = writeln!(body, "{}:", task.kind);
+            current = Some(task.kind);
+        }
+        let _ = writeln!(body, "  - {} ({})", task.plant, task.when());
+    }
+
+    let stale: Vec<(&str, i64)> = plants
+        .iter()
+        .filter_map(|(name, info)| {
+            let age = info.scrape_age_days(now)?;
+            (age > stale_days).then_some((name.as_str(), age))
+        })
+        .collect();
+    if !stale.is_empty() {
+        let _ = writeln!(body, "\nStale scrapes (over {} days old):", stale_days);
+        for (name, age) in stale {
+            let _ = writeln!(body, "  - {} ({} days)", name, age);
+        }
+    }
+
+    if !failed.is_empty() {
+        body.push_str("\nFailed scrapes:\n");
+        for name in failed {
+            let _ = writeln!(body, "  - {}", name);
+        }
+    }
+    (subject, body)
+}
+
+// Email the digest, or print it when there's no SMTP server to send it through
+pub fn send_digest(
+    json_dir: &str,
+    week_start: NaiveDate,
+    frost_date: NaiveDate,
+    stale_days: i64,
+    smtp: Option<&SmtpSettings>,
+) -> Result<()> {
+    let plants = load_json_dir(json_dir)?;
+    let failed = failed_scrapes(json_dir)?;
+    let (subject, body) = build_digest(
+        &plants,
+        &failed,
+        week_start,
+        frost_date,
+        stale_days,
+        Utc::now(),
+    );
+    match smtp {
+        Some(smtp) => {
+            send_email(smtp, &subject, body)?;
+            println!("Sent \"{}\" to {}", subject, smtp.to);
+        }
+        None => println!("Subject: {}\n\n{}", subject, body),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_build_digest() {
+        let now = Utc.with_ymd_and_hms(2025, 4, 21, 12, 0, 0).unwrap();
+        let carrot = PlantInfo {
+            when_to_sow_outside: Some(
+                "2 to 4 weeks before your average last frost date".to_string(),
+            ),
+            scraped_at: Some(now - chrono::Duration::days(200)),
+            ..Default::default()
+        };
+        let plants = vec![("Carrot".to_string(), carrot)];
+        let failed = vec!["Tomato (unreadable JSON)".to_string()];
+        let week_start = NaiveDate::from_ymd_opt(2025, 4, 21).unwrap();
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+
+        let (subject, body) = build_digest(&plants, &failed, week_start, frost_date, 90, now);
+        assert_eq!(subject, "Garden tasks for Apr 21 to Apr 27, 2025");
+        assert_eq!(
+            body,
+            "Sow outside:\n  - Carrot (Apr 12 - Apr 26)\n\
+             \nStale scrapes (over 90 days old):\n  - Carrot (200 days)\n\
+             \nFailed scrapes:\n  - Tomato (unreadable JSON)\n"
+        );
+
+        let (_, body) = build_digest(&plants, &[], week_start, frost_date, 365, now);
+        assert!(!body.contains("Stale"));
+    }
+}
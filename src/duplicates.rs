@@ -0,0 +1,131 @@
+// Spotting the same variety bought from two vendors, so a plan doesn't count it twice
+use std::collections::HashSet;
+use tracing::warn;
+
+use crate::{ExportRow, PlantInfo};
+
+// Words that describe the packet or how it was grown rather than the variety itself
+const PACKET_WORDS: [&str; 14] = [
+    "seed",
+    "seeds",
+    "organic",
+    "heirloom",
+    "hybrid",
+    "f1",
+    "pelleted",
+    "packet",
+    "large",
+    "bulk",
+    "non",
+    "gmo",
+    "open",
+    "pollinated",
+];
+
+// The title's words, sorted, without packet descriptions, e.g. "126 carrot danvers"
+fn variety_key(info: &PlantInfo) -> Option<String> {
+    let title = info
+        .title
+        .as_deref()?
+        .to_lowercase()
+        .replace(['\'', '’'], "");
+    let mut words: Vec<&str> = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty() && !PACKET_WORDS.contains(word))
+        .collect();
+    words.sort_unstable();
+    words.dedup();
+    (!words.is_empty()).then(|| words.join(" "))
+}
+
+// Genus and species only, so "Daucus carota var. sativus" matches "Daucus carota"
+fn species(info: &PlantInfo) -> Option<String> {
+    let name = info.botanical_name.as_deref()?.to_lowercase();
+    Some(
+        name.split_whitespace()
+            .take(2)
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+fn same_variety(a: &PlantInfo, b: &PlantInfo) -> bool {
+    let species_differ = matches!((species(a), species(b)), (Some(a), Some(b)) if a != b);
+    !species_differ && variety_key(a).is_some() && variety_key(a) == variety_key(b)
+}
+
+// Record on each plant the other plants in the export that look like the same variety
+pub fn mark_duplicates(rows: &mut [ExportRow]) {
+    let names: Vec<Vec<String>> = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let Some(plant) = &row.plant else {
+                return Vec::new();
+            };
+            rows.iter()
+                .enumerate()
+                .filter(|(j, other)| {
+                    *j != i
+                        && other
+                            .plant
+                            .as_ref()
+                            .is_some_and(|other| same_variety(&plant.info, &other.info))
+                })
+                .map(|(_, other)| other.input.plant_name.to_string())
+                .collect()
+        })
+        .collect();
+
+    // Warn once per group rather than once per member
+    let mut warned = HashSet::new();
+    for (row, duplicate_of) in rows.iter_mut().zip(names) {
+        if let Some(plant) = &mut row.plant {
+            if !duplicate_of.is_empty() && !warned.contains(row.input.plant_name) {
+                warned.extend(duplicate_of.iter().cloned());
+                warn!(
+                    "{} looks like the same variety as {}",
+                    row.input.plant_name,
+                    duplicate_of.join(", ")
+                );
+            }
+            plant.duplicate_of = duplicate_of;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plant(title: &str, botanical_name: Option<&str>) -> PlantInfo {
+        PlantInfo {
+            title: Some(title.to_string()),
+            botanical_name: botanical_name.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_same_variety() {
+        assert!(same_variety(
+            &plant("Danvers 126 Carrot Seeds", Some("Daucus carota")),
+            &plant("Carrot, 'Danvers 126' (Organic)", None),
+        ));
+        assert!(same_variety(
+            &plant(
+                "Danvers 126 Carrot Seeds",
+                Some("Daucus carota var. sativus")
+            ),
+            &plant("Danvers 126 Carrot", Some("Daucus carota")),
+        ));
+        assert!(!same_variety(
+            &plant("Danvers 126 Carrot Seeds", None),
+            &plant("Scarlet Nantes Carrot Seeds", None),
+        ));
+        assert!(!same_variety(
+            &plant("Sweet Basil", Some("Ocimum basilicum")),
+            &plant("Sweet Basil", Some("Ocimum tenuiflorum")),
+        ));
+    }
+}
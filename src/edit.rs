@@ -0,0 +1,177 @@
+// Correcting a stored plant by hand in the user's editor, checked before it's saved
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Map, Value};
+use std::env;
+use std::fs;
+use std::process::Command;
+
+use crate::interactive::ask;
+use crate::names::PlantIndex;
+use crate::schema;
+use crate::show::find_plant;
+use crate::{OutputFormat, DERIVED_FIELDS};
+
+// Fields that describe the scrape rather than the plant; they're left out of the file being
+// edited and put back as they were
+const METADATA_FIELDS: [&str; 8] = [
+    "schema_version",
+    "scraped_at",
+    "source_domain",
+    "http_status",
+    "parser_version",
+    "content_hash",
+    "etag",
+    "last_modified",
+];
+
+// $VISUAL or $EDITOR, which may carry its own arguments such as "code --wait"
+fn editor_command() -> Vec<String> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    editor.split_whitespace().map(String::from).collect()
+}
+
+// Parsed from the text fields, so they're left out of the editor and parsed again on save
+fn is_derived(field: &str) -> bool {
+    DERIVED_FIELDS
+        .iter()
+        .any(|(_, derived)| derived.contains(&field))
+}
+
+// The stored record split into what's shown in the editor and the metadata kept aside, with
+// the derived fields dropped
+fn split_metadata(mut record: Map<String, Value>) -> (Map<String, Value>, Map<String, Value>) {
+    record.retain(|field, _| !is_derived(field));
+    record
+        .into_iter()
+        .partition(|(field, _)| !METADATA_FIELDS.contains(&field.as_str()))
+}
+
+// The edited fields with the metadata put back, checked against the plant schema, and the
+// derived fields parsed from the edited text
+fn merge_edit(edited: &str, metadata: &Map<String, Value>) -> Result<Value> {
+    let mut record: Map<String, Value> =
+        serde_json::from_str(edited).context("The edited file isn't a JSON object")?;
+    for field in METADATA_FIELDS {
+        record.remove(field);
+    }
+    record.extend(metadata.clone());
+    let info = schema::parse_plant_json(&Value::Object(record).to_string())?;
+    Ok(serde_json::to_value(info)?)
+}
+
+pub fn edit_plant(json_dir: &str, plant: &str, output: OutputFormat) -> Result<()> {
+    let (name, _) = find_plant(json_dir, plant)?;
+    let path = PlantIndex::load(json_dir)?.path_for(&name);
+    let content = fs::read_to_string(&path).context(format!("Failed to read {}", path))?;
+    let record = match serde_json::from_str(&content)? {
+        Value::Object(record) => record,
+        _ => bail!("{} isn't a JSON object", path),
+    };
+    let (editable, metadata) = split_metadata(record);
+    let original = serde_json::to_string_pretty(&editable)? + "\n";
+
+    let scratch = env::temp_dir().join(format!("seed-scraper-edit-{}.json", std::process::id()));
+    fs::write(&scratch, &original).context(format!("Failed to write {}", scratch.display()))?;
+    let editor = editor_command();
+    let (program, args) = editor
+        .split_first()
+        .ok_or_else(|| anyhow!("$EDITOR is empty"))?;
+
+    let result = loop {
+        let status = Command::new(program)
+            .args(args)
+            .arg(&scratch)
+            .status()
+            .context(format!("Failed to run {}", program))?;
+        if !status.success() {
+            break Err(anyhow!("{} exited with {}; nothing saved", program, status));
+        }
+
+        let edited = fs::read_to_string(&scratch)?;
+        if edited == original {
+            output.print_text(&format!("No changes to {}", name));
+            output.print_json(&json!({"plant": name, "saved": false}))?;
+            break Ok(());
+        }
+        match merge_edit(&edited, &metadata) {
+            Ok(record) => {
+                fs::write(&path, serde_json::to_string_pretty(&record)?)
+                    .context(format!("Failed to write {}", path))?;
+                output.print_text(&format!("Saved {}", path));
+                output.print_json(&json!({"plant": name, "saved": true, "path": path}))?;
+                break Ok(());
+            }
+            Err(e) => {
+                eprintln!("{:#}", e);
+                let again = ask("Edit again? [Y/n]")?;
+                if again.is_some_and(|answer| answer.to_lowercase().starts_with('n')) {
+                    break Err(anyhow!("Discarded the changes to {}", name));
+                }
+            }
+        }
+    };
+    let _ = fs::remove_file(&scratch);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_edit_keeps_metadata() {
+        let record = json!({
+            "url": "http://example.com/carrot",
+            "family": "Apiaceae",
+            "parser_version": 8,
+            "scraped_at": "2025-01-01T00:00:00Z",
+            "days_to_maturity": "65 days",
+            "days_to_maturity_min": 65,
+            "days_to_maturity_max": 65,
+        });
+        let Value::Object(record) = record else {
+            unreachable!()
+        };
+        let (editable, metadata) = split_metadata(record);
+        assert!(!editable.contains_key("parser_version"));
+        assert!(!editable.contains_key("days_to_maturity_min"));
+
+        let edited = r#"{"url": "http://example.com/carrot", "family": "Umbellifers",
+            "days_to_maturity": "80 days", "parser_version": 1}"#;
+        let merged = merge_edit(edited, &metadata).unwrap();
+        assert_eq!(merged["family"], "Umbellifers");
+        assert_eq!(merged["days_to_maturity_min"], 80);
+        assert_eq!(merged["days_to_maturity_max"], 80);
+        assert_eq!(merged["parser_version"], 8);
+        assert_eq!(merged["scraped_at"], "2025-01-01T00:00:00Z");
+
+        // Designations and resistance codes are redetected from the edited title and text
+        let record = json!({
+            "url": "http://example.com/tomato",
+            "title": "Tomato",
+            "description": "Disease resistance: TMV.",
+            "organic": false,
+            "heirloom": false,
+            "open_pollinated": false,
+            "hybrid": false,
+            "disease_resistance": [{"code": "TMV", "disease": "Tobacco mosaic virus"}],
+        });
+        let Value::Object(record) = record else {
+            unreachable!()
+        };
+        let (editable, tomato_metadata) = split_metadata(record);
+        assert!(!editable.contains_key("organic"));
+        assert!(!editable.contains_key("disease_resistance"));
+        let edited = r#"{"url": "http://example.com/tomato", "title": "Organic Tomato",
+            "description": "Disease resistance: V."}"#;
+        let merged = merge_edit(edited, &tomato_metadata).unwrap();
+        assert_eq!(merged["organic"], true);
+        assert_eq!(merged["heirloom"], false);
+        assert_eq!(merged["disease_resistance"][0]["code"], "V");
+
+        assert!(merge_edit(r#"{"url": 5}"#, &metadata).is_err());
+        assert!(merge_edit("not json", &metadata).is_err());
+    }
+}
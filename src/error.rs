@@ -0,0 +1,101 @@
+// The ways scraping and loading plants can fail, kept apart so callers can react to each one
+use regex::Regex;
+use scraper::Selector;
+use std::io;
+use std::sync::LazyLock;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ScrapingError {
+    #[error("Network error: {0}")]
+    Network(#[source] reqwest::Error),
+    // The host was too slow, rather than unreachable or refusing, so a later retry may work
+    #[error("Timed out: {0}")]
+    Timeout(String),
+    #[error("Access blocked by Cloudflare. Try again later or check if the URL is correct.")]
+    Blocked,
+    #[error("Parse error: {0}")]
+    Parse(String),
+    #[error("Failed to access {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+}
+
+impl From<reqwest::Error> for ScrapingError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            ScrapingError::Timeout(error.to_string())
+        } else {
+            ScrapingError::Network(error)
+        }
+    }
+}
+
+impl ScrapingError {
+    pub fn io(path: impl Into<String>) -> impl FnOnce(io::Error) -> Self {
+        let path = path.into();
+        move |source| ScrapingError::Io { path, source }
+    }
+}
+
+// A selector or regex compiled on first use, holding on to the reason when its pattern is invalid
+pub type Pattern<T> = LazyLock<Result<T, String>>;
+
+pub fn selector(css: &str) -> Result<Selector, String> {
+    Selector::parse(css).map_err(|e| format!("invalid selector {}: {}", css, e))
+}
+
+pub fn regex(pattern: &str) -> Result<Regex, String> {
+    Regex::new(pattern).map_err(|e| format!("invalid regex {}: {}", pattern, e))
+}
+
+pub fn compiled<T>(pattern: &'static Pattern<T>) -> Result<&'static T, ScrapingError> {
+    pattern
+        .as_ref()
+        .map_err(|reason| ScrapingError::Parse(reason.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{schema, PlantInfo};
+
+    #[test]
+    fn test_errors_by_kind() {
+        let blocked = "<title>Attention Required! | Cloudflare</title>";
+        assert!(matches!(
+            PlantInfo::from_html(blocked, "http://example.com".to_string()),
+            Err(ScrapingError::Blocked)
+        ));
+        assert!(matches!(
+            schema::parse_plant_json("[1, 2]"),
+            Err(ScrapingError::Parse(_))
+        ));
+        assert!(matches!(
+            crate::open_csv("/nonexistent/plants.csv"),
+            Err(ScrapingError::Io { .. })
+        ));
+
+        static INVALID: Pattern<Selector> = LazyLock::new(|| selector("div > > p"));
+        assert!(matches!(compiled(&INVALID), Err(ScrapingError::Parse(_))));
+    }
+
+    #[test]
+    fn test_slow_host_times_out() {
+        // Accepts the connection but never answers
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let request = crate::create_http_client()
+            .get(url)
+            .timeout(std::time::Duration::from_millis(200));
+        assert!(matches!(
+            crate::fetch_page(request),
+            Err(ScrapingError::Timeout(_))
+        ));
+    }
+}
@@ -0,0 +1,138 @@
+// Atom feed of newly scraped plants and the changes watch has seen, for following along
+// in a feed reader
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+use crate::calendar::event_slug;
+use crate::load_json_dir;
+use crate::watch::{detect_changes, load_history, PriceSnapshot};
+
+// Newest entries kept in the feed
+const MAX_ENTRIES: usize = 50;
+
+#[derive(Debug, PartialEq)]
+pub struct FeedEntry {
+    pub id: String,
+    pub title: String,
+    pub summary: String,
+    pub link: String,
+    pub updated: DateTime<Utc>,
+}
+
+// One entry for each snapshot that differed from the plant's previous one
+pub fn change_entries(history: &[PriceSnapshot]) -> Vec<FeedEntry> {
+    let mut previous: HashMap<&str, &PriceSnapshot> = HashMap::new();
+    let mut entries = Vec::new();
+    for snapshot in history {
+        if let Some(before) = previous.insert(&snapshot.name, snapshot) {
+            let changes: Vec<String> = detect_changes(before, snapshot)
+                .iter()
+                .map(|change| change.to_string())
+                .collect();
+            if !changes.is_empty() {
+                entries.push(FeedEntry {
+                    id: format!(
+                        "urn:seeds-scraper:{}:{}",
+                        event_slug(&snapshot.name),
+                        snapshot.checked_at.timestamp()
+                    ),
+                    title: format!("{}: {}", snapshot.name, changes.join(", ")),
+                    summary: changes.join("\n"),
+                    link: snapshot.url.clone(),
+                    updated: snapshot.checked_at,
+                });
+            }
+        }
+    }
+    entries
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn render_atom(title: &str, mut entries: Vec<FeedEntry>) -> String {
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.updated));
+    entries.truncate(MAX_ENTRIES);
+    let updated = entries.first().map_or_else(Utc::now, |entry| entry.updated);
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str(&format!("  <title>{}</title>\n", escape_xml(title)));
+    xml.push_str("  <id>urn:seeds-scraper:collection</id>\n");
+    xml.push_str(&format!("  <updated>{}</updated>\n", updated.to_rfc3339()));
+    xml.push_str("  <author><name>seeds-scraper</name></author>\n");
+    for entry in &entries {
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!("    <id>{}</id>\n", escape_xml(&entry.id)));
+        xml.push_str(&format!(
+            "    <title>{}</title>\n",
+            escape_xml(&entry.title)
+        ));
+        xml.push_str(&format!(
+            "    <link href=\"{}\"/>\n",
+            escape_xml(&entry.link)
+        ));
+        xml.push_str(&format!(
+            "    <updated>{}</updated>\n",
+            entry.updated.to_rfc3339()
+        ));
+        xml.push_str(&format!(
+            "    <summary>{}</summary>\n",
+            escape_xml(&entry.summary)
+        ));
+        xml.push_str("  </entry>\n");
+    }
+    xml.push_str("</feed>\n");
+    xml
+}
+
+// New plants, by when they were scraped, and the changes recorded in the price history
+pub fn collection_feed(json_dir: &str, history_file: &str) -> Result<String> {
+    let mut entries: Vec<FeedEntry> = load_json_dir(json_dir)?
+        .into_iter()
+        .filter_map(|(name, info)| {
+            let scraped_at = info.scraped_at?;
+            Some(FeedEntry {
+                id: format!("urn:seeds-scraper:{}:added", event_slug(&name)),
+                title: format!("New plant: {}", name),
+                summary: info.title.unwrap_or_else(|| name.clone()),
+                link: info.url,
+                updated: scraped_at,
+            })
+        })
+        .collect();
+    entries.extend(change_entries(&load_history(history_file)?));
+    Ok(render_atom("Seed collection updates", entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_change_entries() {
+        let snapshot = |day, price| PriceSnapshot {
+            name: "Beans & Peas".to_string(),
+            url: "http://example.com/beans".to_string(),
+            checked_at: Utc.with_ymd_and_hms(2025, 3, day, 12, 0, 0).unwrap(),
+            price: Some(price),
+            compare_at_price: None,
+            in_stock: Some(true),
+            sowing_info: None,
+        };
+        let history = [snapshot(1, 3.99), snapshot(2, 3.99), snapshot(3, 4.49)];
+        let entries = change_entries(&history);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].title, "Beans & Peas: price $3.99 -> $4.49");
+        assert_eq!(entries[0].id, "urn:seeds-scraper:beans-peas:1741003200");
+
+        let atom = render_atom("Updates", entries);
+        assert!(atom.contains("<title>Beans &amp; Peas: price $3.99 -&gt; $4.49</title>"));
+    }
+}
@@ -0,0 +1,245 @@
+// Saving product pages and what the parser makes of them, to grow the regression corpus
+use anyhow::{anyhow, Context, Result};
+use scraper::{Html, Selector};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+use tracing::warn;
+
+use crate::error::{compiled, selector, Pattern, ScrapingError};
+use crate::{create_http_client, fetch_page, page_request, PlantInfo};
+
+// Page furniture the parser never reads; JSON scripts stay since variants and ratings live there
+const STRIPPED_ELEMENTS: &str = r#"script:not([type="application/json"]):not([type="application/ld+json"]), style, noscript, svg, iframe, link[rel="stylesheet"], link[rel="preload"]"#;
+
+// The page without scripts, styles, inline images, and comments, which are most of its size
+// and where any session tokens would be
+pub fn sanitize_html(html: &str) -> Result<String, ScrapingError> {
+    static STRIPPED: Pattern<Selector> = LazyLock::new(|| selector(STRIPPED_ELEMENTS));
+    let mut document = Html::parse_document(html);
+    let mut removed: Vec<_> = document
+        .select(compiled(&STRIPPED)?)
+        .map(|element| element.id())
+        .collect();
+    removed.extend(
+        document
+            .tree
+            .nodes()
+            .filter(|node| node.value().is_comment())
+            .map(|node| node.id()),
+    );
+    for id in removed {
+        if let Some(mut node) = document.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+    Ok(document.html())
+}
+
+// What a fixture is expected to parse to, without the fields that depend on when it was fetched
+pub fn expected_json(html: &str, url: &str) -> Result<Value> {
+    let info = PlantInfo::from_html(html, url.to_string())?;
+    // Through text, so f32 fields compare equal to the same values read back from a file
+    Ok(serde_json::from_str(&serde_json::to_string(&info)?)?)
+}
+
+// A file name for the fixture from the last part of the URL's path, e.g. danvers-126-carrot-seeds
+fn fixture_name(url: &str) -> Option<String> {
+    let url = reqwest::Url::parse(url).ok()?;
+    let slug = url
+        .path_segments()?
+        .rev()
+        .find(|segment| !segment.is_empty())?;
+    let slug = slug.strip_suffix(".html").unwrap_or(slug);
+    let name: String = slug
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    Some(name)
+}
+
+// Fetch a page and save it as <name>.html beside the <name>.json it parses to. The page is
+// sanitized first; if that changes what the parser reads, it's only saved as fetched when
+// `raw` allows it.
+pub fn record_fixture(url: &str, fixture_dir: &str, name: Option<&str>, raw: bool) -> Result<()> {
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => fixture_name(url).ok_or_else(|| anyhow!("Give a --name for {}", url))?,
+    };
+    let page = fetch_page(page_request(&create_http_client(), url))?;
+    if !(200..300).contains(&page.status) {
+        return Err(anyhow!("{} returned HTTP {}", url, page.status));
+    }
+
+    let expected = expected_json(&page.body, url)?;
+    let mut html = sanitize_html(&page.body)?;
+    // Only keep the smaller page if the parser can't tell the difference
+    if expected_json(&html, url)? != expected {
+        if !raw {
+            return Err(anyhow!(
+                "Sanitizing {} changed what the parser reads; give --raw to save the page as \
+                 fetched, after checking it for session tokens",
+                url
+            ));
+        }
+        warn!("Sanitizing changed what the parser reads, so saving the page as fetched");
+        html = page.body;
+    }
+
+    fs::create_dir_all(fixture_dir)
+        .context(format!("Failed to create directory: {}", fixture_dir))?;
+    let dir = Path::new(fixture_dir);
+    let html_path = dir.join(format!("{}.html", name));
+    let json_path = dir.join(format!("{}.json", name));
+    fs::write(&html_path, html).context(format!("Failed to write {}", html_path.display()))?;
+    fs::write(&json_path, serde_json::to_string_pretty(&expected)? + "\n")
+        .context(format!("Failed to write {}", json_path.display()))?;
+
+    println!(
+        "Recorded {} and {}",
+        html_path.display(),
+        json_path.display()
+    );
+    Ok(())
+}
+
+// A top-level field that parses differently from how it was recorded
+#[derive(Debug, PartialEq)]
+pub struct FieldDiff {
+    pub field: String,
+    pub expected: Value,
+    pub actual: Value,
+}
+
+pub fn diff_fields(expected: &Value, actual: &Value) -> Vec<FieldDiff> {
+    let empty = serde_json::Map::new();
+    let expected = expected.as_object().unwrap_or(&empty);
+    let actual = actual.as_object().unwrap_or(&empty);
+    let mut fields: Vec<&String> = expected.keys().chain(actual.keys()).collect();
+    fields.sort();
+    fields.dedup();
+
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let expected = expected.get(field).cloned().unwrap_or(Value::Null);
+            let actual = actual.get(field).cloned().unwrap_or(Value::Null);
+            (expected != actual).then(|| FieldDiff {
+                field: field.clone(),
+                expected,
+                actual,
+            })
+        })
+        .collect()
+}
+
+// Re-parse every <name>.html that has a recorded <name>.json, returning each fixture's name
+// with the fields that changed
+pub fn check_fixtures(fixture_dir: &str) -> Result<Vec<(String, Vec<FieldDiff>)>> {
+    let mut json_paths: Vec<_> = fs::read_dir(fixture_dir)
+        .context(format!("Failed to read directory: {}", fixture_dir))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+        .filter(|path| path.with_extension("html").exists())
+        .collect();
+    json_paths.sort();
+
+    let mut results = Vec::new();
+    for json_path in json_paths {
+        let name = json_path
+            .file_stem()
+            .map_or_else(String::new, |stem| stem.to_string_lossy().to_string());
+        let expected: Value = serde_json::from_str(&fs::read_to_string(&json_path)?)
+            .context(format!("Invalid expected JSON: {}", json_path.display()))?;
+        let html = fs::read_to_string(json_path.with_extension("html"))?;
+        let url = expected["url"].as_str().unwrap_or_default();
+        let actual = expected_json(&html, url)?;
+        results.push((name, diff_fields(&expected, &actual)));
+    }
+    Ok(results)
+}
+
+// Print which fixtures no longer parse as recorded. Returns the number that don't.
+pub fn test_parse(fixture_dir: &str) -> Result<usize> {
+    let results = check_fixtures(fixture_dir)?;
+    let mut failed = 0;
+    for (name, diffs) in &results {
+        if diffs.is_empty() {
+            println!("ok    {}", name);
+            continue;
+        }
+        failed += 1;
+        println!("FAIL  {}", name);
+        for diff in diffs {
+            println!(
+                "      {}: expected {}, got {}",
+                diff.field, diff.expected, diff.actual
+            );
+        }
+    }
+    println!(
+        "\n{} fixtures: {} passed, {} failed",
+        results.len(),
+        results.len() - failed,
+        failed
+    );
+    Ok(failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE_URL: &str = "https://www.botanicalinterests.com/products/danvers-126-carrot-seeds";
+
+    #[test]
+    fn test_sanitize_html_keeps_parsed_fields() {
+        let html = include_str!("../tests/fixtures/seed.html");
+        let sanitized = sanitize_html(html).unwrap();
+        assert!(sanitized.len() < html.len());
+        assert!(!sanitized.contains("<style"));
+        assert_eq!(
+            expected_json(&sanitized, PAGE_URL).unwrap(),
+            expected_json(html, PAGE_URL).unwrap()
+        );
+        assert_eq!(
+            fixture_name(PAGE_URL).as_deref(),
+            Some("danvers-126-carrot-seeds")
+        );
+    }
+
+    // Every recorded page still parses to what it did when it was recorded
+    #[test]
+    fn test_recorded_fixtures() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+        for (name, diffs) in check_fixtures(dir).unwrap() {
+            assert_eq!(diffs, vec![], "{} no longer parses as recorded", name);
+        }
+    }
+
+    #[test]
+    fn test_parse_snapshot() {
+        let html = include_str!("../tests/fixtures/seed.html");
+        insta::assert_json_snapshot!("danvers_126_carrot", expected_json(html, PAGE_URL).unwrap());
+    }
+
+    #[test]
+    fn test_diff_fields() {
+        let expected = serde_json::json!({"title": "Carrot", "family": "Apiaceae"});
+        let actual = serde_json::json!({"title": "Carrot", "rating": 4.5});
+        assert_eq!(
+            diff_fields(&expected, &actual)
+                .iter()
+                .map(|diff| diff.field.as_str())
+                .collect::<Vec<_>>(),
+            vec!["family", "rating"]
+        );
+    }
+}
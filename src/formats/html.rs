@@ -0,0 +1,156 @@
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::fs;
+
+use crate::{export_record, ExportRow, Units, CSV_HEADERS};
+
+// Columns shown in the table; every exported column is still embedded in the data
+const DISPLAY_COLUMNS: [&str; 11] = [
+    "Plant Name",
+    "Brand",
+    "Family",
+    "Days to Maturity",
+    "Exposure",
+    "Sowing Strategy",
+    "Calculated Start Date",
+    "Transplant Date",
+    "Estimated First Harvest",
+    "Seed Depth",
+    "Seed Spacing",
+];
+
+const TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Seed Collection</title>
+<style>
+  body { font-family: sans-serif; margin: 2rem; }
+  input { font-size: 1rem; padding: 0.4rem; width: 20rem; margin-bottom: 1rem; }
+  table { border-collapse: collapse; width: 100%; }
+  th, td { border: 1px solid #ccc; padding: 0.3rem 0.5rem; text-align: left; vertical-align: top; }
+  th { background: #eef3e8; cursor: pointer; user-select: none; }
+  th.asc::after { content: " \25B2"; }
+  th.desc::after { content: " \25BC"; }
+  tr:nth-child(even) td { background: #fafafa; }
+</style>
+</head>
+<body>
+<h1>Seed Collection</h1>
+<input id="filter" type="search" placeholder="Filter plants...">
+<span id="count"></span>
+<table>
+  <thead><tr id="header"></tr></thead>
+  <tbody id="rows"></tbody>
+</table>
+<script>
+const DATA = __DATA__;
+const COLUMNS = __COLUMNS__;
+let sortColumn = null;
+let sortDirection = 1;
+
+function compare(a, b) {
+  const x = a[sortColumn] || "";
+  const y = b[sortColumn] || "";
+  const nx = parseFloat(x);
+  const ny = parseFloat(y);
+  if (!isNaN(nx) && !isNaN(ny) && !/^\d{4}-\d{2}-\d{2}$/.test(x)) {
+    return (nx - ny) * sortDirection;
+  }
+  return x.localeCompare(y) * sortDirection;
+}
+
+function render() {
+  const filter = document.getElementById("filter").value.toLowerCase();
+  let rows = DATA.filter(row =>
+    Object.values(row).some(value => String(value).toLowerCase().includes(filter)));
+  if (sortColumn) {
+    rows = rows.slice().sort(compare);
+  }
+
+  const body = document.getElementById("rows");
+  body.innerHTML = "";
+  for (const row of rows) {
+    const tr = document.createElement("tr");
+    for (const column of COLUMNS) {
+      const td = document.createElement("td");
+      if (column === "Plant Name" && row["URL"]) {
+        const link = document.createElement("a");
+        link.href = row["URL"];
+        link.textContent = row[column];
+        td.appendChild(link);
+      } else {
+        td.textContent = row[column] === "NULL" ? "" : row[column];
+      }
+      tr.appendChild(td);
+    }
+    body.appendChild(tr);
+  }
+  document.getElementById("count").textContent = rows.length + " of " + DATA.length + " plants";
+}
+
+const header = document.getElementById("header");
+for (const column of COLUMNS) {
+  const th = document.createElement("th");
+  th.textContent = column;
+  th.addEventListener("click", () => {
+    sortDirection = sortColumn === column ? -sortDirection : 1;
+    sortColumn = column;
+    for (const other of header.children) {
+      other.className = "";
+    }
+    th.className = sortDirection === 1 ? "asc" : "desc";
+    render();
+  });
+  header.appendChild(th);
+}
+document.getElementById("filter").addEventListener("input", render);
+render();
+</script>
+</body>
+</html>
+"#;
+
+// Serialize a value for embedding in a <script> element
+fn script_json(value: &Value) -> Result<String> {
+    Ok(serde_json::to_string(value)?.replace("</", "<\\/"))
+}
+
+fn render_html(rows: &[ExportRow], units: Units) -> Result<String> {
+    let data: Vec<Value> = rows
+        .iter()
+        .map(|row| {
+            let record = export_record(row);
+            let object: Map<String, Value> = CSV_HEADERS
+                .iter()
+                .zip(record)
+                .map(|(header, value)| (units.header(header), Value::String(value)))
+                .collect();
+            Value::Object(object)
+        })
+        .collect();
+    let columns: Vec<Value> = DISPLAY_COLUMNS
+        .iter()
+        .map(|column| Value::String(column.to_string()))
+        .collect();
+
+    Ok(TEMPLATE
+        .replace("__DATA__", &script_json(&Value::Array(data))?)
+        .replace("__COLUMNS__", &script_json(&Value::Array(columns))?))
+}
+
+pub fn write_html(rows: &[ExportRow], units: Units, output_file: &str) -> Result<()> {
+    fs::write(output_file, render_html(rows, units)?)
+        .context(format!("Failed to write report to {}", output_file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_json_escapes_closing_tags() {
+        let value = Value::String("</script><b>".to_string());
+        assert_eq!(script_json(&value).unwrap(), r#""<\/script><b>""#);
+    }
+}
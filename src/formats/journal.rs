@@ -0,0 +1,123 @@
+// The season journal CSV: each plant's planned dates beside the ones the planting log recorded
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate};
+use std::fs::File;
+use std::io::{self, Write};
+
+use crate::planting_log::{EventKind, PlantingLog};
+use crate::{format_date, ExportRow};
+
+const JOURNAL_HEADERS: [&str; 9] = [
+    "Plant",
+    "Planned Start",
+    "Sown",
+    "Days Off Plan (Sowing)",
+    "Germinated",
+    "Days to Germinate",
+    "Planned Transplant",
+    "Transplanted",
+    "Days Off Plan (Transplant)",
+];
+
+// Blank cells where there's nothing to compare, as in the schedule; a positive difference
+// means later than planned. Only the logged events from the frost date's season count.
+fn journal_record(row: &ExportRow, log: &PlantingLog, frost_date: NaiveDate) -> [String; 9] {
+    let date = |d: Option<NaiveDate>| d.map(|d| format_date(Some(d))).unwrap_or_default();
+    let days = |from: Option<NaiveDate>, to: Option<NaiveDate>| match (from, to) {
+        (Some(from), Some(to)) => (to - from).num_days().to_string(),
+        _ => String::new(),
+    };
+    let name = row.input.plant_name;
+    let planned = row.plant.as_ref().map(|plant| &plant.dates);
+    let planned_start = planned.and_then(|dates| dates.start);
+    let planned_transplant = planned.and_then(|dates| dates.transplant);
+    let season = row.input.frost_date.unwrap_or(frost_date).year();
+    let sown = log.first(name, EventKind::Sow, season);
+    let germinated = log.first(name, EventKind::Germination, season);
+    let transplanted = log.first(name, EventKind::Transplant, season);
+    [
+        name.to_string(),
+        date(planned_start),
+        date(sown),
+        days(planned_start, sown),
+        date(germinated),
+        days(sown, germinated),
+        date(planned_transplant),
+        date(transplanted),
+        days(planned_transplant, transplanted),
+    ]
+}
+
+// Write the journal CSV; an output of "-" writes to stdout
+pub fn write_journal_csv(
+    rows: &[ExportRow],
+    log: &PlantingLog,
+    frost_date: NaiveDate,
+    output_file: &str,
+) -> Result<()> {
+    let out: Box<dyn Write> = if output_file == "-" {
+        Box::new(io::stdout().lock())
+    } else {
+        Box::new(File::create(output_file).context(format!("Failed to create {}", output_file))?)
+    };
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(JOURNAL_HEADERS)?;
+    for row in rows {
+        writer.write_record(journal_record(row, log, frost_date))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planting_log::{append, Event};
+    use crate::{InputRecord, PlantExport, PlantInfo};
+
+    #[test]
+    fn test_journal_record() {
+        let dir = std::env::temp_dir().join(format!("seed-scraper-journal-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let json_dir = dir.to_str().unwrap();
+        let event = |date: &str, kind| Event {
+            date: date.parse().unwrap(),
+            plant: "Carrot".to_string(),
+            kind,
+            notes: String::new(),
+        };
+        append(json_dir, &event("2024-04-20", EventKind::Sow)).unwrap();
+        append(json_dir, &event("2025-04-29", EventKind::Sow)).unwrap();
+        append(json_dir, &event("2025-05-12", EventKind::Germination)).unwrap();
+        let log = PlantingLog::load(json_dir).unwrap();
+
+        let record = csv::StringRecord::from(vec!["Carrot"]);
+        let info = PlantInfo {
+            when_to_sow_outside: Some(
+                "2 to 4 weeks before your average last frost date".to_string(),
+            ),
+            ..Default::default()
+        };
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        let row = ExportRow {
+            input: InputRecord::from_csv_record(&record),
+            plant: Some(PlantExport::new(info, None, frost_date)),
+        };
+        // Last season's sowing is left out, so it's 3 days later than the planned Apr 26
+        assert_eq!(
+            journal_record(&row, &log, frost_date),
+            [
+                "Carrot",
+                "2025-04-26",
+                "2025-04-29",
+                "3",
+                "2025-05-12",
+                "13",
+                "",
+                "",
+                ""
+            ]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
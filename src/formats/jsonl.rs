@@ -0,0 +1,142 @@
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::{format_sowing_time, ExportRow, Units};
+
+// Merge a row's input columns, scraped fields, and calculated dates into one flat object
+pub fn plant_object(row: &ExportRow) -> Value {
+    let mut object = Map::new();
+
+    if let Some(plant) = &row.plant {
+        if let Ok(Value::Object(info)) = serde_json::to_value(&plant.info) {
+            object.extend(info);
+        }
+    }
+
+    // Input columns take precedence over scraped fields of the same name
+    let input = &row.input;
+    object.insert("plant_name".to_string(), json!(input.plant_name));
+    object.insert("url".to_string(), json!(input.url));
+    object.insert("brand".to_string(), json!(input.brand));
+    object.insert("purchase_year".to_string(), json!(input.purchase_year));
+    object.insert("notes".to_string(), json!(input.notes));
+    object.insert(
+        "users_sowing_strategy".to_string(),
+        json!(input.user_strategy_str),
+    );
+    object.insert("has_json_data".to_string(), json!(row.plant.is_some()));
+
+    if let Some(plant) = &row.plant {
+        if !plant.duplicate_of.is_empty() {
+            object.insert("duplicate_of".to_string(), json!(plant.duplicate_of));
+        }
+        let layout = &plant.layout;
+        if let Some(per_sq_ft) = layout.plants_per_sq_ft {
+            object.insert("plants_per_sq_ft".to_string(), json!(per_sq_ft));
+        }
+        if let Some(per_bed) = layout.plants_per_bed {
+            object.insert("plants_per_bed".to_string(), json!(per_bed));
+        }
+        if let Some(seeds) = layout.seeds_needed {
+            object.insert("seeds_needed".to_string(), json!(seeds));
+        }
+        if !plant.neighbors.good.is_empty() {
+            object.insert("good_neighbors".to_string(), json!(plant.neighbors.good));
+        }
+        if !plant.neighbors.bad.is_empty() {
+            object.insert("bad_neighbors".to_string(), json!(plant.neighbors.bad));
+        }
+        if !plant.overridden.is_empty() {
+            object.insert("overridden_fields".to_string(), json!(plant.overridden));
+        }
+        let date =
+            |d: Option<chrono::NaiveDate>| json!(d.map(|d| d.format("%Y-%m-%d").to_string()));
+        object.insert(
+            "sowing_strategy".to_string(),
+            json!(plant.sowing_strategy.map(|s| s.to_string())),
+        );
+        object.insert(
+            "when_to_seed_start".to_string(),
+            json!(plant
+                .when_to_start
+                .first()
+                .map(|t| format_sowing_time(Some(t)))),
+        );
+        object.insert("calculated_start_date".to_string(), date(plant.dates.start));
+        object.insert(
+            "earliest_start".to_string(),
+            date(plant.dates.earliest_start),
+        );
+        object.insert("latest_start".to_string(), date(plant.dates.latest_start));
+        object.insert("transplant_date".to_string(), date(plant.dates.transplant));
+        object.insert(
+            "estimated_first_harvest".to_string(),
+            date(plant.dates.first_harvest),
+        );
+        object.insert(
+            "estimated_last_harvest".to_string(),
+            date(plant.dates.last_harvest),
+        );
+        let alternates: Vec<Value> = plant
+            .dates
+            .alternate_windows
+            .iter()
+            .map(|(earliest, latest)| {
+                json!({"earliest_start": date(Some(*earliest)), "latest_start": date(Some(*latest))})
+            })
+            .collect();
+        if !alternates.is_empty() {
+            object.insert("alternate_sowing_windows".to_string(), json!(alternates));
+        }
+    }
+
+    Value::Object(object)
+}
+
+// Write one JSON object per line; an output of "-" writes to stdout
+pub fn write_jsonl(rows: &[ExportRow], units: Units, output_file: &str) -> Result<()> {
+    let mut out: Box<dyn Write> = if output_file == "-" {
+        Box::new(io::stdout().lock())
+    } else {
+        let file =
+            File::create(output_file).context(format!("Failed to create {}", output_file))?;
+        Box::new(BufWriter::new(file))
+    };
+
+    for row in rows {
+        let object: Map<String, Value> = match plant_object(row) {
+            Value::Object(object) => object
+                .into_iter()
+                .map(|(name, value)| (units.field_name(&name), value))
+                .collect(),
+            _ => Map::new(),
+        };
+        serde_json::to_writer(&mut out, &object)?;
+        out.write_all(b"\n")?;
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputRecord;
+
+    #[test]
+    fn test_plant_object_without_json() {
+        let record = csv::StringRecord::from(vec!["Carrot", "http://example.com", "", "", "", ""]);
+        let row = ExportRow {
+            input: InputRecord::from_csv_record(&record),
+            plant: None,
+        };
+        let object = plant_object(&row);
+
+        assert_eq!(object["plant_name"], "Carrot");
+        assert_eq!(object["has_json_data"], false);
+        assert!(object.get("calculated_start_date").is_none());
+    }
+}
@@ -0,0 +1,168 @@
+use anyhow::{Context, Result};
+use std::fs;
+
+use crate::pdf::{Document, Font, Page, LETTER_HEIGHT, LETTER_WIDTH};
+use crate::{format_date, ExportRow};
+
+const POINTS_PER_INCH: f32 = 72.0;
+
+// Placement of labels on a letter-size sheet, in points from the top-left corner
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LabelLayout {
+    columns: usize,
+    rows: usize,
+    left: f32,
+    top: f32,
+    width: f32,
+    height: f32,
+    h_pitch: f32,
+    v_pitch: f32,
+}
+
+impl LabelLayout {
+    // Avery 5160 / 8160: 30 labels of 2⅝" x 1" in 3 columns
+    pub fn avery_5160() -> Self {
+        LabelLayout {
+            columns: 3,
+            rows: 10,
+            left: 0.1875 * POINTS_PER_INCH,
+            top: 0.5 * POINTS_PER_INCH,
+            width: 2.625 * POINTS_PER_INCH,
+            height: POINTS_PER_INCH,
+            h_pitch: 2.75 * POINTS_PER_INCH,
+            v_pitch: POINTS_PER_INCH,
+        }
+    }
+
+    // An evenly divided grid inside half-inch page margins
+    pub fn grid(columns: usize, rows: usize) -> Self {
+        let margin = 0.5 * POINTS_PER_INCH;
+        let h_pitch = (LETTER_WIDTH - 2.0 * margin) / columns as f32;
+        let v_pitch = (LETTER_HEIGHT - 2.0 * margin) / rows as f32;
+        LabelLayout {
+            columns,
+            rows,
+            left: margin,
+            top: margin,
+            width: h_pitch,
+            height: v_pitch,
+            h_pitch,
+            v_pitch,
+        }
+    }
+
+    fn per_page(&self) -> usize {
+        self.columns * self.rows
+    }
+}
+
+// Parse a grid size such as "4x12" (columns x rows)
+pub fn parse_label_grid(text: &str) -> Result<(usize, usize), String> {
+    let (columns, rows) = text
+        .split_once(['x', 'X'])
+        .ok_or_else(|| format!("expected COLUMNSxROWS, got \"{}\"", text))?;
+    let columns: usize = columns.trim().parse().map_err(|_| "invalid column count")?;
+    let rows: usize = rows.trim().parse().map_err(|_| "invalid row count")?;
+    if columns == 0 || rows == 0 {
+        return Err("grid must have at least one column and row".to_string());
+    }
+    Ok((columns, rows))
+}
+
+// Shorten text to roughly fit a width, assuming Helvetica's average glyph width
+fn fit_text(text: &str, width: f32, size: f32) -> String {
+    let max_chars = (width / (size * 0.5)) as usize;
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut fitted: String = text.chars().take(max_chars.saturating_sub(3)).collect();
+    fitted.push_str("...");
+    fitted
+}
+
+fn label_lines(row: &ExportRow) -> Option<Vec<String>> {
+    let plant = row.plant.as_ref()?;
+    let info = &plant.info;
+    let mut lines = Vec::new();
+
+    if let Some(title) = &info.title {
+        lines.push(title.trim_end_matches(" Seeds").to_string());
+    }
+    lines.push(format!("Sow: {}", format_date(plant.dates.start)));
+    let spacing: Vec<String> = [("Depth", &info.seed_depth), ("Spacing", &info.seed_spacing)]
+        .iter()
+        .filter_map(|(label, value)| value.as_ref().map(|v| format!("{} {}", label, v)))
+        .collect();
+    if !spacing.is_empty() {
+        lines.push(spacing.join("  "));
+    }
+
+    Some(lines)
+}
+
+fn render_labels(rows: &[ExportRow], layout: LabelLayout) -> Document {
+    let mut document = Document::new();
+    let mut page = Page::new(LETTER_WIDTH, LETTER_HEIGHT);
+    let padding = 6.0;
+    let text_width = layout.width - 2.0 * padding;
+
+    let labels: Vec<(&str, Vec<String>)> = rows
+        .iter()
+        .filter_map(|row| label_lines(row).map(|lines| (row.input.plant_name, lines)))
+        .collect();
+
+    for (i, (name, lines)) in labels.iter().enumerate() {
+        let slot = i % layout.per_page();
+        if slot == 0 && i > 0 {
+            document.add_page(page);
+            page = Page::new(LETTER_WIDTH, LETTER_HEIGHT);
+        }
+
+        let column = slot % layout.columns;
+        let row = slot / layout.columns;
+        let x = layout.left + column as f32 * layout.h_pitch + padding;
+        let label_top = LETTER_HEIGHT - layout.top - row as f32 * layout.v_pitch;
+        let label_bottom = label_top - layout.height;
+        let mut y = label_top - padding - 10.0;
+
+        page.text(x, y, 11.0, Font::Bold, &fit_text(name, text_width, 11.0));
+        for line in lines {
+            y -= 11.0;
+            // Skip lines that would spill onto the next label
+            if y < label_bottom + padding {
+                break;
+            }
+            page.text(x, y, 8.0, Font::Regular, &fit_text(line, text_width, 8.0));
+        }
+    }
+    document.add_page(page);
+
+    document
+}
+
+pub fn write_labels(rows: &[ExportRow], layout: LabelLayout, output_file: &str) -> Result<()> {
+    fs::write(output_file, render_labels(rows, layout).to_bytes())
+        .context(format!("Failed to write labels to {}", output_file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_label_grid() {
+        assert_eq!(parse_label_grid("4x12"), Ok((4, 12)));
+        assert_eq!(parse_label_grid("2X5"), Ok((2, 5)));
+        assert!(parse_label_grid("0x5").is_err());
+        assert!(parse_label_grid("four").is_err());
+    }
+
+    #[test]
+    fn test_fit_text() {
+        assert_eq!(fit_text("Carrot", 100.0, 10.0), "Carrot");
+        assert_eq!(
+            fit_text("Danvers 126 Carrot Seeds", 50.0, 10.0),
+            "Danvers..."
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+
+use crate::{format_date, ExportRow, PlantExport, ReportGrouping};
+
+// A group's heading label and the named plants in it
+type PlantGroup<'a> = (String, Vec<(&'a str, &'a PlantExport)>);
+
+// Group heading for a plant; the key sorts groups, the label is shown
+fn group_for(plant: &PlantExport, grouping: ReportGrouping) -> (String, String) {
+    match grouping {
+        ReportGrouping::Family => match &plant.info.family {
+            Some(family) => (family.clone(), family.clone()),
+            None => ("~".to_string(), "Unknown Family".to_string()),
+        },
+        ReportGrouping::Month => match plant.dates.start {
+            Some(start) => (
+                start.format("%Y-%m").to_string(),
+                start.format("%B %Y").to_string(),
+            ),
+            None => ("~".to_string(), "No Start Date".to_string()),
+        },
+    }
+}
+
+fn write_plant(out: &mut String, name: &str, plant: &PlantExport) {
+    let info = &plant.info;
+    let _ = writeln!(out, "### {}\n", name);
+    if let Some(title) = &info.title {
+        let _ = writeln!(out, "*{}*\n", title);
+    }
+    if let Some(description) = &info.description_markdown {
+        let _ = writeln!(out, "{}\n", description);
+    }
+
+    let fields = [
+        (
+            "Sowing Strategy",
+            plant.sowing_strategy.map(|s| s.to_string()),
+        ),
+        (
+            "Start Date",
+            plant.dates.start.map(|d| format_date(Some(d))),
+        ),
+        (
+            "Transplant Date",
+            plant.dates.transplant.map(|d| format_date(Some(d))),
+        ),
+        (
+            "Harvest",
+            plant
+                .dates
+                .first_harvest
+                .zip(plant.dates.last_harvest)
+                .map(|(first, last)| {
+                    if first == last {
+                        format_date(Some(first))
+                    } else {
+                        format!(
+                            "{} to {}",
+                            format_date(Some(first)),
+                            format_date(Some(last))
+                        )
+                    }
+                }),
+        ),
+        ("Days to Maturity", info.days_to_maturity.clone()),
+        ("Family", info.family.clone()),
+        ("Exposure", info.exposure.clone()),
+        ("Days to Emerge", info.days_to_emerge.clone()),
+        ("Seed Depth", info.seed_depth.clone()),
+        ("Seed Spacing", info.seed_spacing.clone()),
+        ("Row Spacing", info.row_spacing.clone()),
+        ("Thinning", info.thinning.clone()),
+    ];
+    for (label, value) in fields {
+        if let Some(value) = value {
+            let _ = writeln!(out, "- **{}:** {}", label, value);
+        }
+    }
+    out.push('\n');
+}
+
+fn render_markdown(rows: &[ExportRow], grouping: ReportGrouping) -> String {
+    let mut groups: BTreeMap<String, PlantGroup> = BTreeMap::new();
+    let mut missing = Vec::new();
+
+    for row in rows {
+        match &row.plant {
+            Some(plant) => {
+                let (key, label) = group_for(plant, grouping);
+                groups
+                    .entry(key)
+                    .or_insert_with(|| (label, Vec::new()))
+                    .1
+                    .push((row.input.plant_name, plant));
+            }
+            None => missing.push(row.input.plant_name),
+        }
+    }
+
+    let mut out = String::from("# Garden Report\n\n");
+    for (label, plants) in groups.values() {
+        let _ = writeln!(out, "## {}\n", label);
+        for (name, plant) in plants {
+            write_plant(&mut out, name, plant);
+        }
+    }
+
+    if !missing.is_empty() {
+        out.push_str("## Missing Data\n\n");
+        for name in missing {
+            let _ = writeln!(out, "- {}", name);
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+pub fn write_markdown(
+    rows: &[ExportRow],
+    grouping: ReportGrouping,
+    output_file: &str,
+) -> Result<()> {
+    fs::write(output_file, render_markdown(rows, grouping))
+        .context(format!("Failed to write report to {}", output_file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InputRecord, PlantInfo};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_render_markdown_by_family() {
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        let plant = |family: Option<&str>| {
+            let info = PlantInfo {
+                family: family.map(String::from),
+                ..Default::default()
+            };
+            Some(PlantExport::new(info, None, frost_date))
+        };
+        let records =
+            ["Mystery Bean", "Carrot", "Tomato"].map(|name| csv::StringRecord::from(vec![name]));
+        let rows = [
+            ExportRow {
+                input: InputRecord::from_csv_record(&records[0]),
+                plant: plant(None),
+            },
+            ExportRow {
+                input: InputRecord::from_csv_record(&records[1]),
+                plant: plant(Some("Apiaceae")),
+            },
+            ExportRow {
+                input: InputRecord::from_csv_record(&records[2]),
+                plant: None,
+            },
+        ];
+
+        let markdown = render_markdown(&rows, ReportGrouping::Family);
+        assert_eq!(
+            markdown,
+            "# Garden Report\n\n\
+             ## Apiaceae\n\n### Carrot\n\n- **Family:** Apiaceae\n\n\
+             ## Unknown Family\n\n### Mystery Bean\n\n\n\
+             ## Missing Data\n\n- Tomato\n\n"
+        );
+        // Fields without a value are left out rather than shown as NULL
+        assert!(!markdown.contains("NULL"));
+    }
+}
@@ -0,0 +1,11 @@
+// Alternative output formats for the export command
+pub mod html;
+pub mod journal;
+pub mod jsonl;
+pub mod labels;
+pub mod markdown;
+pub mod parquet_export;
+pub mod pdf_schedule;
+pub mod schedule;
+pub mod template;
+pub mod todoist;
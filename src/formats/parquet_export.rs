@@ -0,0 +1,325 @@
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use parquet::basic::Compression;
+use parquet::data_type::{ByteArray, ByteArrayType, FloatType, Int32Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+use std::fs::File;
+use std::sync::Arc;
+
+use crate::{format_sowing_time, format_windows, ExportRow, PlantInfo, Units};
+
+enum ColumnValues {
+    Text(Vec<Option<String>>),
+    Float(Vec<Option<f32>>),
+    Int(Vec<Option<i32>>),
+    // Days since the Unix epoch, as Parquet's DATE logical type expects
+    Date(Vec<Option<i32>>),
+}
+
+struct Column {
+    name: &'static str,
+    values: ColumnValues,
+}
+
+impl Column {
+    fn schema_field(&self, units: Units) -> String {
+        let physical = match self.values {
+            ColumnValues::Text(_) => "BYTE_ARRAY",
+            ColumnValues::Float(_) => "FLOAT",
+            ColumnValues::Int(_) | ColumnValues::Date(_) => "INT32",
+        };
+        let logical = match self.values {
+            ColumnValues::Text(_) => " (UTF8)",
+            ColumnValues::Date(_) => " (DATE)",
+            _ => "",
+        };
+        format!(
+            "OPTIONAL {} {}{};",
+            physical,
+            units.field_name(self.name),
+            logical
+        )
+    }
+}
+
+type InfoField = fn(&PlantInfo) -> &Option<String>;
+
+// Scraped text fields in the same order as the CSV export
+const INFO_TEXT_FIELDS: [(&str, InfoField); 20] = [
+    ("title", |i| &i.title),
+    ("description", |i| &i.description),
+    ("description_markdown", |i| &i.description_markdown),
+    ("days_to_maturity", |i| &i.days_to_maturity),
+    ("family", |i| &i.family),
+    ("plant_type", |i| &i.plant_type),
+    ("native", |i| &i.native),
+    ("hardiness", |i| &i.hardiness),
+    ("exposure", |i| &i.exposure),
+    ("plant_dimensions", |i| &i.plant_dimensions),
+    ("variety_info", |i| &i.variety_info),
+    ("attributes", |i| &i.attributes),
+    ("when_to_sow_outside", |i| &i.when_to_sow_outside),
+    ("when_to_start_inside", |i| &i.when_to_start_inside),
+    ("days_to_emerge", |i| &i.days_to_emerge),
+    ("seed_depth", |i| &i.seed_depth),
+    ("seed_spacing", |i| &i.seed_spacing),
+    ("row_spacing", |i| &i.row_spacing),
+    ("thinning", |i| &i.thinning),
+    ("botanical_name", |i| &i.botanical_name),
+];
+
+fn non_empty(text: &str) -> Option<String> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn days_since_epoch(date: Option<NaiveDate>) -> Option<i32> {
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+    date.map(|d| (d - epoch).num_days() as i32)
+}
+
+fn build_columns(rows: &[ExportRow]) -> Vec<Column> {
+    let text = |name, f: &dyn Fn(&ExportRow) -> Option<String>| Column {
+        name,
+        values: ColumnValues::Text(rows.iter().map(f).collect()),
+    };
+    let int = |name, f: &dyn Fn(&ExportRow) -> Option<i32>| Column {
+        name,
+        values: ColumnValues::Int(rows.iter().map(f).collect()),
+    };
+    let date = |name, f: &dyn Fn(&ExportRow) -> Option<NaiveDate>| Column {
+        name,
+        values: ColumnValues::Date(rows.iter().map(|row| days_since_epoch(f(row))).collect()),
+    };
+
+    let mut columns = vec![
+        text("plant_name", &|row| non_empty(row.input.plant_name)),
+        text("url", &|row| non_empty(row.input.url)),
+        text("brand", &|row| non_empty(row.input.brand)),
+        int("purchase_year", &|row| {
+            row.input.purchase_year.trim().parse().ok()
+        }),
+        text("notes", &|row| non_empty(row.input.notes)),
+        text("users_sowing_strategy", &|row| {
+            non_empty(row.input.user_strategy_str)
+        }),
+    ];
+
+    for (name, field) in INFO_TEXT_FIELDS {
+        columns.push(text(name, &|row| {
+            row.plant.as_ref().and_then(|p| field(&p.info).clone())
+        }));
+    }
+
+    let maturity = |row: &ExportRow| row.plant.as_ref().and_then(|p| p.info.maturity_days());
+    let float = |name, f: &dyn Fn(&PlantInfo) -> Option<f32>| Column {
+        name,
+        values: ColumnValues::Float(
+            rows.iter()
+                .map(|row| row.plant.as_ref().and_then(|p| f(&p.info)))
+                .collect(),
+        ),
+    };
+    columns.extend([
+        float("height_min_in", &|info| info.height_min_in),
+        float("height_max_in", &|info| info.height_max_in),
+        float("spread_min_in", &|info| info.spread_min_in),
+        float("spread_max_in", &|info| info.spread_max_in),
+        float("seed_depth_min_in", &|info| info.seed_depth_min_in),
+        float("seed_depth_max_in", &|info| info.seed_depth_max_in),
+        float("seed_spacing_min_in", &|info| info.seed_spacing_min_in),
+        float("seed_spacing_max_in", &|info| info.seed_spacing_max_in),
+        float("row_spacing_min_in", &|info| info.row_spacing_min_in),
+        float("row_spacing_max_in", &|info| info.row_spacing_max_in),
+        float("plant_spacing_in", &|info| info.plant_spacing_in),
+        Column {
+            name: "plants_per_sq_ft",
+            values: ColumnValues::Float(
+                rows.iter()
+                    .map(|row| row.plant.as_ref().and_then(|p| p.layout.plants_per_sq_ft))
+                    .collect(),
+            ),
+        },
+        int("plants_per_bed", &|row| {
+            row.plant
+                .as_ref()
+                .and_then(|p| p.layout.plants_per_bed)
+                .map(|n| n as i32)
+        }),
+        int("seeds_needed", &|row| {
+            row.plant
+                .as_ref()
+                .and_then(|p| p.layout.seeds_needed)
+                .map(|n| n as i32)
+        }),
+        Column {
+            name: "rating",
+            values: ColumnValues::Float(
+                rows.iter()
+                    .map(|row| row.plant.as_ref().and_then(|p| p.info.rating))
+                    .collect(),
+            ),
+        },
+        int("votes", &|row| {
+            row.plant
+                .as_ref()
+                .and_then(|p| p.info.votes)
+                .map(|v| v as i32)
+        }),
+        int("min_soil_temp_f", &|row| {
+            row.plant.as_ref().and_then(|p| p.info.min_soil_temp_f)
+        }),
+        int("ideal_soil_temp_min_f", &|row| {
+            row.plant
+                .as_ref()
+                .and_then(|p| p.info.ideal_soil_temp_min_f)
+        }),
+        int("ideal_soil_temp_max_f", &|row| {
+            row.plant
+                .as_ref()
+                .and_then(|p| p.info.ideal_soil_temp_max_f)
+        }),
+        int("days_to_maturity_min", &|row| {
+            maturity(row).map(|(min, _)| min as i32)
+        }),
+        int("days_to_maturity_max", &|row| {
+            maturity(row).map(|(_, max)| max as i32)
+        }),
+        text("sowing_strategy", &|row| {
+            row.plant
+                .as_ref()
+                .and_then(|p| p.sowing_strategy)
+                .map(|s| s.to_string())
+        }),
+        text("when_to_seed_start", &|row| {
+            row.plant
+                .as_ref()
+                .and_then(|p| p.when_to_start.first())
+                .map(|t| format_sowing_time(Some(t)))
+        }),
+        date("calculated_start_date", &|row| {
+            row.plant.as_ref().and_then(|p| p.dates.start)
+        }),
+        date("earliest_start", &|row| {
+            row.plant.as_ref().and_then(|p| p.dates.earliest_start)
+        }),
+        date("latest_start", &|row| {
+            row.plant.as_ref().and_then(|p| p.dates.latest_start)
+        }),
+        date("transplant_date", &|row| {
+            row.plant.as_ref().and_then(|p| p.dates.transplant)
+        }),
+        date("estimated_first_harvest", &|row| {
+            row.plant.as_ref().and_then(|p| p.dates.first_harvest)
+        }),
+        date("estimated_last_harvest", &|row| {
+            row.plant.as_ref().and_then(|p| p.dates.last_harvest)
+        }),
+        text("alternate_sowing_windows", &|row| {
+            row.plant
+                .as_ref()
+                .filter(|p| !p.dates.alternate_windows.is_empty())
+                .map(|p| format_windows(&p.dates.alternate_windows))
+        }),
+        text("overridden_fields", &|row| {
+            row.plant
+                .as_ref()
+                .filter(|p| !p.overridden.is_empty())
+                .map(|p| p.overridden.join(", "))
+        }),
+    ]);
+
+    columns
+}
+
+// Split optional values into the present values and their definition levels
+fn levels<T: Clone>(values: &[Option<T>]) -> (Vec<T>, Vec<i16>) {
+    let present = values.iter().flatten().cloned().collect();
+    let def_levels = values.iter().map(|v| v.is_some() as i16).collect();
+    (present, def_levels)
+}
+
+pub fn write_parquet(rows: &[ExportRow], units: Units, output_file: &str) -> Result<()> {
+    let columns = build_columns(rows);
+    let fields: Vec<String> = columns
+        .iter()
+        .map(|column| column.schema_field(units))
+        .collect();
+    let schema = Arc::new(parse_message_type(&format!(
+        "message plant {{ {} }}",
+        fields.join(" ")
+    ))?);
+    let props = Arc::new(
+        WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build(),
+    );
+
+    let file = File::create(output_file).context(format!("Failed to create {}", output_file))?;
+    let mut writer = SerializedFileWriter::new(file, schema, props)?;
+    let mut row_group = writer.next_row_group()?;
+
+    for column in &columns {
+        let mut column_writer = row_group
+            .next_column()?
+            .context("Parquet schema has fewer columns than expected")?;
+        match &column.values {
+            ColumnValues::Text(values) => {
+                let (present, def_levels) = levels(values);
+                let present: Vec<ByteArray> = present
+                    .into_iter()
+                    .map(|s| ByteArray::from(s.as_str()))
+                    .collect();
+                column_writer.typed::<ByteArrayType>().write_batch(
+                    &present,
+                    Some(&def_levels),
+                    None,
+                )?;
+            }
+            ColumnValues::Float(values) => {
+                let (present, def_levels) = levels(values);
+                column_writer.typed::<FloatType>().write_batch(
+                    &present,
+                    Some(&def_levels),
+                    None,
+                )?;
+            }
+            ColumnValues::Int(values) | ColumnValues::Date(values) => {
+                let (present, def_levels) = levels(values);
+                column_writer.typed::<Int32Type>().write_batch(
+                    &present,
+                    Some(&def_levels),
+                    None,
+                )?;
+            }
+        }
+        column_writer.close()?;
+    }
+
+    row_group.close()?;
+    writer.close()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels() {
+        let (present, def_levels) = levels(&[Some(1), None, Some(3)]);
+        assert_eq!(present, vec![1, 3]);
+        assert_eq!(def_levels, vec![1, 0, 1]);
+    }
+
+    #[test]
+    fn test_days_since_epoch() {
+        assert_eq!(
+            days_since_epoch(NaiveDate::from_ymd_opt(1970, 1, 2)),
+            Some(1)
+        );
+        assert_eq!(days_since_epoch(None), None);
+    }
+}
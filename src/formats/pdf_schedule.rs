@@ -0,0 +1,161 @@
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate};
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::pdf::{Document, Font, Page, LETTER_HEIGHT, LETTER_WIDTH};
+use crate::{ExportRow, SowingStrategy};
+
+const MARGIN: f32 = 54.0;
+const LINE_HEIGHT: f32 = 18.0;
+
+// One dated task on the schedule
+struct ScheduleEntry<'a> {
+    date: NaiveDate,
+    task: &'static str,
+    plant_name: &'a str,
+}
+
+fn schedule_entries<'a>(rows: &'a [ExportRow]) -> Vec<ScheduleEntry<'a>> {
+    let mut entries = Vec::new();
+
+    for row in rows {
+        let plant = match &row.plant {
+            Some(plant) => plant,
+            None => continue,
+        };
+        if let Some(start) = plant.dates.start {
+            let task = match plant.sowing_strategy {
+                Some(SowingStrategy::Inside) => "Start indoors",
+                _ => "Sow outside",
+            };
+            entries.push(ScheduleEntry {
+                date: start,
+                task,
+                plant_name: row.input.plant_name,
+            });
+        }
+        if let Some(transplant) = plant.dates.transplant {
+            entries.push(ScheduleEntry {
+                date: transplant,
+                task: "Transplant",
+                plant_name: row.input.plant_name,
+            });
+        }
+    }
+
+    entries.sort_by(|a, b| a.date.cmp(&b.date).then(a.plant_name.cmp(b.plant_name)));
+    entries
+}
+
+fn month_page(title: &str) -> (Page, f32) {
+    let mut page = Page::new(LETTER_WIDTH, LETTER_HEIGHT);
+    let top = LETTER_HEIGHT - MARGIN;
+    page.text(MARGIN, top, 22.0, Font::Bold, title);
+    page.text(MARGIN, top - 32.0, 11.0, Font::Bold, "Date");
+    page.text(MARGIN + 80.0, top - 32.0, 11.0, Font::Bold, "Task");
+    page.text(MARGIN + 200.0, top - 32.0, 11.0, Font::Bold, "Plant");
+    page.line(MARGIN, top - 38.0, LETTER_WIDTH - MARGIN, top - 38.0);
+    (page, top - 56.0)
+}
+
+fn render_schedule(rows: &[ExportRow]) -> Document {
+    let mut months: BTreeMap<(i32, u32), Vec<ScheduleEntry>> = BTreeMap::new();
+    for entry in schedule_entries(rows) {
+        months
+            .entry((entry.date.year(), entry.date.month()))
+            .or_default()
+            .push(entry);
+    }
+
+    let mut document = Document::new();
+    for entries in months.values() {
+        let title = entries[0].date.format("%B %Y").to_string();
+        let (mut page, mut y) = month_page(&title);
+
+        for entry in entries {
+            // Continue long months on another page
+            if y < MARGIN {
+                document.add_page(page);
+                (page, y) = month_page(&format!("{} (continued)", title));
+            }
+            page.text(
+                MARGIN,
+                y,
+                11.0,
+                Font::Regular,
+                &entry.date.format("%b %-d").to_string(),
+            );
+            page.text(MARGIN + 80.0, y, 11.0, Font::Regular, entry.task);
+            page.text(MARGIN + 200.0, y, 11.0, Font::Regular, entry.plant_name);
+            y -= LINE_HEIGHT;
+        }
+        document.add_page(page);
+    }
+
+    // A PDF needs at least one page, and an empty one would look like a rendering failure
+    if months.is_empty() {
+        let mut page = Page::new(LETTER_WIDTH, LETTER_HEIGHT);
+        let top = LETTER_HEIGHT - MARGIN;
+        page.text(MARGIN, top, 22.0, Font::Bold, "Sowing schedule");
+        page.text(
+            MARGIN,
+            top - 32.0,
+            11.0,
+            Font::Regular,
+            "Nothing is scheduled: no plant has a sowing or transplant date.",
+        );
+        document.add_page(page);
+    }
+
+    document
+}
+
+pub fn write_pdf_schedule(rows: &[ExportRow], output_file: &str) -> Result<()> {
+    fs::write(output_file, render_schedule(rows).to_bytes())
+        .context(format!("Failed to write schedule to {}", output_file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InputRecord, PlantExport, PlantInfo};
+
+    fn render(rows: &[ExportRow]) -> String {
+        String::from_utf8_lossy(&render_schedule(rows).to_bytes()).to_string()
+    }
+
+    #[test]
+    fn test_render_schedule() {
+        let record = csv::StringRecord::from(vec!["Carrot"]);
+        let info = PlantInfo {
+            when_to_sow_outside: Some(
+                "2 to 4 weeks before your average last frost date".to_string(),
+            ),
+            ..Default::default()
+        };
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        let rows = [ExportRow {
+            input: InputRecord::from_csv_record(&record),
+            plant: Some(PlantExport::new(info, None, frost_date)),
+        }];
+        let pdf = render(&rows);
+        assert!(pdf.contains("/Count 1"));
+        assert!(pdf.contains("(April 2025) Tj"));
+        assert!(pdf.contains("(Apr 26) Tj"));
+        assert!(pdf.contains("(Sow outside) Tj"));
+        assert!(pdf.contains("(Carrot) Tj"));
+    }
+
+    #[test]
+    fn test_render_empty_schedule() {
+        let record = csv::StringRecord::from(vec!["Carrot"]);
+        let rows = [ExportRow {
+            input: InputRecord::from_csv_record(&record),
+            plant: None,
+        }];
+        let pdf = render(&rows);
+        assert!(pdf.contains("/Count 1"));
+        assert!(pdf.contains("(Nothing is scheduled"));
+    }
+}
@@ -0,0 +1,91 @@
+// The short planting schedule CSV: just the dates someone acts on, one plant per row
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{self, Write};
+
+use crate::{format_date, ExportRow, SowingStrategy};
+
+const SCHEDULE_HEADERS: [&str; 6] = [
+    "Plant",
+    "Strategy",
+    "Start Inside",
+    "Sow Outside",
+    "Transplant",
+    "Expected Harvest",
+];
+
+// Blank cells rather than NULL, since this is meant to be printed
+fn schedule_record(row: &ExportRow) -> [String; 6] {
+    let date = |d: Option<chrono::NaiveDate>| d.map(|d| format_date(Some(d))).unwrap_or_default();
+    let name = row.input.plant_name.to_string();
+    let Some(plant) = &row.plant else {
+        return [
+            name,
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+        ];
+    };
+    let dates = &plant.dates;
+    let (inside, outside) = match plant.sowing_strategy {
+        Some(SowingStrategy::Inside) => (dates.start, None),
+        Some(SowingStrategy::Outside) => (None, dates.start),
+        None => (None, None),
+    };
+    [
+        name,
+        plant
+            .sowing_strategy
+            .map(|s| s.to_string())
+            .unwrap_or_default(),
+        date(inside),
+        date(outside),
+        date(dates.transplant),
+        date(dates.first_harvest),
+    ]
+}
+
+// Write the schedule CSV; an output of "-" writes to stdout
+pub fn write_schedule_csv(rows: &[ExportRow], output_file: &str) -> Result<()> {
+    let out: Box<dyn Write> = if output_file == "-" {
+        Box::new(io::stdout().lock())
+    } else {
+        Box::new(File::create(output_file).context(format!("Failed to create {}", output_file))?)
+    };
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(SCHEDULE_HEADERS)?;
+    for row in rows {
+        writer.write_record(schedule_record(row))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InputRecord, PlantExport, PlantInfo};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_schedule_record() {
+        let record = csv::StringRecord::from(vec!["Carrot"]);
+        let info = PlantInfo {
+            when_to_sow_outside: Some(
+                "2 to 4 weeks before your average last frost date".to_string(),
+            ),
+            ..Default::default()
+        };
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        let row = ExportRow {
+            input: InputRecord::from_csv_record(&record),
+            plant: Some(PlantExport::new(info, None, frost_date)),
+        };
+        assert_eq!(
+            schedule_record(&row),
+            ["Carrot", "Outside", "", "2025-04-26", "", ""]
+        );
+    }
+}
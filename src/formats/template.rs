@@ -0,0 +1,78 @@
+// Rendering the export through a user's Handlebars template, for formats we don't ship
+use anyhow::{Context, Result};
+use handlebars::Handlebars;
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{self, Write};
+
+use super::jsonl::plant_object;
+use crate::{ExportRow, Units};
+
+// What a template sees: `plants`, each with the same fields as a JSONL export line
+fn template_data(rows: &[ExportRow], units: Units) -> Value {
+    let plants: Vec<Value> = rows
+        .iter()
+        .map(|row| match plant_object(row) {
+            Value::Object(object) => Value::Object(
+                object
+                    .into_iter()
+                    .map(|(name, value)| (units.field_name(&name), value))
+                    .collect(),
+            ),
+            other => other,
+        })
+        .collect();
+    json!({
+        "plants": plants,
+        "generated_at": chrono::Local::now().format("%Y-%m-%d").to_string(),
+    })
+}
+
+pub fn render_template(template: &str, rows: &[ExportRow], units: Units) -> Result<String> {
+    let mut registry = Handlebars::new();
+    // The output is rarely HTML, so print values as they are
+    registry.register_escape_fn(handlebars::no_escape);
+    registry.set_strict_mode(false);
+    Ok(registry.render_template(template, &template_data(rows, units))?)
+}
+
+// Render the template file; an output of "-" writes to stdout
+pub fn write_template(
+    rows: &[ExportRow],
+    units: Units,
+    template_file: &str,
+    output_file: &str,
+) -> Result<()> {
+    let template = fs::read_to_string(template_file)
+        .context(format!("Failed to read template {}", template_file))?;
+    let rendered = render_template(&template, rows, units)
+        .context(format!("Failed to render template {}", template_file))?;
+    if output_file == "-" {
+        io::stdout().lock().write_all(rendered.as_bytes())?;
+        return Ok(());
+    }
+    fs::write(output_file, rendered).context(format!("Failed to write {}", output_file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputRecord;
+
+    #[test]
+    fn test_render_template() {
+        let record = csv::StringRecord::from(vec!["Carrot & Co", "http://example.com"]);
+        let row = ExportRow {
+            input: InputRecord::from_csv_record(&record),
+            plant: None,
+        };
+        let rendered = render_template(
+            "{{#each plants}}| {{plant_name}} | {{calculated_start_date}} |\n{{/each}}",
+            &[row],
+            Units::Imperial,
+        )
+        .unwrap();
+        assert_eq!(rendered, "| Carrot & Co |  |\n");
+        assert!(render_template("{{#each plants}}", &[], Units::Imperial).is_err());
+    }
+}
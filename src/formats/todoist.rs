@@ -0,0 +1,116 @@
+use anyhow::Result;
+use tracing::warn;
+
+use crate::{ExportRow, SowingStrategy};
+
+// Columns of Todoist's CSV project template import
+const TODOIST_HEADERS: [&str; 10] = [
+    "TYPE",
+    "CONTENT",
+    "DESCRIPTION",
+    "PRIORITY",
+    "INDENT",
+    "AUTHOR",
+    "RESPONSIBLE",
+    "DATE",
+    "DATE_LANG",
+    "TIMEZONE",
+];
+
+// Build the task name and description for a row, if it has a start date
+fn sowing_task(row: &ExportRow) -> Option<(String, String, String)> {
+    let plant = row.plant.as_ref()?;
+    let start = plant.dates.start?;
+
+    let (content, instructions) = match plant.sowing_strategy {
+        Some(SowingStrategy::Inside) => (
+            format!("Start {} indoors", row.input.plant_name),
+            &plant.info.when_to_start_inside,
+        ),
+        _ => (
+            format!("Sow {} outside", row.input.plant_name),
+            &plant.info.when_to_sow_outside,
+        ),
+    };
+
+    Some((
+        content,
+        instructions.clone().unwrap_or_default(),
+        start.format("%Y-%m-%d").to_string(),
+    ))
+}
+
+pub fn write_todoist_csv(rows: &[ExportRow], output_file: &str) -> Result<()> {
+    let mut writer = csv::Writer::from_path(output_file)?;
+    writer.write_record(TODOIST_HEADERS)?;
+
+    for row in rows {
+        let (content, description, date) = match sowing_task(row) {
+            Some(task) => task,
+            None => {
+                warn!(
+                    "Skipping {} - no calculated start date",
+                    row.input.plant_name
+                );
+                continue;
+            }
+        };
+        writer.write_record([
+            "task",
+            &content,
+            &description,
+            "4",
+            "1",
+            "",
+            "",
+            &date,
+            "en",
+            "",
+        ])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InputRecord, PlantExport, PlantInfo};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_write_todoist_csv() {
+        let carrot = csv::StringRecord::from(vec!["Carrot"]);
+        let mystery = csv::StringRecord::from(vec!["Mystery Bean"]);
+        let info = PlantInfo {
+            when_to_sow_outside: Some(
+                "2 to 4 weeks before your average last frost date".to_string(),
+            ),
+            ..Default::default()
+        };
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        let rows = [
+            ExportRow {
+                input: InputRecord::from_csv_record(&carrot),
+                plant: Some(PlantExport::new(info, None, frost_date)),
+            },
+            ExportRow {
+                input: InputRecord::from_csv_record(&mystery),
+                plant: Some(PlantExport::new(PlantInfo::default(), None, frost_date)),
+            },
+        ];
+        assert!(sowing_task(&rows[1]).is_none());
+
+        let path =
+            std::env::temp_dir().join(format!("seed-scraper-todoist-{}.csv", std::process::id()));
+        write_todoist_csv(&rows, path.to_str().unwrap()).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            content,
+            "TYPE,CONTENT,DESCRIPTION,PRIORITY,INDENT,AUTHOR,RESPONSIBLE,DATE,DATE_LANG,TIMEZONE\n\
+             task,Sow Carrot outside,2 to 4 weeks before your average last frost date,4,1,,,2025-04-26,en,\n"
+        );
+    }
+}
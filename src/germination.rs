@@ -0,0 +1,135 @@
+// Germination tests of seed lots, kept beside the plant JSON to tell which old packets to oversow
+use anyhow::{anyhow, Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::OutputFormat;
+
+pub const GERMINATION_FILE: &str = "germination.toml";
+
+// One test, e.g. 20 seeds on a damp paper towel of which 17 sprouted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GerminationTest {
+    pub date: NaiveDate,
+    // The purchase year of the packet, matched against the input CSV's Purchase Year
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lot: Option<String>,
+    pub tested: u32,
+    pub sprouted: u32,
+}
+
+impl GerminationTest {
+    pub fn rate(&self) -> f32 {
+        self.sprouted as f32 * 100.0 / self.tested as f32
+    }
+}
+
+// Arrays of tests keyed by plant name, e.g. `[[Carrot]]` then `date = "2025-02-01"`
+#[derive(Debug, Default)]
+pub struct GerminationLog {
+    path: PathBuf,
+    plants: BTreeMap<String, Vec<GerminationTest>>,
+}
+
+impl GerminationLog {
+    pub fn load(json_dir: &str) -> Result<Self> {
+        let path = Path::new(json_dir).join(GERMINATION_FILE);
+        let plants = match fs::read_to_string(&path) {
+            Ok(content) => {
+                toml::from_str(&content).context(format!("Failed to parse {}", path.display()))?
+            }
+            Err(_) => BTreeMap::new(),
+        };
+        Ok(GerminationLog { path, plants })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, toml::to_string(&self.plants)?)
+            .context(format!("Failed to write {}", self.path.display()))
+    }
+
+    pub fn record(&mut self, plant: &str, test: GerminationTest) -> Result<()> {
+        if test.tested == 0 || test.sprouted > test.tested {
+            return Err(anyhow!(
+                "Sprouted seeds must be between 0 and the {} tested",
+                test.tested
+            ));
+        }
+        let tests = self.plants.entry(plant.to_string()).or_default();
+        tests.push(test);
+        tests.sort_by_key(|test| test.date);
+        Ok(())
+    }
+
+    // The latest test of the lot bought in purchase_year, counting tests that name no lot as
+    // being of any lot
+    pub fn latest(&self, plant: &str, purchase_year: &str) -> Option<&GerminationTest> {
+        let purchase_year = purchase_year.trim();
+        self.plants
+            .get(plant)?
+            .iter()
+            .filter(|test| {
+                test.lot
+                    .as_deref()
+                    .is_none_or(|lot| purchase_year.is_empty() || lot == purchase_year)
+            })
+            .max_by_key(|test| test.date)
+    }
+}
+
+// Print every recorded test, by plant
+pub fn list_tests(json_dir: &str, output: OutputFormat) -> Result<()> {
+    let log = GerminationLog::load(json_dir)?;
+    if log.plants.is_empty() {
+        output.print_text(&format!("No germination tests recorded in {}", json_dir));
+    }
+    for (plant, tests) in &log.plants {
+        output.print_text(plant);
+        for test in tests {
+            output.print_text(&format!(
+                "  {}  {:<6} {}/{} sprouted ({:.0}%)",
+                test.date,
+                test.lot.as_deref().unwrap_or("-"),
+                test.sprouted,
+                test.tested,
+                test.rate()
+            ));
+        }
+    }
+    output.print_json(&serde_json::to_value(&log.plants)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_test_for_lot() {
+        let test = |date: &str, lot: Option<&str>, sprouted| GerminationTest {
+            date: date.parse().unwrap(),
+            lot: lot.map(String::from),
+            tested: 20,
+            sprouted,
+        };
+        let mut log = GerminationLog::default();
+        log.record("Carrot", test("2025-02-01", Some("2022"), 11))
+            .unwrap();
+        log.record("Carrot", test("2024-02-01", Some("2024"), 19))
+            .unwrap();
+        log.record("Carrot", test("2023-02-01", None, 18)).unwrap();
+        assert!(log.record("Carrot", test("2025-02-01", None, 21)).is_err());
+
+        assert_eq!(log.latest("Carrot", "2022").unwrap().rate(), 55.0);
+        assert_eq!(log.latest("Carrot", "2024").unwrap().sprouted, 19);
+        assert_eq!(log.latest("Carrot", "2021").unwrap().sprouted, 18);
+        assert_eq!(log.latest("Carrot", "").unwrap().sprouted, 11);
+        assert_eq!(log.latest("Tomato", "2022"), None);
+
+        let saved = toml::to_string(&log.plants).unwrap();
+        let loaded: BTreeMap<String, Vec<GerminationTest>> = toml::from_str(&saved).unwrap();
+        assert_eq!(loaded, log.plants);
+    }
+}
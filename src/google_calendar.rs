@@ -0,0 +1,285 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::NaiveDate;
+use reqwest::{blocking::Client, StatusCode, Url};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use tracing::error;
+
+use crate::calendar::{plant_events, CalendarEvent};
+use crate::load_json_dir;
+
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const CALENDAR_API_URL: &str = "https://www.googleapis.com/calendar/v3/calendars";
+// Private extended property marking the events this tool made, so it only ever deletes those
+const EVENT_TAG: &str = "seeds-scraper";
+
+// OAuth credentials for an installed app with a previously granted refresh token
+pub struct OAuthCredentials {
+    pub client_id: String,
+    pub client_secret: String,
+    pub refresh_token: String,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+fn fetch_access_token(client: &Client, credentials: &OAuthCredentials) -> Result<String> {
+    let response = client
+        .post(TOKEN_URL)
+        .form(&[
+            ("client_id", credentials.client_id.as_str()),
+            ("client_secret", credentials.client_secret.as_str()),
+            ("refresh_token", credentials.refresh_token.as_str()),
+            ("grant_type", "refresh_token"),
+        ])
+        .send()
+        .context("Failed to request OAuth access token")?;
+
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "OAuth token request failed with {}: {}",
+            response.status(),
+            response.text().unwrap_or_default()
+        ));
+    }
+
+    Ok(response.json::<TokenResponse>()?.access_token)
+}
+
+// Google event IDs may only use base32hex characters, so hex-encode our stable UID
+fn google_event_id(event: &CalendarEvent) -> String {
+    event.uid.bytes().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn events_url(calendar_id: &str, event_id: Option<&str>) -> Result<Url> {
+    let mut url = Url::parse(CALENDAR_API_URL)?;
+    {
+        let mut segments = url
+            .path_segments_mut()
+            .map_err(|_| anyhow!("Invalid Calendar API URL"))?;
+        segments.push(calendar_id).push("events");
+        if let Some(event_id) = event_id {
+            segments.push(event_id);
+        }
+    }
+    Ok(url)
+}
+
+enum SyncOutcome {
+    Created,
+    Updated,
+}
+
+fn event_body(event: &CalendarEvent) -> Value {
+    json!({
+        "id": google_event_id(event),
+        "summary": event.summary,
+        "description": event.description,
+        "start": { "date": event.start.format("%Y-%m-%d").to_string() },
+        "end": { "date": event.end.format("%Y-%m-%d").to_string() },
+        "status": "confirmed",
+        "extendedProperties": { "private": { EVENT_TAG: "1" } },
+    })
+}
+
+// Update the event if it already exists, otherwise create it under its stable ID
+fn upsert_event(
+    client: &Client,
+    access_token: &str,
+    calendar_id: &str,
+    event: &CalendarEvent,
+) -> Result<SyncOutcome> {
+    let event_id = google_event_id(event);
+    let body = event_body(event);
+
+    let response = client
+        .put(events_url(calendar_id, Some(&event_id))?)
+        .bearer_auth(access_token)
+        .json(&body)
+        .send()?;
+    if response.status().is_success() {
+        return Ok(SyncOutcome::Updated);
+    }
+    if response.status() != StatusCode::NOT_FOUND {
+        return Err(anyhow!(
+            "Updating event failed with {}: {}",
+            response.status(),
+            response.text().unwrap_or_default()
+        ));
+    }
+
+    let response = client
+        .post(events_url(calendar_id, None)?)
+        .bearer_auth(access_token)
+        .json(&body)
+        .send()?;
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "Creating event failed with {}: {}",
+            response.status(),
+            response.text().unwrap_or_default()
+        ));
+    }
+    Ok(SyncOutcome::Created)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EventList {
+    #[serde(default)]
+    items: Vec<ListedEvent>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ListedEvent {
+    id: String,
+}
+
+// IDs of every event on the calendar tagged as made by this tool, following the pages
+fn tagged_event_ids(client: &Client, access_token: &str, calendar_id: &str) -> Result<Vec<String>> {
+    let mut ids = Vec::new();
+    let mut page_token: Option<String> = None;
+    loop {
+        let mut request = client
+            .get(events_url(calendar_id, None)?)
+            .bearer_auth(access_token)
+            .query(&[
+                ("privateExtendedProperty", format!("{}=1", EVENT_TAG)),
+                ("maxResults", "2500".to_string()),
+            ]);
+        if let Some(page_token) = &page_token {
+            request = request.query(&[("pageToken", page_token)]);
+        }
+        let response = request.send()?;
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "Listing events failed with {}: {}",
+                response.status(),
+                response.text().unwrap_or_default()
+            ));
+        }
+        let list: EventList = response.json()?;
+        ids.extend(list.items.into_iter().map(|event| event.id));
+        match list.next_page_token {
+            Some(token) => page_token = Some(token),
+            None => return Ok(ids),
+        }
+    }
+}
+
+// Tagged events the current plants no longer produce, e.g. after a strategy change
+fn stale_event_ids(tagged: Vec<String>, current: &HashSet<String>) -> Vec<String> {
+    tagged
+        .into_iter()
+        .filter(|id| !current.contains(id))
+        .collect()
+}
+
+fn delete_event(
+    client: &Client,
+    access_token: &str,
+    calendar_id: &str,
+    event_id: &str,
+) -> Result<()> {
+    let response = client
+        .delete(events_url(calendar_id, Some(event_id))?)
+        .bearer_auth(access_token)
+        .send()?;
+    // Already gone is as good as deleted
+    if !response.status().is_success() && response.status() != StatusCode::GONE {
+        return Err(anyhow!(
+            "Deleting event failed with {}: {}",
+            response.status(),
+            response.text().unwrap_or_default()
+        ));
+    }
+    Ok(())
+}
+
+pub fn sync_calendar(
+    json_dir: &str,
+    frost_date: NaiveDate,
+    calendar_id: &str,
+    credentials: &OAuthCredentials,
+) -> Result<()> {
+    let client = Client::new();
+    let access_token = fetch_access_token(&client, credentials)?;
+    let plants = load_json_dir(json_dir)?;
+
+    let mut created = 0;
+    let mut updated = 0;
+    let mut deleted = 0;
+    let mut failed = 0;
+    let mut current = HashSet::new();
+    for (name, info) in &plants {
+        for event in plant_events(name, info, frost_date) {
+            current.insert(google_event_id(&event));
+            match upsert_event(&client, &access_token, calendar_id, &event) {
+                Ok(SyncOutcome::Created) => created += 1,
+                Ok(SyncOutcome::Updated) => updated += 1,
+                Err(e) => {
+                    error!("Failed to sync \"{}\": {}", event.summary, e);
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    // Events made before they were tagged are left alone; they're tagged once updated above
+    let tagged = tagged_event_ids(&client, &access_token, calendar_id)?;
+    for event_id in stale_event_ids(tagged, &current) {
+        match delete_event(&client, &access_token, calendar_id, &event_id) {
+            Ok(()) => deleted += 1,
+            Err(e) => {
+                error!("Failed to delete event {}: {}", event_id, e);
+                failed += 1;
+            }
+        }
+    }
+
+    println!(
+        "Synced {} plants to calendar {}: {} created, {} updated, {} deleted, {} failed",
+        plants.len(),
+        calendar_id,
+        created,
+        updated,
+        deleted,
+        failed
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_google_event_id_is_base32hex() {
+        let event = CalendarEvent {
+            uid: "danvers-126-carrot-sow".to_string(),
+            summary: String::new(),
+            description: String::new(),
+            start: NaiveDate::from_ymd_opt(2025, 4, 26).unwrap(),
+            end: NaiveDate::from_ymd_opt(2025, 4, 27).unwrap(),
+        };
+        let id = google_event_id(&event);
+
+        assert!(id.len() >= 5);
+        assert!(id.chars().all(|c| matches!(c, '0'..='9' | 'a'..='v')));
+        assert_eq!(id, google_event_id(&event));
+        assert_eq!(
+            event_body(&event)["extendedProperties"]["private"][EVENT_TAG],
+            "1"
+        );
+
+        let current = HashSet::from([id.clone()]);
+        assert_eq!(
+            stale_event_ids(vec![id, "6f6c64".to_string()], &current),
+            vec!["6f6c64"]
+        );
+    }
+}
@@ -0,0 +1,321 @@
+// Reading the input sheet from, and writing exports back to, Google Sheets as a service account
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use reqwest::{blocking::Client, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+
+use crate::columns::ColumnMap;
+use crate::{build_export_rows, create_http_client, export_record, ExportOptions, CSV_HEADERS};
+
+const SHEETS_API_URL: &str = "https://sheets.googleapis.com/v4/spreadsheets";
+const SHEETS_SCOPE: &str = "https://www.googleapis.com/auth/spreadsheets";
+
+// The fields we need from the JSON key file downloaded for a service account
+#[derive(Deserialize)]
+struct ServiceAccountKey {
+    client_email: String,
+    private_key: String,
+    token_uri: String,
+}
+
+#[derive(Serialize)]
+struct Claims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+// Trade a signed assertion for an access token, as Google's server-to-server OAuth flow does
+fn fetch_access_token(client: &Client, key_file: &str) -> Result<String> {
+    let content = fs::read_to_string(key_file)
+        .context(format!("Failed to read service account key: {}", key_file))?;
+    let key: ServiceAccountKey = serde_json::from_str(&content)
+        .context(format!("Invalid service account key: {}", key_file))?;
+
+    let now = Utc::now().timestamp();
+    let claims = Claims {
+        iss: &key.client_email,
+        scope: SHEETS_SCOPE,
+        aud: &key.token_uri,
+        iat: now,
+        exp: now + 3600,
+    };
+    let signing_key = jsonwebtoken::EncodingKey::from_rsa_pem(key.private_key.as_bytes())
+        .context("Invalid private key in service account key")?;
+    let assertion = jsonwebtoken::encode(
+        &jsonwebtoken::Header::new(jsonwebtoken::Algorithm::RS256),
+        &claims,
+        &signing_key,
+    )?;
+
+    let response = client
+        .post(&key.token_uri)
+        .form(&[
+            ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+            ("assertion", assertion.as_str()),
+        ])
+        .send()
+        .context("Failed to request OAuth access token")?;
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "OAuth token request failed with {}: {}",
+            response.status(),
+            response.text().unwrap_or_default()
+        ));
+    }
+    Ok(response.json::<TokenResponse>()?.access_token)
+}
+
+// The spreadsheet ID from a link such as https://docs.google.com/spreadsheets/d/<id>/edit#gid=0
+pub fn spreadsheet_id(sheet_url: &str) -> Result<String> {
+    let url = Url::parse(sheet_url).context(format!("Invalid sheet URL: {}", sheet_url))?;
+    let mut segments = url.path_segments().into_iter().flatten();
+    segments
+        .by_ref()
+        .find(|segment| *segment == "d")
+        .and_then(|_| segments.next())
+        .filter(|id| !id.is_empty())
+        .map(String::from)
+        .ok_or_else(|| anyhow!("No spreadsheet ID in {}", sheet_url))
+}
+
+// A1 notation for a whole tab, quoted so names with spaces work
+fn tab_range(tab: &str) -> String {
+    format!("'{}'", tab.replace('\'', "''"))
+}
+
+fn api_url(spreadsheet_id: &str, path: &[&str]) -> Result<Url> {
+    let mut url = Url::parse(SHEETS_API_URL)?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow!("Invalid Sheets API URL"))?
+        .push(spreadsheet_id)
+        .extend(path);
+    Ok(url)
+}
+
+fn check(response: reqwest::blocking::Response, action: &str) -> Result<Value> {
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "{} failed with {}: {}",
+            action,
+            response.status(),
+            response.text().unwrap_or_default()
+        ));
+    }
+    Ok(response.json()?)
+}
+
+pub struct Spreadsheet {
+    client: Client,
+    access_token: String,
+    id: String,
+}
+
+impl Spreadsheet {
+    pub fn open(sheet_url: &str, key_file: &str) -> Result<Self> {
+        let client = create_http_client();
+        let access_token = fetch_access_token(&client, key_file)?;
+        Ok(Spreadsheet {
+            client,
+            access_token,
+            id: spreadsheet_id(sheet_url)?,
+        })
+    }
+
+    // Every row of a tab as text, or of the first tab when none is named
+    pub fn read_tab(&self, tab: Option<&str>) -> Result<Vec<Vec<String>>> {
+        let range = tab.map_or_else(|| "A:ZZ".to_string(), tab_range);
+        let data = check(
+            self.client
+                .get(api_url(&self.id, &["values", &range])?)
+                .bearer_auth(&self.access_token)
+                .send()?,
+            "Reading the sheet",
+        )?;
+        Ok(values_to_rows(&data["values"]))
+    }
+
+    fn tab_exists(&self, tab: &str) -> Result<bool> {
+        let data = check(
+            self.client
+                .get(api_url(&self.id, &[])?)
+                .query(&[("fields", "sheets.properties.title")])
+                .bearer_auth(&self.access_token)
+                .send()?,
+            "Reading the spreadsheet",
+        )?;
+        Ok(data["sheets"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .any(|sheet| sheet["properties"]["title"] == tab))
+    }
+
+    // Replace everything in a tab with the given rows, adding the tab if it's missing
+    pub fn write_tab(&self, tab: &str, rows: &[Vec<String>]) -> Result<()> {
+        if !self.tab_exists(tab)? {
+            let mut url = api_url(&self.id, &[])?;
+            url.set_path(&format!("{}:batchUpdate", url.path()));
+            check(
+                self.client
+                    .post(url)
+                    .bearer_auth(&self.access_token)
+                    .json(&json!({"requests": [{"addSheet": {"properties": {"title": tab}}}]}))
+                    .send()?,
+                "Adding the tab",
+            )?;
+        }
+
+        let range = tab_range(tab);
+        let mut clear_url = api_url(&self.id, &["values", &range])?;
+        clear_url.set_path(&format!("{}:clear", clear_url.path()));
+        check(
+            self.client
+                .post(clear_url)
+                .bearer_auth(&self.access_token)
+                .json(&json!({}))
+                .send()?,
+            "Clearing the tab",
+        )?;
+        check(
+            self.client
+                .put(api_url(&self.id, &["values", &range])?)
+                .query(&[("valueInputOption", "RAW")])
+                .bearer_auth(&self.access_token)
+                .json(&json!({"range": range, "values": rows}))
+                .send()?,
+            "Writing the tab",
+        )?;
+        Ok(())
+    }
+}
+
+// The API leaves off trailing empty cells, so rows can be shorter than the header
+fn values_to_rows(values: &Value) -> Vec<Vec<String>> {
+    values
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|row| {
+            row.as_array()
+                .into_iter()
+                .flatten()
+                .map(|cell| match cell {
+                    Value::String(text) => text.clone(),
+                    Value::Null => String::new(),
+                    other => other.to_string(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// A tab's rows as CSV records, split off from its header row and padded to the header's width
+pub fn rows_to_records(rows: &[Vec<String>]) -> (csv::StringRecord, Vec<csv::StringRecord>) {
+    let Some((header, rows)) = rows.split_first() else {
+        return (csv::StringRecord::new(), Vec::new());
+    };
+    let records = rows
+        .iter()
+        .filter(|row| row.iter().any(|cell| !cell.trim().is_empty()))
+        .map(|row| {
+            let mut record = csv::StringRecord::from(row.clone());
+            while record.len() < header.len() {
+                record.push_field("");
+            }
+            record
+        })
+        .collect();
+    (csv::StringRecord::from(header.clone()), records)
+}
+
+// Save a tab as a CSV file in the layout it already has
+pub fn download_sheet(
+    sheet_url: &str,
+    tab: Option<&str>,
+    output_file: &str,
+    key_file: &str,
+) -> Result<()> {
+    let spreadsheet = Spreadsheet::open(sheet_url, key_file)?;
+    let (header, records) = rows_to_records(&spreadsheet.read_tab(tab)?);
+
+    let mut writer = csv::Writer::from_path(output_file)?;
+    writer.write_record(&header)?;
+    for record in &records {
+        writer.write_record(record)?;
+    }
+    writer.flush()?;
+    println!("Saved {} rows to {}", records.len(), output_file);
+    Ok(())
+}
+
+// Export the plants listed in one tab, with the same columns as a CSV export, into another
+pub fn sync_sheet(
+    sheet_url: &str,
+    input_tab: Option<&str>,
+    output_tab: &str,
+    json_dir: &str,
+    options: &ExportOptions,
+    key_file: &str,
+) -> Result<()> {
+    let spreadsheet = Spreadsheet::open(sheet_url, key_file)?;
+    let (header, records) = rows_to_records(&spreadsheet.read_tab(input_tab)?);
+    let columns = ColumnMap::from_headers(&header);
+    let records: Vec<csv::StringRecord> = records
+        .iter()
+        .map(|record| columns.normalize(record))
+        .collect();
+
+    let rows = build_export_rows(&records, json_dir, options)?;
+    let mut values = vec![CSV_HEADERS
+        .iter()
+        .map(|header| options.units.header(header))
+        .collect::<Vec<_>>()];
+    values.extend(rows.iter().map(export_record));
+    spreadsheet.write_tab(output_tab, &values)?;
+
+    println!("Exported {} plants to the {} tab", rows.len(), output_tab);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spreadsheet_id() {
+        assert_eq!(
+            spreadsheet_id("https://docs.google.com/spreadsheets/d/1AbC-xyz_9/edit#gid=0").unwrap(),
+            "1AbC-xyz_9"
+        );
+        assert!(spreadsheet_id("https://docs.google.com/document/").is_err());
+    }
+
+    #[test]
+    fn test_rows_to_records() {
+        let rows = values_to_rows(&json!([
+            ["Plant Name", "URL", "Brand"],
+            ["Carrot", "http://x"],
+            [],
+            ["Bean", "http://y", "BI"],
+        ]));
+        let (header, records) = rows_to_records(&rows);
+        assert_eq!(header.len(), 3);
+        assert_eq!(
+            records,
+            vec![
+                csv::StringRecord::from(vec!["Carrot", "http://x", ""]),
+                csv::StringRecord::from(vec!["Bean", "http://y", "BI"]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,294 @@
+// GraphQL schema over the collection, so frontends can ask for just the fields they need
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, InputObject, Object, Schema, SimpleObject,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::query::PlantFilter;
+use crate::related::RelatedPlant;
+use crate::resistance::DiseaseResistance;
+use crate::{
+    calculate_dates, determine_sowing_strategy, format_sowing_time, get_when_to_seed_start,
+    PlantInfo, Variant,
+};
+
+// Read-only GraphQL objects over the stored types, so those don't depend on the server. Each
+// field resolves to the same field of the wrapped value, and each list to views of its items.
+macro_rules! graphql_view {
+    (
+        $view:ident($type:ty) as $name:literal {
+            $($field:ident: $field_type:ty,)*
+        }
+        $(lists { $($list:ident: $list_view:ident,)* })?
+    ) => {
+        pub struct $view<'a>(&'a $type);
+
+        #[Object(name = $name)]
+        impl<'a> $view<'a> {
+            $(async fn $field(&self) -> &'a $field_type {
+                &self.0.$field
+            })*
+            $($(async fn $list(&self) -> Vec<$list_view<'a>> {
+                self.0.$list.iter().map($list_view).collect()
+            })*)?
+        }
+    };
+}
+
+graphql_view! {
+    PlantInfoView(PlantInfo) as "PlantInfo" {
+        schema_version: u32,
+        url: String,
+        title: Option<String>,
+        botanical_name: Option<String>,
+        description: Option<String>,
+        description_markdown: Option<String>,
+        days_to_maturity: Option<String>,
+        days_to_maturity_min: Option<u32>,
+        days_to_maturity_max: Option<u32>,
+        family: Option<String>,
+        plant_type: Option<String>,
+        native: Option<String>,
+        hardiness: Option<String>,
+        exposure: Option<String>,
+        plant_dimensions: Option<String>,
+        height_min_in: Option<f32>,
+        height_max_in: Option<f32>,
+        spread_min_in: Option<f32>,
+        spread_max_in: Option<f32>,
+        variety_info: Option<String>,
+        attributes: Option<String>,
+        tags: Vec<String>,
+        organic: Option<bool>,
+        heirloom: Option<bool>,
+        open_pollinated: Option<bool>,
+        hybrid: Option<bool>,
+        images: Vec<String>,
+        when_to_sow_outside: Option<String>,
+        when_to_start_inside: Option<String>,
+        days_to_emerge: Option<String>,
+        seed_depth: Option<String>,
+        seed_spacing: Option<String>,
+        row_spacing: Option<String>,
+        thinning: Option<String>,
+        special_care: Option<String>,
+        harvesting: Option<String>,
+        growing_tips: Option<String>,
+        seed_depth_min_in: Option<f32>,
+        seed_depth_max_in: Option<f32>,
+        seed_spacing_min_in: Option<f32>,
+        seed_spacing_max_in: Option<f32>,
+        row_spacing_min_in: Option<f32>,
+        row_spacing_max_in: Option<f32>,
+        plant_spacing_in: Option<f32>,
+        min_soil_temp_f: Option<i32>,
+        ideal_soil_temp_min_f: Option<i32>,
+        ideal_soil_temp_max_f: Option<i32>,
+        rating: Option<f32>,
+        votes: Option<u32>,
+        price: Option<f32>,
+        compare_at_price: Option<f32>,
+        in_stock: Option<bool>,
+        discontinued: Option<bool>,
+        archived_snapshot: Option<String>,
+        scraped_at: Option<DateTime<Utc>>,
+        source_domain: Option<String>,
+        http_status: Option<u16>,
+        parser_version: Option<u32>,
+        content_hash: Option<String>,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    }
+    lists {
+        disease_resistance: DiseaseResistanceView,
+        related: RelatedPlantView,
+        variants: VariantView,
+    }
+}
+
+graphql_view! {
+    VariantView(Variant) as "Variant" {
+        size: String,
+        sku: Option<String>,
+        price: f32,
+        compare_at_price: Option<f32>,
+        available: bool,
+    }
+}
+
+graphql_view! {
+    DiseaseResistanceView(DiseaseResistance) as "DiseaseResistance" {
+        code: String,
+        disease: String,
+    }
+}
+
+graphql_view! {
+    RelatedPlantView(RelatedPlant) as "RelatedPlant" {
+        title: String,
+        url: String,
+    }
+}
+
+pub type CollectionSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+// The frost date sowing dates are calculated from when a query doesn't give one
+struct DefaultFrostDate(NaiveDate);
+
+pub struct Plant {
+    name: String,
+    info: PlantInfo,
+}
+
+// Sowing data calculated from the scraped instructions and a frost date
+#[derive(SimpleObject, Serialize)]
+pub struct Sowing {
+    strategy: Option<String>,
+    when_to_start: Option<String>,
+    start: Option<NaiveDate>,
+    earliest_start: Option<NaiveDate>,
+    latest_start: Option<NaiveDate>,
+    transplant: Option<NaiveDate>,
+    first_harvest: Option<NaiveDate>,
+    last_harvest: Option<NaiveDate>,
+}
+
+impl Sowing {
+    pub fn calculate(info: &PlantInfo, frost_date: NaiveDate) -> Self {
+        let strategy = determine_sowing_strategy(info, None);
+        let when_to_start = get_when_to_seed_start(info, None);
+        let dates = calculate_dates(info, strategy, &when_to_start, frost_date);
+        Sowing {
+            strategy: strategy.map(|s| s.to_string()),
+            when_to_start: when_to_start.first().map(|t| format_sowing_time(Some(t))),
+            start: dates.start,
+            earliest_start: dates.earliest_start,
+            latest_start: dates.latest_start,
+            transplant: dates.transplant,
+            first_harvest: dates.first_harvest,
+            last_harvest: dates.last_harvest,
+        }
+    }
+}
+
+#[Object]
+impl Plant {
+    // The plant's name, as in its JSON file name
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn info(&self) -> PlantInfoView<'_> {
+        PlantInfoView(&self.info)
+    }
+
+    async fn sowing(&self, ctx: &Context<'_>, frost_date: Option<NaiveDate>) -> Sowing {
+        let frost_date = frost_date.unwrap_or(ctx.data_unchecked::<DefaultFrostDate>().0);
+        Sowing::calculate(&self.info, frost_date)
+    }
+}
+
+// The query command's criteria; unset criteria match anything
+#[derive(InputObject, Default)]
+pub struct PlantFilterInput {
+    family: Option<String>,
+    exposure: Option<String>,
+    min_maturity: Option<u32>,
+    max_maturity: Option<u32>,
+    #[graphql(default)]
+    tags: Vec<String>,
+    #[graphql(default)]
+    resistant_to: Vec<String>,
+}
+
+impl From<PlantFilterInput> for PlantFilter {
+    fn from(input: PlantFilterInput) -> Self {
+        PlantFilter {
+            family: input.family,
+            exposure: input.exposure,
+            min_maturity: input.min_maturity,
+            max_maturity: input.max_maturity,
+            tags: input.tags,
+            resistant_to: input.resistant_to,
+        }
+    }
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    // Every plant matching all of the given criteria, like the query command
+    async fn plants<'ctx>(
+        &self,
+        ctx: &Context<'ctx>,
+        #[graphql(default)] filter: PlantFilterInput,
+    ) -> Vec<&'ctx Plant> {
+        let filter = PlantFilter::from(filter);
+        ctx.data_unchecked::<Vec<Plant>>()
+            .iter()
+            .filter(|plant| filter.matches(&plant.info))
+            .collect()
+    }
+
+    // One plant by name, ignoring case
+    async fn plant<'ctx>(&self, ctx: &Context<'ctx>, name: String) -> Option<&'ctx Plant> {
+        ctx.data_unchecked::<Vec<Plant>>()
+            .iter()
+            .find(|plant| plant.name.eq_ignore_ascii_case(&name))
+    }
+}
+
+pub fn build_schema(plants: Vec<(String, PlantInfo)>, frost_date: NaiveDate) -> CollectionSchema {
+    let plants: Vec<Plant> = plants
+        .into_iter()
+        .map(|(name, info)| Plant { name, info })
+        .collect();
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(plants)
+        .data(DefaultFrostDate(frost_date))
+        .finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_plants_query() {
+        let carrot = PlantInfo {
+            url: "http://example.com/carrot".to_string(),
+            family: Some("Apiaceae".to_string()),
+            when_to_sow_outside: Some(
+                "RECOMMENDED. Sow 2 to 4 weeks before your average last frost date.".to_string(),
+            ),
+            ..Default::default()
+        };
+        let tomato = PlantInfo {
+            family: Some("Solanaceae".to_string()),
+            ..Default::default()
+        };
+        let schema = build_schema(
+            vec![
+                ("Carrot".to_string(), carrot),
+                ("Tomato".to_string(), tomato),
+            ],
+            NaiveDate::from_ymd_opt(2025, 5, 15).unwrap(),
+        );
+
+        let response = pollster::block_on(schema.execute(
+            r#"{ plants(filter: {family: "apiaceae"}) { name info { url } sowing { strategy start } } }"#,
+        ));
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            json!({"plants": [{
+                "name": "Carrot",
+                "info": {"url": "http://example.com/carrot"},
+                "sowing": {"strategy": "Outside", "start": "2025-05-01"},
+            }]})
+        );
+    }
+}
@@ -0,0 +1,143 @@
+// Prompting for the sowing details a page left out, so export has something better than NULL
+use anyhow::Result;
+use std::io::{self, BufRead, Write};
+
+use crate::overrides::Overrides;
+use crate::{
+    determine_sowing_strategy, extract_weeks_pattern, get_when_to_seed_start, PlantInfo,
+    SowingStrategy,
+};
+
+// Ask a question on the terminal; a blank answer is None. Prompts go to stderr so they
+// stay out of exports written to stdout
+pub fn ask(question: &str) -> Result<Option<String>> {
+    eprint!("{} ", question);
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    let answer = answer.trim();
+    Ok((!answer.is_empty()).then(|| answer.to_string()))
+}
+
+// The strategy a non-blank answer names, or None if it names neither
+fn parse_strategy(answer: &str) -> Option<SowingStrategy> {
+    match answer.to_lowercase().as_str() {
+        "i" | "inside" => Some(SowingStrategy::Inside),
+        "o" | "outside" => Some(SowingStrategy::Outside),
+        _ => None,
+    }
+}
+
+fn ask_strategy(question: &str) -> Result<Option<SowingStrategy>> {
+    loop {
+        let Some(answer) = ask(question)? else {
+            return Ok(None);
+        };
+        match parse_strategy(&answer) {
+            Some(strategy) => return Ok(Some(strategy)),
+            None => eprintln!("Please answer i or o, or leave it blank to skip"),
+        }
+    }
+}
+
+// Whether the date calculation can read a sowing time from the answer
+fn reads_as_sowing_time(answer: &str) -> bool {
+    !extract_weeks_pattern(answer).is_empty()
+}
+
+// Ask until the answer reads as a sowing time, or is left blank
+fn ask_sowing_time(plant: &str) -> Result<Option<String>> {
+    loop {
+        let question = format!(
+            "When should {} be sown? (e.g. \"2 to 4 weeks before your average last frost date\", blank to skip)",
+            plant
+        );
+        match ask(&question)? {
+            Some(text) if !reads_as_sowing_time(&text) => {
+                eprintln!("Couldn't read a sowing time from that; try the example's wording")
+            }
+            answer => return Ok(answer),
+        }
+    }
+}
+
+// Prompt for whatever keeps the plant's sowing date from being calculated, recording
+// answers as overrides; returns whether anything was recorded
+pub fn fill_missing(
+    plant: &str,
+    info: &PlantInfo,
+    user_strategy: Option<SowingStrategy>,
+    overrides: &mut Overrides,
+) -> Result<bool> {
+    let user_strategy = user_strategy.or(overrides.strategy(plant));
+    let recommended = |text: &Option<String>| {
+        text.as_deref()
+            .is_some_and(|text| text.contains("RECOMMENDED"))
+    };
+    let mut changed = false;
+
+    // Both methods described and neither recommended: the default of outside is a guess
+    let ambiguous = info.when_to_sow_outside.is_some()
+        && info.when_to_start_inside.is_some()
+        && !recommended(&info.when_to_sow_outside)
+        && !recommended(&info.when_to_start_inside);
+    let strategy = match determine_sowing_strategy(info, user_strategy) {
+        Some(_) if user_strategy.is_none() && ambiguous => {
+            let question = format!(
+                "{} can be started inside or sown outside. Which do you do? [i/o]",
+                plant
+            );
+            ask_strategy(&question)?
+        }
+        Some(strategy) => Some(strategy),
+        None => {
+            eprintln!("{} has no sowing instructions.", plant);
+            ask_strategy(&format!("Start {} [i]nside or sow [o]utside?", plant))?
+        }
+    };
+    if let Some(strategy) = strategy.filter(|_| user_strategy.is_none()) {
+        overrides.set_strategy(plant, strategy);
+        changed = true;
+    }
+
+    let Some(strategy) = strategy.or(user_strategy) else {
+        return Ok(changed);
+    };
+    if get_when_to_seed_start(info, Some(strategy)).is_empty() {
+        if let Some(text) = ask_sowing_time(plant)? {
+            let field = match strategy {
+                SowingStrategy::Inside => "when_to_start_inside",
+                SowingStrategy::Outside => "when_to_sow_outside",
+            };
+            overrides.set(plant, field, text);
+            changed = true;
+        }
+    }
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_strategy() {
+        assert_eq!(parse_strategy("i"), Some(SowingStrategy::Inside));
+        assert_eq!(parse_strategy("Inside"), Some(SowingStrategy::Inside));
+        assert_eq!(parse_strategy("O"), Some(SowingStrategy::Outside));
+        assert_eq!(parse_strategy("outside"), Some(SowingStrategy::Outside));
+        assert_eq!(parse_strategy("both"), None);
+        assert_eq!(parse_strategy("in"), None);
+    }
+
+    #[test]
+    fn test_reads_as_sowing_time() {
+        assert!(reads_as_sowing_time(
+            "2 to 4 weeks before your average last frost date"
+        ));
+        assert!(reads_as_sowing_time("1 week after transplanting"));
+        assert!(!reads_as_sowing_time("6 weeks before last frost"));
+        assert!(reads_as_sowing_time("early spring"));
+        assert!(!reads_as_sowing_time("whenever"));
+    }
+}
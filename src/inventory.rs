@@ -0,0 +1,147 @@
+// Seeds on hand per plant, kept beside the plant JSON so exports can flag what to reorder
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::OutputFormat;
+
+pub const INVENTORY_FILE: &str = "inventory.toml";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Stock {
+    // Loose seeds across every packet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub packets: Option<u32>,
+    // Purchase or packed-for year of the seeds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lot: Option<String>,
+    // Where the packets are kept, e.g. "Shoebox 2"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+impl Stock {
+    // Take the fields the update sets, keeping the rest
+    fn merge(&mut self, update: Stock) {
+        self.quantity = update.quantity.or(self.quantity);
+        self.packets = update.packets.or(self.packets);
+        self.lot = update.lot.or(self.lot.take());
+        self.location = update.location.or(self.location.take());
+    }
+}
+
+// Whether the seeds on hand cover every planned sowing, when both are known
+pub fn enough_seed(
+    stock: Option<&Stock>,
+    seeds_per_sowing: Option<u32>,
+    sowings: u32,
+) -> Option<bool> {
+    let quantity = stock?.quantity?;
+    Some(quantity >= seeds_per_sowing? * sowings)
+}
+
+// Tables of stock keyed by plant name, e.g. `[Carrot]` then `quantity = 400`
+#[derive(Debug, Default)]
+pub struct Inventory {
+    path: PathBuf,
+    plants: BTreeMap<String, Stock>,
+}
+
+impl Inventory {
+    pub fn load(json_dir: &str) -> Result<Self> {
+        let path = Path::new(json_dir).join(INVENTORY_FILE);
+        let plants = match fs::read_to_string(&path) {
+            Ok(content) => {
+                toml::from_str(&content).context(format!("Failed to parse {}", path.display()))?
+            }
+            Err(_) => BTreeMap::new(),
+        };
+        Ok(Inventory { path, plants })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, toml::to_string(&self.plants)?)
+            .context(format!("Failed to write {}", self.path.display()))
+    }
+
+    pub fn get(&self, plant: &str) -> Option<&Stock> {
+        self.plants.get(plant)
+    }
+
+    pub fn update(&mut self, plant: &str, update: Stock) -> &Stock {
+        let stock = self.plants.entry(plant.to_string()).or_default();
+        stock.merge(update);
+        stock
+    }
+
+    pub fn remove(&mut self, plant: &str) -> Result<Stock> {
+        self.plants
+            .remove(plant)
+            .ok_or_else(|| anyhow!("{} isn't in the inventory", plant))
+    }
+}
+
+// Print the stock of every plant in the inventory
+pub fn list_inventory(json_dir: &str, output: OutputFormat) -> Result<()> {
+    let inventory = Inventory::load(json_dir)?;
+    if inventory.plants.is_empty() {
+        output.print_text(&format!("No seeds in the inventory in {}", json_dir));
+    } else {
+        output.print_text(&format!(
+            "{:<30} {:>8} {:>7}  {:<6} {}",
+            "Plant", "Seeds", "Packets", "Lot", "Location"
+        ));
+    }
+    let field = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+    for (plant, stock) in &inventory.plants {
+        output.print_text(&format!(
+            "{:<30} {:>8} {:>7}  {:<6} {}",
+            plant,
+            field(stock.quantity.map(|quantity| quantity.to_string())),
+            field(stock.packets.map(|packets| packets.to_string())),
+            field(stock.lot.clone()),
+            field(stock.location.clone())
+        ));
+    }
+    output.print_json(&serde_json::to_value(&inventory.plants)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_and_check_stock() {
+        let mut inventory = Inventory::default();
+        inventory.update(
+            "Carrot",
+            Stock {
+                quantity: Some(300),
+                location: Some("Shoebox 2".to_string()),
+                ..Default::default()
+            },
+        );
+        let stock = inventory.update(
+            "Carrot",
+            Stock {
+                quantity: Some(100),
+                packets: Some(1),
+                ..Default::default()
+            },
+        );
+        assert_eq!(stock.quantity, Some(100));
+        assert_eq!(stock.location.as_deref(), Some("Shoebox 2"));
+
+        let stock = inventory.get("Carrot");
+        assert_eq!(enough_seed(stock, Some(40), 2), Some(true));
+        assert_eq!(enough_seed(stock, Some(40), 3), Some(false));
+        assert_eq!(enough_seed(stock, None, 3), None);
+        assert_eq!(enough_seed(inventory.get("Tomato"), Some(4), 1), None);
+        assert!(inventory.remove("Tomato").is_err());
+    }
+}
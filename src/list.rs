@@ -0,0 +1,120 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::fs;
+
+use crate::names::PlantIndex;
+use crate::{determine_sowing_strategy, load_json_dir, warn_outdated, OutputFormat, PlantInfo};
+
+const HEADERS: [&str; 5] = ["Name", "Family", "Maturity", "Strategy", "Scraped"];
+
+pub fn summary_row(index: &PlantIndex, name: &str, info: &PlantInfo) -> [String; 5] {
+    let maturity = match info.maturity_days() {
+        Some((min, max)) if min == max => format!("{} days", min),
+        Some((min, max)) => format!("{}-{} days", min, max),
+        None => "-".to_string(),
+    };
+    // Older files predate scrape metadata, so fall back to their modification time
+    let scraped_at = info.scraped_at.or_else(|| {
+        fs::metadata(index.path_for(name))
+            .and_then(|m| m.modified())
+            .ok()
+            .map(DateTime::<Utc>::from)
+    });
+
+    [
+        name.to_string(),
+        info.family.clone().unwrap_or_else(|| "-".to_string()),
+        maturity,
+        determine_sowing_strategy(info, None).map_or_else(|| "-".to_string(), |s| s.to_string()),
+        scraped_at.map_or_else(|| "-".to_string(), |t| t.format("%Y-%m-%d").to_string()),
+    ]
+}
+
+// A summary row as JSON, with the "-" placeholders as nulls
+pub fn summary_object(row: &[String; 5]) -> Value {
+    let value = |value: &String| {
+        if value == "-" {
+            Value::Null
+        } else {
+            json!(value)
+        }
+    };
+    json!({
+        "name": row[0],
+        "family": value(&row[1]),
+        "maturity": value(&row[2]),
+        "strategy": value(&row[3]),
+        "scraped": value(&row[4]),
+    })
+}
+
+// Left-align each column to its widest value
+pub fn format_table(rows: &[[String; 5]]) -> String {
+    let mut widths = HEADERS.map(|header| header.chars().count());
+    for row in rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(value.chars().count());
+        }
+    }
+
+    let header = HEADERS.map(String::from);
+    let rule = widths.map(|width| "-".repeat(width));
+    std::iter::once(&header)
+        .chain(std::iter::once(&rule))
+        .chain(rows)
+        .map(|row| {
+            row.iter()
+                .zip(widths)
+                .map(|(value, width)| format!("{:<width$}", value, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Print a one-line summary of every plant in a results directory
+pub fn list_plants(json_dir: &str, output: OutputFormat) -> Result<()> {
+    let plants = load_json_dir(json_dir)?;
+    warn_outdated(plants.iter().map(|(_, info)| info));
+    let index = PlantIndex::load(json_dir)?;
+    let rows: Vec<[String; 5]> = plants
+        .iter()
+        .map(|(name, info)| summary_row(&index, name, info))
+        .collect();
+
+    if output == OutputFormat::Json {
+        output.print_json(&rows.iter().map(summary_object).collect())?;
+    } else {
+        println!("{}", format_table(&rows));
+    }
+    output.print_text(&format!("\n{} plants", rows.len()));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_table() {
+        let info: PlantInfo = serde_json::from_str(
+            r#"{"url": "u", "family": "Apiaceae", "days_to_maturity": "55-70 days",
+                "when_to_sow_outside": "RECOMMENDED. 2 to 4 weeks before your average last frost date",
+                "scraped_at": "2025-03-01T12:00:00Z"}"#,
+        )
+        .unwrap();
+        let row = summary_row(&PlantIndex::load("results").unwrap(), "Carrot", &info);
+        assert_eq!(summary_object(&row)["maturity"], "55-70 days");
+
+        assert_eq!(
+            format_table(&[row]),
+            "Name    Family    Maturity    Strategy  Scraped\n\
+             ------  --------  ----------  --------  ----------\n\
+             Carrot  Apiaceae  55-70 days  Outside   2025-03-01"
+        );
+    }
+}
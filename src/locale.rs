@@ -0,0 +1,86 @@
+// How dates and measurements are written for a region, e.g. en-GB for 31/03/2025 and metric
+use std::sync::OnceLock;
+
+use crate::Units;
+
+// Regions writing day.month.year
+const DOT_REGIONS: [&str; 12] = [
+    "AT", "CH", "CZ", "DE", "DK", "FI", "NO", "PL", "RU", "SK", "TR", "UA",
+];
+// Regions writing year-month-day anyway
+const ISO_REGIONS: [&str; 7] = ["CN", "HU", "JP", "KR", "LT", "SE", "TW"];
+// Regions still using inches and Fahrenheit, which also write month/day/year
+const IMPERIAL_REGIONS: [&str; 3] = ["US", "LR", "MM"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Locale {
+    pub date_format: &'static str,
+    pub units: Units,
+}
+
+// A tag such as en-GB, de_DE, or fr_CA.UTF-8; only the region matters
+pub fn parse_locale(text: &str) -> Result<Locale, String> {
+    let tag = text.split('.').next().unwrap_or_default();
+    let region = tag
+        .split(['-', '_'])
+        .nth(1)
+        .filter(|region| region.len() == 2 && region.chars().all(|c| c.is_ascii_alphabetic()))
+        .ok_or_else(|| format!("expected LANGUAGE-REGION, e.g. en-GB, got \"{}\"", text))?
+        .to_ascii_uppercase();
+
+    let region = region.as_str();
+    Ok(if IMPERIAL_REGIONS.contains(&region) {
+        Locale {
+            date_format: "%m/%d/%Y",
+            units: Units::Imperial,
+        }
+    } else if DOT_REGIONS.contains(&region) {
+        Locale {
+            date_format: "%d.%m.%Y",
+            units: Units::Metric,
+        }
+    } else if ISO_REGIONS.contains(&region) {
+        Locale {
+            date_format: "%Y-%m-%d",
+            units: Units::Metric,
+        }
+    } else {
+        Locale {
+            date_format: "%d/%m/%Y",
+            units: Units::Metric,
+        }
+    })
+}
+
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+// Set once from the global --locale flag
+pub fn set_locale(locale: Option<Locale>) {
+    if let Some(locale) = locale {
+        let _ = LOCALE.set(locale);
+    }
+}
+
+// ISO dates unless a locale was given, so exports stay easy for other programs to read
+pub fn date_format() -> &'static str {
+    LOCALE.get().map_or("%Y-%m-%d", |locale| locale.date_format)
+}
+
+// The locale's units, for commands given no --units
+pub fn units(units: Option<Units>) -> Units {
+    units.unwrap_or_else(|| LOCALE.get().map_or(Units::Imperial, |locale| locale.units))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_locale() {
+        let gb = parse_locale("en-GB").unwrap();
+        assert_eq!((gb.date_format, gb.units), ("%d/%m/%Y", Units::Metric));
+        assert_eq!(parse_locale("de_DE.UTF-8").unwrap().date_format, "%d.%m.%Y");
+        assert_eq!(parse_locale("en_us").unwrap().units, Units::Imperial);
+        assert!(parse_locale("en").is_err());
+    }
+}
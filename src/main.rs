@@ -1,60 +1,1169 @@
 use anyhow::{Context, Result};
-use chrono::{Days, NaiveDate};
+use chrono::{DateTime, Datelike, Days, Duration, Local, NaiveDate, Utc};
 use clap::Parser;
-use regex;
 use scraper::Element;
 use scraper::{Html, Selector};
 use serde::{Deserialize, Serialize};
-use std::{fs, path::Path, thread, time::Duration as StdDuration};
+use std::collections::HashMap;
+use std::io::{self, IsTerminal};
+use std::sync::{LazyLock, OnceLock};
+use std::time::{Duration as StdDuration, Instant};
+use std::{fs, path::Path, thread};
+use tracing::{error, info, info_span, warn};
+use tracing_subscriber::{filter::LevelFilter, fmt, prelude::*};
+
+use error::{compiled, regex, selector, Pattern, ScrapingError};
+
+mod airtable;
+mod backoff;
+mod bundle;
+mod calendar;
+mod charset;
+mod collection;
+mod columns;
+mod companions;
+mod database;
+mod description;
+mod diff;
+mod digest;
+mod duplicates;
+mod edit;
+mod error;
+mod feed;
+mod fixtures;
+mod formats;
+mod germination;
+mod google_calendar;
+mod google_sheets;
+mod graphql;
+mod interactive;
+mod inventory;
+mod list;
+mod locale;
+mod mcp;
+mod measurements;
+mod names;
+mod notify;
+mod overrides;
+mod pdf;
+mod phrases;
+mod planting_log;
+mod profiles;
+mod query;
+mod related;
+mod reminders;
+mod resistance;
+mod rotation;
+mod schema;
+mod server;
+mod show;
+mod spacing;
+mod stats;
+mod tasks;
+mod tui;
+mod updates;
+mod urls;
+mod user_agents;
+mod validate;
+mod viability;
+mod watch;
+mod wayback;
+mod weather;
 
 // Constants for CSV field management
-const CSV_FIELD_COUNT: usize = 29; // Total number of fields in a CSV record
+const CSV_FIELD_COUNT: usize = 74; // Total number of fields in a CSV record
 const INPUT_FIELD_COUNT: usize = 6; // Number of fields from input CSV
 const ERR_FIELD_COUNT: usize = CSV_FIELD_COUNT - INPUT_FIELD_COUNT; // Fields to fill with "ERR"
 
+// Header row for exported CSV files, in the same order as OutputRecord::to_record
+const CSV_HEADERS: [&str; CSV_FIELD_COUNT] = [
+    "Plant Name",
+    "URL",
+    "Brand",                 // New column
+    "Purchase Year",         // New column
+    "Notes",                 // New column
+    "Users Sowing Strategy", // New column to be preserved
+    "Title",
+    "Description",
+    "Days to Maturity",
+    "Family",
+    "Plant Type",
+    "Native",
+    "Hardiness",
+    "Exposure",
+    "Plant Dimensions",
+    "Variety Info",
+    "Attributes",
+    "When to Sow Outside",
+    "When to Start Inside",
+    "Days to Emerge",
+    "Seed Depth",
+    "Seed Spacing",
+    "Row Spacing",
+    "Thinning",
+    "Rating",
+    "Votes",
+    "Sowing Strategy",
+    "When to Seed Start",
+    "Calculated Start Date",
+    "Earliest Start",
+    "Latest Start",
+    "Transplant Date",
+    "Estimated First Harvest",
+    "Estimated Last Harvest",
+    "Scrape Age (Days)",
+    "Price",
+    "Compare At Price",
+    "In Stock",
+    "Botanical Name",
+    "Min Soil Temp (F)",
+    "Ideal Soil Temp Min (F)",
+    "Ideal Soil Temp Max (F)",
+    "Days to Maturity Min",
+    "Days to Maturity Max",
+    "Height Min (in)",
+    "Height Max (in)",
+    "Spread Min (in)",
+    "Spread Max (in)",
+    "Seed Depth Min (in)",
+    "Seed Depth Max (in)",
+    "Seed Spacing Min (in)",
+    "Seed Spacing Max (in)",
+    "Row Spacing Min (in)",
+    "Row Spacing Max (in)",
+    "Organic",
+    "Heirloom",
+    "Open Pollinated",
+    "F1 Hybrid",
+    "Disease Resistance",
+    "Possible Duplicate Of",
+    "Plant Spacing (in)",
+    "Plants per Sq Ft",
+    "Plants per Bed",
+    "Seeds Needed",
+    "Good Neighbors",
+    "Bad Neighbors",
+    "Alternate Sowing Windows",
+    "Overridden Fields",
+    "Status",
+    "Germination Rate (%)",
+    "Seeds On Hand",
+    "Seed Location",
+    "Enough Seed",
+    "Seed Viability",
+];
+
+// Average last frost date used when none is given on the command line
+const DEFAULT_FROST_DATE: &str = "2025-05-10";
+
+// Bump whenever PlantInfo::from_html changes what it extracts, so older scrapes can be
+// told apart from current ones
+const PARSER_VERSION: u32 = 10;
+
+// Indoor starts are assumed to be transplanted this many days after the last frost
+// unless the product page says otherwise
+const TRANSPLANT_OFFSET_DAYS: i64 = 21;
+
+// Without --fall-frost-date the first fall frost is assumed to come this many days after the
+// last spring frost, and the ground to freeze this many days after that, for pages that time
+// sowing from the fall
+const FROST_FREE_DAYS: i64 = 153;
+const GROUND_FREEZE_OFFSET_DAYS: i64 = 28;
+
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     #[command(subcommand)]
     command: Commands,
+    /// Proxy for all web requests, e.g. http://proxy:3128
+    #[arg(long, global = true, env = "SEED_SCRAPER_PROXY")]
+    proxy: Option<String>,
+    /// TOML file of per-domain settings: a table per domain with any of delay, timeout,
+    /// user_agents, and headers, used instead of the global ones for pages on that domain
+    #[arg(long, global = true, env = "SEED_SCRAPER_PROFILES")]
+    profiles: Option<String>,
+    /// User agent to send with page requests; repeat to take turns between several
+    #[arg(long = "user-agent", global = true)]
+    user_agents: Vec<String>,
+    /// When to move on to the next --user-agent
+    #[arg(long, global = true, value_enum, default_value_t = user_agents::RotateBy::Request)]
+    rotate_by: user_agents::RotateBy,
+    /// Also send the Sec-Fetch and client hint headers a browser sends with its user agent
+    #[arg(long, global = true)]
+    browser_headers: bool,
+    /// When a product page is blocked or gone, parse archive.org's latest snapshot of it instead
+    #[arg(long, global = true)]
+    wayback: bool,
+    /// Seconds to wait between page requests
+    #[arg(long, global = true, env = "SEED_SCRAPER_DELAY", default_value_t = 2.0)]
+    delay: f64,
+    /// Seconds to wait on a web request, for the connection or for more of the response,
+    /// before giving up on it
+    #[arg(
+        long,
+        global = true,
+        env = "SEED_SCRAPER_TIMEOUT",
+        default_value_t = 30.0
+    )]
+    timeout: f64,
+    /// Seconds to wait for a web server to accept a connection
+    #[arg(
+        long,
+        global = true,
+        env = "SEED_SCRAPER_CONNECT_TIMEOUT",
+        default_value_t = 10.0
+    )]
+    connect_timeout: f64,
+    /// Seconds a batch spends on one plant's page, related products, and image before moving on
+    #[arg(
+        long,
+        global = true,
+        env = "SEED_SCRAPER_PLANT_TIMEOUT",
+        default_value_t = 120.0
+    )]
+    plant_timeout: f64,
+    /// Most verbose messages to log: error, warn, info, debug or trace
+    #[arg(long, global = true, env = "SEED_SCRAPER_LOG_LEVEL", default_value_t = tracing::Level::INFO)]
+    log_level: tracing::Level,
+    /// Also append log messages to this file, for looking into long batches afterwards
+    #[arg(long, global = true, env = "SEED_SCRAPER_LOG_FILE")]
+    log_file: Option<String>,
+    /// How to print results; json puts them on stdout and messages on stderr. Give it
+    /// before the command, since some commands have their own --output file
+    #[arg(long = "output", env = "SEED_SCRAPER_OUTPUT", value_enum, default_value_t = OutputFormat::Text)]
+    output_format: OutputFormat,
+    /// Also read the input CSV column COLUMN from a header named HEADER, e.g. brand=Supplier.
+    /// Columns: plant, url, brand, year, notes, strategy, frost_date, start_date
+    #[arg(long = "column", global = true, env = "SEED_SCRAPER_COLUMNS", value_delimiter = ',', value_parser = columns::parse_column_alias)]
+    columns: Vec<columns::ColumnAlias>,
+    /// Read sowing instructions containing PHRASE as WEEKS from the last frost date, e.g.
+    /// "early spring=-6"; these come before the built-in phrases
+    #[arg(long = "sowing-phrase", global = true, env = "SEED_SCRAPER_SOWING_PHRASES", value_delimiter = ',', value_parser = phrases::parse_sowing_phrase)]
+    sowing_phrases: Vec<phrases::SowingPhrase>,
+    /// Average first fall frost date, for sowing timed from the fall; its month and day are
+    /// used in each season. Defaults to 153 days after the last frost date
+    #[arg(long, global = true, env = "SEED_SCRAPER_FALL_FROST_DATE")]
+    fall_frost_date: Option<NaiveDate>,
+    /// Write exported dates and measurements the way a region does, e.g. en-GB for
+    /// DD/MM/YYYY and metric; dates are YYYY-MM-DD without one
+    #[arg(long, global = true, env = "SEED_SCRAPER_LOCALE", value_parser = locale::parse_locale)]
+    locale: Option<locale::Locale>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+enum OutputFormat {
+    /// Tables and summaries for reading
+    Text,
+    /// Structured results on stdout for scripts, with everything else on stderr
+    Json,
+}
+
+impl OutputFormat {
+    // Text meant for people, which moves to stderr when stdout is carrying JSON
+    fn print_text(self, text: &str) {
+        match self {
+            OutputFormat::Text => println!("{}", text),
+            OutputFormat::Json => eprintln!("{}", text),
+        }
+    }
+
+    // A command's structured result, only printed in JSON mode
+    fn print_json(self, value: &serde_json::Value) -> Result<()> {
+        if self == OutputFormat::Json {
+            println!("{}", serde_json::to_string_pretty(value)?);
+        }
+        Ok(())
+    }
+}
+
+// Settings for every command that fetches pages, set once from the global flags
+struct NetworkSettings {
+    proxy: Option<reqwest::Proxy>,
+    delay: StdDuration,
+    timeout: StdDuration,
+    connect_timeout: StdDuration,
+    plant_timeout: StdDuration,
+    wayback: bool,
+}
+
+static NETWORK: OnceLock<NetworkSettings> = OnceLock::new();
+
+fn network() -> &'static NetworkSettings {
+    NETWORK.get_or_init(|| NetworkSettings {
+        proxy: None,
+        delay: StdDuration::from_secs(2),
+        timeout: StdDuration::from_secs(30),
+        connect_timeout: StdDuration::from_secs(10),
+        plant_timeout: StdDuration::from_secs(120),
+        wayback: false,
+    })
+}
+
+// How long to sleep before requesting a page from the URL's store
+fn request_delay(url: &str) -> StdDuration {
+    profiles::for_url(url)
+        .and_then(|profile| profile.delay)
+        .unwrap_or(network().delay)
+}
+
+// How long to wait on a page from the URL's store
+fn request_timeout(url: &str) -> StdDuration {
+    profiles::for_url(url)
+        .and_then(|profile| profile.timeout)
+        .unwrap_or(network().timeout)
+}
+
+// A request for a product page, with its store's profile applied
+fn page_request(
+    client: &reqwest::blocking::Client,
+    url: &str,
+) -> reqwest::blocking::RequestBuilder {
+    let profile = profiles::for_url(url);
+    let domain_agents = profile.map_or(&[][..], |profile| &profile.user_agents);
+    let request = client
+        .get(url)
+        .timeout(request_timeout(url))
+        .headers(user_agents::headers_for(
+            url,
+            domain_agents,
+            DEFAULT_USER_AGENT,
+        ));
+    match profile {
+        Some(profile) => request.headers(profile.headers.clone()),
+        None => request,
+    }
 }
 
 #[derive(Parser)]
 enum Commands {
-    /// Scrape a single URL
+    /// Scrape one or more product pages without building a CSV
     Single {
+        /// Page to scrape; repeat to scrape several, one after another
         #[arg(short, long)]
-        url: String,
+        url: Vec<String>,
+        /// Plain text file of page URLs to scrape, one per line
+        #[arg(long)]
+        url_file: Option<String>,
+        /// Parse a page saved from a browser instead of fetching it. Its URL is read from the
+        /// page unless one --url is given
+        #[arg(long, conflicts_with = "url_file")]
+        file: Option<String>,
+        /// Also save the JSON here; several pages are saved as one JSON array
         #[arg(short, long)]
         output: Option<String>,
     },
     /// Process a CSV file containing plant names and URLs
+    ///
+    /// Exits with 0 when every plant was scraped or already saved, 2 when some plants failed,
+    /// 3 when all of them failed, 4 when Cloudflare blocked any request, and 5 when it stopped
+    /// early after --max-blocks blocks in a row.
     Batch {
+        /// Input CSV, or - to read it from stdin
         #[arg(short, long)]
         file: String,
-        #[arg(short, long)]
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
         json_dir: String,
+        /// Also save each plant's primary product image into this directory
+        #[arg(long)]
+        download_images: Option<String>,
+        /// URL to POST a JSON summary to when the batch finishes, and alerts when Cloudflare blocks it
+        #[arg(long)]
+        webhook_url: Option<String>,
+        /// Ask for sowing details a page is missing, saving answers to overrides.toml
+        #[arg(long)]
+        interactive: bool,
+        /// Stop after this many plants in a row are blocked by Cloudflare, saving them and the
+        /// plants not yet tried to remaining.csv in the JSON directory
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+        max_blocks: u32,
     },
     /// Export data from JSON files to CSV, using input CSV for additional columns
     Export {
+        /// Input CSV, or - to read it from stdin
         #[arg(short, long)]
         input_file: String,
+        /// File to write, or - to write CSV, JSONL, or template exports to stdout
         #[arg(short, long)]
         output_file: String,
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        #[arg(short, long, env = "SEED_SCRAPER_FROST_DATE", default_value = DEFAULT_FROST_DATE)]
+        frost_date: NaiveDate,
+        #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+        /// How to group plants in report formats
+        #[arg(long, value_enum, default_value_t = ReportGrouping::Family)]
+        group_by: ReportGrouping,
+        /// Label grid as COLUMNSxROWS, e.g. 4x12, instead of the Avery 5160 layout
+        #[arg(long, value_parser = formats::labels::parse_label_grid)]
+        label_grid: Option<(usize, usize)>,
+        /// Which packet size the price columns describe
+        #[arg(long, value_enum, default_value_t = VariantChoice::Selected)]
+        variant: VariantChoice,
+        /// Units for the parsed depth, spacing, dimension, and temperature columns; defaults to
+        /// the --locale's, or imperial
+        #[arg(long, value_enum)]
+        units: Option<Units>,
+        /// Bed size in feet as WIDTHxLENGTH, e.g. 4x8, for the Plants per Bed column
+        #[arg(long, value_parser = spacing::parse_bed_size)]
+        bed_size: Option<spacing::BedSize>,
+        /// Row length in feet for the Seeds Needed column, instead of filling the bed
+        #[arg(long)]
+        row_length: Option<f32>,
+        /// Successive sowings of each plant, each using the Seeds Needed column's count, that
+        /// the Enough Seed column checks the inventory against
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        sowings: u32,
+        /// Ask for sowing details a plant is missing, saving answers to overrides.toml
+        #[arg(long)]
+        interactive: bool,
+        /// Only export plants with this attribute tag, e.g. "Frost Tolerant"; repeat to require several
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Only export plants resistant to this disease, by code (TMV) or name (blight); repeatable
+        #[arg(long = "resistant-to")]
+        resistant_to: Vec<String>,
+        /// Order rows by this instead of the input CSV's order, then by name
+        #[arg(long, value_enum)]
+        sort_by: Option<SortKey>,
+        /// CSV columns to write, in order, as comma-separated headers, e.g. "Plant Name,Family";
+        /// or a preset: planner
+        #[arg(long = "columns", value_parser = parse_export_columns)]
+        export_columns: Option<ColumnSelection>,
+        /// Write a short CSV layout instead of every column
+        #[arg(long, value_enum, conflicts_with = "export_columns")]
+        preset: Option<ExportPreset>,
+        /// Handlebars template for --format template; it gets `plants`, each with the fields of
+        /// a jsonl line, and `generated_at`
+        #[arg(long)]
+        template: Option<String>,
+    },
+    /// Export sowing, transplant, and harvest dates to an iCalendar file
+    Calendar {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        #[arg(short, long, env = "SEED_SCRAPER_FROST_DATE", default_value = DEFAULT_FROST_DATE)]
+        frost_date: NaiveDate,
+        #[arg(short, long)]
+        output: String,
+    },
+    /// List what to start, sow, pot up, and transplant during one week
+    Tasks {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        /// First day of the week; defaults to this week's Monday
+        #[arg(short, long)]
+        week: Option<NaiveDate>,
+        #[arg(short, long, env = "SEED_SCRAPER_FROST_DATE", default_value = DEFAULT_FROST_DATE)]
+        frost_date: NaiveDate,
+        /// Garden location as LATITUDE,LONGITUDE, to warn about outdoor tasks that the next
+        /// 16 days' forecast frost or cold soil would spoil
+        #[arg(long, env = "SEED_SCRAPER_LOCATION", value_parser = weather::parse_location)]
+        location: Option<(f64, f64)>,
+    },
+    /// Create or update sowing, transplant, and harvest events in a Google Calendar
+    SyncCalendar {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        #[arg(short, long, env = "SEED_SCRAPER_FROST_DATE", default_value = DEFAULT_FROST_DATE)]
+        frost_date: NaiveDate,
+        /// Calendar to sync into, e.g. "primary" or "abc123@group.calendar.google.com"
+        #[arg(short, long)]
+        calendar_id: String,
+        #[arg(long, env = "GOOGLE_CLIENT_ID")]
+        client_id: String,
+        #[arg(long, env = "GOOGLE_CLIENT_SECRET", hide_env_values = true)]
+        client_secret: String,
+        #[arg(long, env = "GOOGLE_REFRESH_TOKEN", hide_env_values = true)]
+        refresh_token: String,
+    },
+    /// Save a Google Sheets tab as an input CSV for batch and export
+    DownloadSheet {
+        /// Link to the spreadsheet, shared with the service account
+        #[arg(short, long)]
+        sheet: String,
+        /// Tab to read; the first one when not given
+        #[arg(short, long)]
+        tab: Option<String>,
+        #[arg(short, long)]
+        output: String,
+        /// JSON key file for the service account
+        #[arg(long, env = "GOOGLE_APPLICATION_CREDENTIALS")]
+        key_file: String,
+    },
+    /// Export the plants listed in one Google Sheets tab into another tab of the same sheet
+    SyncSheet {
+        /// Link to the spreadsheet, shared with the service account as an editor
+        #[arg(short, long)]
+        sheet: String,
+        /// Tab listing the plants, in the input CSV's layout; the first one when not given
+        #[arg(long)]
+        input_tab: Option<String>,
+        /// Tab to replace with the export, added if it doesn't exist
+        #[arg(long, default_value = "Export")]
+        output_tab: String,
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        #[arg(short, long, env = "SEED_SCRAPER_FROST_DATE", default_value = DEFAULT_FROST_DATE)]
+        frost_date: NaiveDate,
+        /// Units for the parsed depth, spacing, dimension, and temperature columns; defaults to
+        /// the --locale's, or imperial
+        #[arg(long, value_enum)]
+        units: Option<Units>,
+        /// JSON key file for the service account
+        #[arg(long, env = "GOOGLE_APPLICATION_CREDENTIALS")]
+        key_file: String,
+    },
+    /// Create or update a record in an Airtable table for each plant in the input CSV
+    SyncAirtable {
+        #[arg(short, long)]
+        input_file: String,
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        #[arg(short, long, env = "SEED_SCRAPER_FROST_DATE", default_value = DEFAULT_FROST_DATE)]
+        frost_date: NaiveDate,
+        /// Base ID, from the base's API docs or URL, e.g. appXXXXXXXXXXXXXX
+        #[arg(long, env = "AIRTABLE_BASE_ID")]
+        base: String,
+        /// Table name or ID
+        #[arg(long, env = "AIRTABLE_TABLE")]
+        table: String,
+        /// Personal access token with data.records:write on the base
+        #[arg(long, env = "AIRTABLE_TOKEN", hide_env_values = true)]
+        token: String,
+        /// Send an export column to a differently named field, e.g. "Plant Name=Name". Once
+        /// any are given, only the mapped columns are sent
+        #[arg(long = "field", value_parser = airtable::parse_field_mapping)]
+        fields: Vec<airtable::FieldMapping>,
+        /// Units for the parsed depth, spacing, dimension, and temperature columns; defaults to
+        /// the --locale's, or imperial
+        #[arg(long, value_enum)]
+        units: Option<Units>,
+    },
+    /// Merge every JSON file in a results directory into a single JSON array
+    Collection {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        /// Input CSV whose brand, purchase year, notes, and strategy are joined in
+        #[arg(short, long)]
+        input_file: Option<String>,
+        #[arg(short, long, env = "SEED_SCRAPER_FROST_DATE", default_value = DEFAULT_FROST_DATE)]
+        frost_date: NaiveDate,
+        #[arg(short, long, default_value = "collection.json")]
+        output: String,
+    },
+    /// Re-fetch every plant's page and report which have changed since they were scraped
+    CheckUpdates {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+    },
+    /// Fetch and parse plants' pages again with the current parser, rewriting their JSON
+    /// files. Hand edits made with `edit` are replaced by the fresh parse; corrections in
+    /// overrides.toml are kept.
+    Reparse {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        /// Only plants whose files were written by an older parser version
+        #[arg(long)]
+        outdated_only: bool,
+    },
+    /// Periodically re-check prices and stock, logging snapshots and reporting changes
+    Watch {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        /// Price history file; defaults to price_history.jsonl in the JSON directory
+        #[arg(long)]
+        history: Option<String>,
+        /// Hours to wait between checks
+        #[arg(long, default_value_t = 24, value_parser = clap::value_parser!(u64).range(1..))]
+        interval_hours: u64,
+        /// Check once and exit instead of running until interrupted
+        #[arg(long)]
+        once: bool,
+        /// URL to POST a JSON message to when a plant comes back in stock
+        #[arg(long)]
+        notify_webhook: Option<String>,
+        /// ntfy topic URL to publish back-in-stock alerts to, e.g. https://ntfy.sh/my-seeds
+        #[arg(long)]
+        notify_ntfy: Option<String>,
+        /// SMTP server to email back-in-stock alerts through
+        #[arg(long, requires_all = ["email_from", "email_to"])]
+        smtp_server: Option<String>,
+        #[arg(long, env = "SMTP_USERNAME")]
+        smtp_username: Option<String>,
+        #[arg(long, env = "SMTP_PASSWORD", hide_env_values = true)]
+        smtp_password: Option<String>,
+        #[arg(long)]
+        email_from: Option<String>,
+        #[arg(long)]
+        email_to: Option<String>,
+        /// Also write an Atom feed of new plants and detected changes to this file after each check
+        #[arg(long)]
+        feed: Option<String>,
+    },
+    /// Send reminders of sowing and transplanting tasks coming up, once each; meant for a
+    /// daily cron job
+    Notify {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        #[arg(short, long, env = "SEED_SCRAPER_FROST_DATE", default_value = DEFAULT_FROST_DATE)]
+        frost_date: NaiveDate,
+        /// Remind about tasks that can be done within this many days from today
+        #[arg(long, default_value_t = 3)]
+        days_ahead: u64,
+        /// URL to POST a JSON message to for each reminder
+        #[arg(long)]
+        notify_webhook: Option<String>,
+        /// ntfy topic URL to publish reminders to, e.g. https://ntfy.sh/my-seeds
+        #[arg(long)]
+        notify_ntfy: Option<String>,
+        /// Pushover application token to send reminders with
+        #[arg(
+            long,
+            env = "PUSHOVER_TOKEN",
+            hide_env_values = true,
+            requires = "pushover_user"
+        )]
+        pushover_token: Option<String>,
+        /// Pushover user or group key to send reminders to
+        #[arg(long, env = "PUSHOVER_USER", requires = "pushover_token")]
+        pushover_user: Option<String>,
+    },
+    /// Email a summary of this week's sowing and transplanting tasks and any stale or failed
+    /// scrapes; meant for a weekly cron job
+    Digest {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        /// First day of the week; defaults to this week's Monday
+        #[arg(short, long)]
+        week: Option<NaiveDate>,
+        #[arg(short, long, env = "SEED_SCRAPER_FROST_DATE", default_value = DEFAULT_FROST_DATE)]
+        frost_date: NaiveDate,
+        /// List scrapes older than this many days as stale
+        #[arg(long, default_value_t = 90)]
+        stale_days: i64,
+        /// Print the digest instead of emailing it
+        #[arg(long)]
+        dry_run: bool,
+        #[arg(
+            long,
+            required_unless_present = "dry_run",
+            requires_all = ["email_from", "email_to"]
+        )]
+        smtp_server: Option<String>,
+        #[arg(long, env = "SMTP_USERNAME")]
+        smtp_username: Option<String>,
+        #[arg(long, env = "SMTP_PASSWORD", hide_env_values = true)]
+        smtp_password: Option<String>,
+        #[arg(long)]
+        email_from: Option<String>,
+        #[arg(long)]
+        email_to: Option<String>,
+    },
+    /// Show which fields changed between two scrapes of the same plant
+    Diff {
+        /// The earlier JSON file
+        old_file: String,
+        /// The later JSON file
+        new_file: String,
+    },
+    /// Print a table of the plants in a results directory
+    List {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+    },
+    /// List the plants matching every given filter, optionally saving them to a JSON file
+    Query {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        /// Botanical family, e.g. Solanaceae
+        #[arg(long)]
+        family: Option<String>,
+        /// Text the exposure must contain, e.g. "Full sun"
+        #[arg(long)]
+        exposure: Option<String>,
+        /// Fewest days to maturity
+        #[arg(long)]
+        min_maturity: Option<u32>,
+        /// Most days to maturity
+        #[arg(long)]
+        max_maturity: Option<u32>,
+        /// Attribute tag the plant must have; repeatable
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Disease the plant must resist, by code or name; repeatable
+        #[arg(long = "resistant-to")]
+        resistant_to: Vec<String>,
+        #[arg(short, long, env = "SEED_SCRAPER_FROST_DATE", default_value = DEFAULT_FROST_DATE)]
+        frost_date: NaiveDate,
+        /// Also write the matching plants, with calculated dates, to this JSON file
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Open a plant's stored data in $EDITOR, checking it before saving and keeping its
+    /// scrape metadata
+    Edit {
+        /// Plant name, as in its JSON file name
+        plant: String,
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+    },
+    /// Print a formatted summary of one plant, with its sowing schedule
+    Show {
+        /// Plant name, as in its JSON file name
+        plant: String,
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        #[arg(short, long, env = "SEED_SCRAPER_FROST_DATE", default_value = DEFAULT_FROST_DATE)]
+        frost_date: NaiveDate,
+    },
+    /// Browse the collection in a terminal UI with search, details, and a sowing calendar
+    Tui {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        #[arg(short, long, env = "SEED_SCRAPER_FROST_DATE", default_value = DEFAULT_FROST_DATE)]
+        frost_date: NaiveDate,
+    },
+    /// Summarize the collection: counts by family, type, and strategy, maturity, and gaps
+    Stats {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+    },
+    /// List good and bad companion plants among the plants in the collection
+    Companions {
+        /// Only this plant, as in its JSON file name
+        plant: Option<String>,
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+    },
+    /// Warn when a plant family would repeat in a bed too soon, and suggest other beds
+    Rotate {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        /// CSV of past and planned plantings with Year, Bed, and Plant columns
+        #[arg(short, long)]
+        beds: String,
+        /// Year to check; defaults to the latest year in the bed record
+        #[arg(short, long)]
+        year: Option<i32>,
+        /// Years a family should stay out of a bed
+        #[arg(short = 'n', long, default_value_t = 3)]
+        years: i32,
+    },
+    /// Check that every JSON file is readable and complete, and that it matches the input CSV
+    Validate {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        /// Input CSV to cross-check for rows without JSON and JSON without rows
+        #[arg(short, long)]
+        input_file: Option<String>,
+    },
+    /// Save a product page and the JSON it parses to as a parser regression fixture
+    Record {
+        #[arg(short, long)]
+        url: String,
+        #[arg(long, default_value = "tests/fixtures")]
+        fixture_dir: String,
+        /// File name for the fixture, without extension; taken from the URL when not given
+        #[arg(long)]
+        name: Option<String>,
+        /// Save the page as fetched when sanitizing it would change what the parser reads.
+        /// Check it for session tokens and other private data before committing it.
+        #[arg(long)]
+        raw: bool,
+    },
+    /// Re-parse every recorded fixture and report fields that differ from its expected JSON
+    TestParse {
+        #[arg(long, default_value = "tests/fixtures")]
+        dir: String,
+    },
+    /// Serve the collection over HTTP with a GraphQL endpoint at /graphql
+    Serve {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        /// Frost date for sowing dates when a query doesn't give one
+        #[arg(short, long, env = "SEED_SCRAPER_FROST_DATE", default_value = DEFAULT_FROST_DATE)]
+        frost_date: NaiveDate,
+        /// Address to listen on; there's no authentication, so only use 0.0.0.0 on a network
+        /// you trust
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+        /// Price history for the Atom feed at /feed.atom [default: JSON_DIR/price_history.jsonl]
+        #[arg(long)]
+        history: Option<String>,
+    },
+    /// Run a Model Context Protocol server on stdio, exposing scraping and the collection as tools
+    Mcp {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        /// Frost date for sowing dates when a tool call doesn't give one
+        #[arg(short, long, env = "SEED_SCRAPER_FROST_DATE", default_value = DEFAULT_FROST_DATE)]
+        frost_date: NaiveDate,
+    },
+    /// Import every JSON file in a results directory into a SQLite database
+    Migrate {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        #[arg(short, long)]
+        db: String,
+    },
+    /// Pack a collection into one .tar.zst file, or unpack one, to share it or move machines
+    Bundle {
+        #[command(subcommand)]
+        action: BundleCommands,
+    },
+    /// Track the seeds on hand per plant, flagged in exports when they won't cover the sowings
+    Inventory {
+        #[command(subcommand)]
+        action: InventoryCommands,
+    },
+    /// Record this season's sowings, germinations, and transplants in planting_log.csv, to
+    /// compare with the plan using `export --preset journal`
+    Log {
+        #[command(subcommand)]
+        action: LogCommands,
+    },
+    /// Record germination tests of seed lots, shown as the Germination Rate export column
+    Germination {
+        #[command(subcommand)]
+        action: GerminationCommands,
+    },
+}
+
+#[derive(Parser)]
+enum InventoryCommands {
+    /// Add a plant to inventory.toml in the results directory, or change the given fields
+    Set {
+        /// Plant name, as in the input CSV
+        plant: String,
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        /// Seeds on hand
+        #[arg(long)]
+        quantity: Option<u32>,
+        /// Packets on hand
+        #[arg(long)]
+        packets: Option<u32>,
+        /// Purchase or packed-for year of the seeds
+        #[arg(long)]
+        lot: Option<String>,
+        /// Where the seeds are stored, e.g. "Shoebox 2"
+        #[arg(long)]
+        location: Option<String>,
+    },
+    /// List the seeds on hand
+    List {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+    },
+    /// Take a plant out of the inventory
+    Remove {
+        /// Plant name, as in the input CSV
+        plant: String,
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+    },
+}
+
+#[derive(Parser)]
+enum LogCommands {
+    /// Seeds went in, inside or out
+    Sow(LogEntry),
+    /// Seedlings were moved outside
+    Transplant(LogEntry),
+    /// The first seedlings came up
+    Germination(LogEntry),
+}
+
+#[derive(clap::Args)]
+struct LogEntry {
+    /// Plant name, as in the input CSV
+    plant: String,
+    #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+    json_dir: String,
+    /// When it happened [default: today]
+    #[arg(long)]
+    date: Option<NaiveDate>,
+    /// Anything worth remembering, e.g. the bed or tray
+    #[arg(long, default_value = "")]
+    notes: String,
+}
+
+#[derive(Parser)]
+enum GerminationCommands {
+    /// Add a test's result to germination.toml in the results directory
+    Record {
+        /// Plant name, as in the input CSV
+        plant: String,
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        /// How many seeds were tested
+        #[arg(long)]
+        tested: u32,
+        /// How many of them sprouted
+        #[arg(long)]
+        sprouted: u32,
+        /// Purchase year of the packet, to match the input CSV's Purchase Year
+        #[arg(long)]
+        lot: Option<String>,
+        /// When the test was done [default: today]
+        #[arg(long)]
+        date: Option<NaiveDate>,
+    },
+    /// List the recorded tests and their germination rates
+    List {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+    },
+}
+
+#[derive(Parser)]
+enum BundleCommands {
+    /// Bundle the plant JSON, overrides, and optionally the input CSV, with a manifest
+    Create {
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
+        json_dir: String,
+        /// Input CSV to include
+        #[arg(short, long)]
+        input_file: Option<String>,
+        /// Bundle to write, e.g. seeds.tar.zst
         #[arg(short, long)]
+        output: String,
+    },
+    /// Unpack a bundle into a results directory, keeping files already there
+    Import {
+        /// Bundle to read
+        bundle: String,
+        #[arg(short, long, env = "SEED_SCRAPER_JSON_DIR")]
         json_dir: String,
+        /// Where to save the bundle's input CSV, if it has one
+        #[arg(short, long)]
+        input_file: Option<String>,
+        /// Replace files that already exist
+        #[arg(long)]
+        overwrite: bool,
     },
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum ExportFormat {
+    /// Every scraped and calculated column
+    Csv,
+    /// Todoist CSV import with one task per plant, due on its start date
+    Todoist,
+    /// Readable report of the key growing fields per plant
+    Markdown,
+    /// Self-contained HTML page with a sortable, filterable table
+    Html,
+    /// Printable month-by-month seed starting and transplanting schedule
+    Pdf,
+    /// PDF label sheets for seed-starting trays (Avery 5160 unless --label-grid is given)
+    Labels,
+    /// Typed columnar file for pandas, DuckDB, or Polars
+    Parquet,
+    /// One JSON object per plant per line; use `-o -` to write to stdout
+    Jsonl,
+    /// Any text format, rendered from the Handlebars file given by --template
+    Template,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum ReportGrouping {
+    /// Group plants by botanical family
+    Family,
+    /// Group plants by the month of their calculated start date
+    Month,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum SortKey {
+    /// Plant name from the input CSV
+    Name,
+    /// Botanical family
+    Family,
+    /// Calculated start date, earliest first
+    Start,
+    /// Days to maturity, shortest first
+    Maturity,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum ExportPreset {
+    /// Plant, strategy, start inside, sow outside, transplant, and expected harvest dates
+    Schedule,
+    /// Planned start and transplant dates beside the ones recorded with the log command
+    Journal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+enum VariantChoice {
+    /// The packet the store selects by default: the first one in stock
+    Selected,
+    /// The cheapest, and so smallest, packet
+    Smallest,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+enum Units {
+    /// Inches and degrees Fahrenheit, as the store lists them
+    Imperial,
+    /// Centimeters and degrees Celsius
+    Metric,
+}
+
+impl Units {
+    // Relabel a CSV header such as "Height Min (in)" for these units
+    fn header(self, header: &str) -> String {
+        match self {
+            Units::Imperial => header.to_string(),
+            Units::Metric => header.replace("(in)", "(cm)").replace("(F)", "(°C)"),
+        }
+    }
+
+    // Rename a field such as height_min_in or min_soil_temp_f for these units
+    fn field_name(self, name: &str) -> String {
+        if self == Units::Metric {
+            if let Some(stem) = name.strip_suffix("_in") {
+                return format!("{}_cm", stem);
+            }
+            if let Some(stem) = name.strip_suffix("_f") {
+                return format!("{}_c", stem);
+            }
+        }
+        name.to_string()
+    }
+}
+
+// Options for the export command beyond its input and output paths
+struct ExportOptions {
+    frost_date: NaiveDate,
+    format: ExportFormat,
+    group_by: ReportGrouping,
+    label_grid: Option<(usize, usize)>,
+    variant: VariantChoice,
+    units: Units,
+    tags: Vec<String>,
+    resistant_to: Vec<String>,
+    bed_size: Option<spacing::BedSize>,
+    row_length: Option<f32>,
+    sowings: u32,
+    interactive: bool,
+    sort_by: Option<SortKey>,
+    columns: Option<ColumnSelection>,
+    preset: Option<ExportPreset>,
+    template: Option<String>,
+}
+
+impl ExportOptions {
+    // Every plant as CSV columns, for commands that push the export somewhere other than a file
+    fn for_sync(units: Units, frost_date: NaiveDate) -> Self {
+        ExportOptions {
+            frost_date,
+            format: ExportFormat::Csv,
+            group_by: ReportGrouping::Family,
+            label_grid: None,
+            variant: VariantChoice::Selected,
+            units,
+            tags: Vec::new(),
+            resistant_to: Vec::new(),
+            bed_size: None,
+            row_length: None,
+            sowings: 1,
+            interactive: false,
+            sort_by: None,
+            columns: None,
+            preset: None,
+            template: None,
+        }
+    }
+}
+
+// The seed designations, detected from the title, packet names, and tags
+const DESIGNATION_FIELDS: [&str; 4] = ["organic", "heirloom", "open_pollinated", "hybrid"];
+
+// Each text field and the fields parsed from it by fill_derived_fields
+const DERIVED_FIELDS: [(&str, &[&str]); 12] = [
+    ("title", &DESIGNATION_FIELDS),
+    (
+        "days_to_maturity",
+        &["days_to_maturity_min", "days_to_maturity_max"],
+    ),
+    (
+        "attributes",
+        &[
+            "tags",
+            "organic",
+            "heirloom",
+            "open_pollinated",
+            "hybrid",
+            "disease_resistance",
+        ],
+    ),
+    ("variety_info", &["disease_resistance"]),
+    ("description", &["disease_resistance"]),
+    (
+        "plant_dimensions",
+        &[
+            "height_min_in",
+            "height_max_in",
+            "spread_min_in",
+            "spread_max_in",
+        ],
+    ),
+    ("seed_depth", &["seed_depth_min_in", "seed_depth_max_in"]),
+    (
+        "seed_spacing",
+        &[
+            "seed_spacing_min_in",
+            "seed_spacing_max_in",
+            "plant_spacing_in",
+        ],
+    ),
+    ("row_spacing", &["row_spacing_min_in", "row_spacing_max_in"]),
+    ("thinning", &["plant_spacing_in"]),
+    (
+        "when_to_sow_outside",
+        &[
+            "min_soil_temp_f",
+            "ideal_soil_temp_min_f",
+            "ideal_soil_temp_max_f",
+        ],
+    ),
+    (
+        "when_to_start_inside",
+        &[
+            "min_soil_temp_f",
+            "ideal_soil_temp_min_f",
+            "ideal_soil_temp_max_f",
+        ],
+    ),
+];
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 struct PlantInfo {
+    // Files written before versioning was introduced deserialize as version 0
+    #[serde(default)]
+    schema_version: u32,
     url: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     title: Option<String>,
+    // Scientific name, e.g. "Daucus carota var. sativus"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    botanical_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     description: Option<String>,
+    // The description with its paragraphs, line breaks, and lists kept, as Markdown
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description_markdown: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     days_to_maturity: Option<String>,
+    // Parsed from days_to_maturity; equal when the page gives a single number
+    #[serde(skip_serializing_if = "Option::is_none")]
+    days_to_maturity_min: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    days_to_maturity_max: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     family: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -67,10 +1176,40 @@ struct PlantInfo {
     exposure: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     plant_dimensions: Option<String>,
+    // Parsed from plant_dimensions, in inches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    height_min_in: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    height_max_in: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    spread_min_in: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    spread_max_in: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     variety_info: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     attributes: Option<String>,
+    // Attributes split into individual tags, e.g. ["Crack Resistant", "Frost Tolerant"]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    // Seed designations, from the title, packet names, and tags
+    #[serde(skip_serializing_if = "Option::is_none")]
+    organic: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    heirloom: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    open_pollinated: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hybrid: Option<bool>,
+    // Resistance codes from the variety info and description, e.g. V, F1, TMV
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    disease_resistance: Vec<resistance::DiseaseResistance>,
+    // Other products the store recommends alongside this one, often companion plants
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    related: Vec<related::RelatedPlant>,
+    // Product image URLs, primary image first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    images: Vec<String>,
     // Sowing Info
     #[serde(skip_serializing_if = "Option::is_none")]
     when_to_sow_outside: Option<String>,
@@ -86,37 +1225,193 @@ struct PlantInfo {
     row_spacing: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     thinning: Option<String>,
+    // Growing Info
+    #[serde(skip_serializing_if = "Option::is_none")]
+    special_care: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    harvesting: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    growing_tips: Option<String>,
+    // Parsed from seed_depth, seed_spacing, and row_spacing, in inches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed_depth_min_in: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed_depth_max_in: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed_spacing_min_in: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed_spacing_max_in: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    row_spacing_min_in: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    row_spacing_max_in: Option<f32>,
+    // Final in-row spacing after thinning, or the seed spacing when there's no thinning
+    #[serde(skip_serializing_if = "Option::is_none")]
+    plant_spacing_in: Option<f32>,
+    // Soil temperatures in °F, parsed from the sowing text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_soil_temp_f: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ideal_soil_temp_min_f: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ideal_soil_temp_max_f: Option<i32>,
     // Rating Info
     #[serde(skip_serializing_if = "Option::is_none")]
     rating: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     votes: Option<u32>,
+    // Store Info, in dollars for the selected packet
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    variants: Vec<Variant>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    price: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compare_at_price: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    in_stock: Option<bool>,
+    // Set when the link now leads to a 404, a search or collection page, or a notice that the
+    // product is gone, in which case nothing else was parsed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    discontinued: Option<bool>,
+    // The archive.org snapshot the fields were parsed from, when the store's own page was
+    // blocked or gone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    archived_snapshot: Option<String>,
+    // Scrape Metadata
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scraped_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    http_status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parser_version: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_modified: Option<String>,
 }
 
-#[derive(Debug)]
-enum ScrapingError {
-    CloudflareBlocked,
-    #[allow(dead_code)]
-    Other(anyhow::Error),
+// A product variant as Shopify embeds it in the page, with prices in cents
+#[derive(Debug, Deserialize)]
+struct ShopifyVariant {
+    title: String,
+    public_title: Option<String>,
+    sku: Option<String>,
+    available: bool,
+    price: u64,
+    compare_at_price: Option<u64>,
 }
 
-impl std::fmt::Display for ScrapingError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ScrapingError::CloudflareBlocked => write!(
-                f,
-                "Access blocked by Cloudflare. Try again later or check if the URL is correct."
-            ),
-            ScrapingError::Other(e) => write!(f, "Error scraping page: {}", e),
+// One packet size offered for a product, with prices in dollars
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Variant {
+    size: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sku: Option<String>,
+    price: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    compare_at_price: Option<f32>,
+    available: bool,
+}
+
+impl From<ShopifyVariant> for Variant {
+    fn from(variant: ShopifyVariant) -> Self {
+        Variant {
+            size: variant.public_title.unwrap_or(variant.title),
+            sku: variant.sku.filter(|sku| !sku.is_empty()),
+            price: cents_to_dollars(variant.price),
+            compare_at_price: variant.compare_at_price.map(cents_to_dollars),
+            available: variant.available,
         }
     }
 }
 
-impl std::error::Error for ScrapingError {}
+fn parse_shopify_variants(document: &Html) -> Result<Vec<ShopifyVariant>, ScrapingError> {
+    static VARIANTS: Pattern<Selector> =
+        LazyLock::new(|| selector(r#"variant-selects script[type="application/json"]"#));
+    Ok(document
+        .select(compiled(&VARIANTS)?)
+        .next()
+        .and_then(|script| serde_json::from_str(&script.text().collect::<String>()).ok())
+        .unwrap_or_default())
+}
+
+// Image URLs from the page's schema.org Product data, falling back to its Open Graph image
+fn parse_product_images(document: &Html) -> Result<Vec<String>, ScrapingError> {
+    static LD_JSON: Pattern<Selector> =
+        LazyLock::new(|| selector(r#"script[type="application/ld+json"]"#));
+    static OG_IMAGE: Pattern<Selector> = LazyLock::new(|| {
+        selector(r#"meta[property="og:image:secure_url"], meta[property="og:image"]"#)
+    });
+
+    let product = document
+        .select(compiled(&LD_JSON)?)
+        .filter_map(|script| {
+            serde_json::from_str::<serde_json::Value>(&script.text().collect::<String>()).ok()
+        })
+        .find(|data| data["@type"] == "Product");
+    let images: Vec<String> = match product.as_ref().map(|product| &product["image"]) {
+        Some(serde_json::Value::String(image)) => vec![image.clone()],
+        Some(serde_json::Value::Array(images)) => images
+            .iter()
+            .filter_map(|image| image.as_str().map(String::from))
+            .collect(),
+        _ => document
+            .select(compiled(&OG_IMAGE)?)
+            .filter_map(|meta| meta.value().attr("content").map(String::from))
+            .take(1)
+            .collect(),
+    };
+
+    // Shopify often leaves off the scheme
+    Ok(images
+        .into_iter()
+        .map(|image| match image.strip_prefix("//") {
+            Some(rest) => format!("https://{}", rest),
+            None => image,
+        })
+        .collect())
+}
+
+// The italicized scientific name shown near the product title, if there is one
+fn parse_botanical_name(document: &Html) -> Result<Option<String>, ScrapingError> {
+    static EMPHASIS: Pattern<Selector> =
+        LazyLock::new(|| selector(".product__info-container em, .product__info-container i"));
+    static BINOMIAL: Pattern<regex::Regex> = LazyLock::new(|| {
+        regex(r"^[A-Z][a-z]+ (?:x )?[a-z-]+(?: (?:var\.|subsp\.|ssp\.|f\.) [a-z-]+)?$")
+    });
+
+    let binomial = compiled(&BINOMIAL)?;
+    Ok(document
+        .select(compiled(&EMPHASIS)?)
+        .map(|element| element.text().collect::<String>().trim().to_string())
+        .find(|text| binomial.is_match(text)))
+}
+
+fn cents_to_dollars(cents: u64) -> f32 {
+    cents as f32 / 100.0
+}
 
 impl PlantInfo {
     fn normalize_text(text: &str) -> String {
-        text.replace('\u{2013}', "-").replace('\u{2014}', "-")
+        text.replace(['\u{2013}', '\u{2014}'], "-")
+    }
+
+    // Parse a fetched page, or record the plant as discontinued if the page says it's gone
+    fn from_page(page: &FetchedPage, url: String) -> Result<Self, ScrapingError> {
+        let Some(reason) = discontinued_reason(page, &url) else {
+            return Self::from_html(&page.body, url);
+        };
+        warn!("{} looks discontinued: {}", url, reason);
+        Ok(PlantInfo {
+            schema_version: schema::SCHEMA_VERSION,
+            url,
+            discontinued: Some(true),
+            ..Default::default()
+        })
     }
 
     fn from_html(html: &str, url: String) -> Result<Self, ScrapingError> {
@@ -124,28 +1419,45 @@ impl PlantInfo {
             || html.contains("Sorry, you have been blocked")
             || html.contains("Please enable cookies.")
         {
-            return Err(ScrapingError::CloudflareBlocked);
+            return Err(ScrapingError::Blocked);
         }
 
         let document = Html::parse_document(html);
-        let info_selector = Selector::parse("div.tab-content p b").unwrap();
-        let rating_selector = Selector::parse("div.loox-rating").unwrap();
-        let title_selector = Selector::parse("h1").unwrap();
-        let description_selector = Selector::parse(".product__description").unwrap();
+        static INFO: Pattern<Selector> = LazyLock::new(|| selector("div.tab-content p b"));
+        static RATING: Pattern<Selector> = LazyLock::new(|| selector("div.loox-rating"));
+        static TITLE: Pattern<Selector> = LazyLock::new(|| selector("h1"));
+        static DESCRIPTION: Pattern<Selector> = LazyLock::new(|| selector(".product__description"));
 
         let mut info = PlantInfo {
+            schema_version: schema::SCHEMA_VERSION,
             url,
             title: None,
+            botanical_name: None,
             description: None,
+            description_markdown: None,
             days_to_maturity: None,
+            days_to_maturity_min: None,
+            days_to_maturity_max: None,
             family: None,
             plant_type: None,
             native: None,
             hardiness: None,
             exposure: None,
             plant_dimensions: None,
+            height_min_in: None,
+            height_max_in: None,
+            spread_min_in: None,
+            spread_max_in: None,
             variety_info: None,
             attributes: None,
+            tags: Vec::new(),
+            organic: None,
+            heirloom: None,
+            open_pollinated: None,
+            hybrid: None,
+            disease_resistance: Vec::new(),
+            related: Vec::new(),
+            images: Vec::new(),
             when_to_sow_outside: None,
             when_to_start_inside: None,
             days_to_emerge: None,
@@ -153,26 +1465,70 @@ impl PlantInfo {
             seed_spacing: None,
             row_spacing: None,
             thinning: None,
+            special_care: None,
+            harvesting: None,
+            growing_tips: None,
+            seed_depth_min_in: None,
+            seed_depth_max_in: None,
+            seed_spacing_min_in: None,
+            seed_spacing_max_in: None,
+            row_spacing_min_in: None,
+            row_spacing_max_in: None,
+            plant_spacing_in: None,
+            min_soil_temp_f: None,
+            ideal_soil_temp_min_f: None,
+            ideal_soil_temp_max_f: None,
             rating: None,
             votes: None,
+            variants: Vec::new(),
+            price: None,
+            compare_at_price: None,
+            in_stock: None,
+            discontinued: None,
+            archived_snapshot: None,
+            scraped_at: None,
+            source_domain: None,
+            http_status: None,
+            parser_version: None,
+            content_hash: None,
+            etag: None,
+            last_modified: None,
         };
 
         // Parse title
-        if let Some(title_element) = document.select(&title_selector).next() {
+        if let Some(title_element) = document.select(compiled(&TITLE)?).next() {
             info.title = Some(Self::normalize_text(
                 &title_element.text().collect::<String>(),
             ));
         }
 
+        info.botanical_name = parse_botanical_name(&document)?;
+
         // Parse description
-        if let Some(desc_element) = document.select(&description_selector).next() {
+        if let Some(desc_element) = document.select(compiled(&DESCRIPTION)?).next() {
             info.description = Some(Self::normalize_text(
-                &desc_element.text().collect::<String>().trim(),
+                desc_element.text().collect::<String>().trim(),
             ));
+            info.description_markdown =
+                description::to_markdown(desc_element).map(|text| Self::normalize_text(&text));
+        }
+
+        info.images = parse_product_images(&document)?;
+        info.related = related::parse_related_products(&document, &info.url)?;
+
+        // Parse packet sizes, then price and availability from the selected one
+        info.variants = parse_shopify_variants(&document)?
+            .into_iter()
+            .map(Variant::from)
+            .collect();
+        if let Some(variant) = info.selected_variant().cloned() {
+            info.price = Some(variant.price);
+            info.compare_at_price = variant.compare_at_price;
+            info.in_stock = Some(info.variants.iter().any(|variant| variant.available));
         }
 
         // Parse rating information
-        if let Some(rating_element) = document.select(&rating_selector).next() {
+        if let Some(rating_element) = document.select(compiled(&RATING)?).next() {
             if let (Some(rating), Some(votes)) = (
                 rating_element.value().attr("data-rating"),
                 rating_element.value().attr("data-raters"),
@@ -182,11 +1538,11 @@ impl PlantInfo {
             }
         }
 
-        for element in document.select(&info_selector) {
+        for element in document.select(compiled(&INFO)?) {
             let label = element.text().collect::<Vec<_>>().join("");
             if let Some(parent) = element.parent_element() {
                 let full_text = parent.text().collect::<Vec<_>>().join("");
-                let normalized = Self::normalize_text(&full_text.replace(&label, "").trim());
+                let normalized = Self::normalize_text(full_text.replace(&label, "").trim());
                 match label.trim_end_matches(':') {
                     "Days to Maturity" => info.days_to_maturity = Some(normalized),
                     "Family" => info.family = Some(normalized),
@@ -204,43 +1560,430 @@ impl PlantInfo {
                     "Seed Spacing" => info.seed_spacing = Some(normalized),
                     "Row Spacing" => info.row_spacing = Some(normalized),
                     "Thinning" => info.thinning = Some(normalized),
+                    "Special Care" => info.special_care = Some(normalized),
+                    "Harvesting" => info.harvesting = Some(normalized),
+                    "Growing Tips" | "Growing Notes" => info.growing_tips = Some(normalized),
+                    "Botanical Name" | "Latin Name" | "Scientific Name" => {
+                        info.botanical_name = Some(normalized)
+                    }
                     _ => (),
                 }
             }
         }
 
+        info.fill_derived_fields();
         Ok(info)
     }
-}
 
-// Create a reusable HTTP client with standard headers
-fn create_http_client() -> reqwest::blocking::Client {
-    reqwest::blocking::Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36")
-        .default_headers({
-            let mut headers = reqwest::header::HeaderMap::new();
-            headers.insert(
-                reqwest::header::ACCEPT,
-                "text/html,application/xhtml+xml,application/xml;q=0.9,image/webp,*/*;q=0.8".parse().unwrap(),
-            );
-            headers.insert(
-                reqwest::header::ACCEPT_LANGUAGE,
-                "en-US,en;q=0.5".parse().unwrap(),
-            );
-            headers.insert(
-                reqwest::header::CONNECTION,
-                "keep-alive".parse().unwrap(),
-            );
-            headers
-        })
-        .build()
-        .expect("Failed to create HTTP client")
-}
+    // The fields fill_derived_fields parses out of a text field, which go stale when the
+    // text is changed by hand
+    fn derived_fields(source: &str) -> &'static [&'static str] {
+        DERIVED_FIELDS
+            .iter()
+            .find(|(text, _)| *text == source)
+            .map_or(&[], |(_, derived)| *derived)
+    }
 
-#[derive(Debug, Clone, Copy)]
-enum TimingType {
-    LastFrost,
+    // Fill numeric fields parsed from the scraped text, leaving any already set alone.
+    // Also run on load so files scraped before a field existed still get it.
+    fn fill_derived_fields(&mut self) {
+        if self.days_to_maturity_min.is_none() && self.days_to_maturity_max.is_none() {
+            if let Some((min, max)) = self.days_to_maturity.as_deref().and_then(parse_days_range) {
+                self.days_to_maturity_min = Some(min);
+                self.days_to_maturity_max = Some(max);
+            }
+        }
+
+        if self.tags.is_empty() {
+            if let Some(attributes) = &self.attributes {
+                self.tags = attributes
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(String::from)
+                    .collect();
+            }
+        }
+
+        // Filled one by one, since an override can keep some while the rest are redetected
+        let missing_designation = [
+            self.organic,
+            self.heirloom,
+            self.open_pollinated,
+            self.hybrid,
+        ]
+        .contains(&None);
+        if missing_designation && (self.title.is_some() || !self.variants.is_empty()) {
+            let designations = SeedDesignations::detect(
+                self.title
+                    .iter()
+                    .chain(self.variants.iter().map(|variant| &variant.size))
+                    .chain(&self.tags),
+            );
+            self.organic.get_or_insert(designations.organic);
+            self.heirloom.get_or_insert(designations.heirloom);
+            self.open_pollinated
+                .get_or_insert(designations.open_pollinated);
+            self.hybrid.get_or_insert(designations.hybrid);
+        }
+
+        if self.disease_resistance.is_empty() {
+            let text = [&self.variety_info, &self.description, &self.attributes]
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join("\n");
+            self.disease_resistance = resistance::parse_disease_resistance(&text);
+        }
+
+        let no_dimensions = self.height_min_in.is_none()
+            && self.height_max_in.is_none()
+            && self.spread_min_in.is_none()
+            && self.spread_max_in.is_none();
+        if no_dimensions {
+            if let Some(text) = &self.plant_dimensions {
+                let dimensions = measurements::parse_plant_dimensions(text);
+                self.height_min_in = dimensions.height.map(|(min, _)| min);
+                self.height_max_in = dimensions.height.map(|(_, max)| max);
+                self.spread_min_in = dimensions.spread.map(|(min, _)| min);
+                self.spread_max_in = dimensions.spread.map(|(_, max)| max);
+            }
+        }
+
+        let spacings = [
+            (
+                &self.seed_depth,
+                &mut self.seed_depth_min_in,
+                &mut self.seed_depth_max_in,
+            ),
+            (
+                &self.seed_spacing,
+                &mut self.seed_spacing_min_in,
+                &mut self.seed_spacing_max_in,
+            ),
+            (
+                &self.row_spacing,
+                &mut self.row_spacing_min_in,
+                &mut self.row_spacing_max_in,
+            ),
+        ];
+        for (text, min, max) in spacings {
+            if min.is_none() && max.is_none() {
+                if let Some((low, high)) = text.as_deref().and_then(measurements::parse_inch_range)
+                {
+                    *min = Some(low);
+                    *max = Some(high);
+                }
+            }
+        }
+
+        if self.plant_spacing_in.is_none() {
+            self.plant_spacing_in = self
+                .thinning
+                .as_deref()
+                .and_then(measurements::parse_thinning_spacing)
+                .map(|(_, max)| max)
+                .or(self.seed_spacing_max_in);
+        }
+
+        let no_soil_temps = self.min_soil_temp_f.is_none()
+            && self.ideal_soil_temp_min_f.is_none()
+            && self.ideal_soil_temp_max_f.is_none();
+        if no_soil_temps {
+            let temps = [&self.when_to_sow_outside, &self.when_to_start_inside]
+                .into_iter()
+                .flatten()
+                .map(|text| parse_soil_temperature(text))
+                .find(|temps| *temps != SoilTemperature::default())
+                .unwrap_or_default();
+            self.min_soil_temp_f = temps.min;
+            self.ideal_soil_temp_min_f = temps.ideal.map(|(min, _)| min);
+            self.ideal_soil_temp_max_f = temps.ideal.map(|(_, max)| max);
+        }
+    }
+
+    // The variant Shopify selects by default: the first one in stock
+    fn selected_variant(&self) -> Option<&Variant> {
+        self.variants
+            .iter()
+            .find(|variant| variant.available)
+            .or(self.variants.first())
+    }
+
+    fn smallest_variant(&self) -> Option<&Variant> {
+        self.variants
+            .iter()
+            .min_by(|a, b| a.price.total_cmp(&b.price))
+    }
+
+    // Point the price columns at a different packet size than the one scraped
+    fn choose_variant(&mut self, choice: VariantChoice) {
+        let variant = match choice {
+            VariantChoice::Selected => return,
+            VariantChoice::Smallest => self.smallest_variant(),
+        };
+        if let Some(variant) = variant.cloned() {
+            self.price = Some(variant.price);
+            self.compare_at_price = variant.compare_at_price;
+            self.in_stock = Some(variant.available);
+        }
+    }
+
+    // Whether the plant carries every one of the given tags, ignoring case
+    fn has_tags(&self, tags: &[String]) -> bool {
+        tags.iter()
+            .all(|wanted| self.tags.iter().any(|tag| tag.eq_ignore_ascii_case(wanted)))
+    }
+
+    // Whether the plant resists a disease, given as a code like "TMV" or part of a name like "blight"
+    fn resists(&self, disease: &str) -> bool {
+        let disease = disease.to_lowercase();
+        self.disease_resistance.iter().any(|resistance| {
+            resistance.code.to_lowercase() == disease
+                || resistance.disease.to_lowercase().contains(&disease)
+        })
+    }
+
+    // Convert the parsed numeric measurements, leaving the scraped text as the store wrote it
+    fn convert_units(&mut self, units: Units) {
+        if units == Units::Imperial {
+            return;
+        }
+
+        let to_cm = |inches: &mut Option<f32>| {
+            *inches = inches.map(|inches| (inches * 2.54 * 10.0).round() / 10.0);
+        };
+        to_cm(&mut self.height_min_in);
+        to_cm(&mut self.height_max_in);
+        to_cm(&mut self.spread_min_in);
+        to_cm(&mut self.spread_max_in);
+        to_cm(&mut self.seed_depth_min_in);
+        to_cm(&mut self.seed_depth_max_in);
+        to_cm(&mut self.seed_spacing_min_in);
+        to_cm(&mut self.seed_spacing_max_in);
+        to_cm(&mut self.row_spacing_min_in);
+        to_cm(&mut self.row_spacing_max_in);
+        to_cm(&mut self.plant_spacing_in);
+
+        let to_celsius = |fahrenheit: &mut Option<i32>| {
+            *fahrenheit =
+                fahrenheit.map(|fahrenheit| ((fahrenheit - 32) as f32 * 5.0 / 9.0).round() as i32);
+        };
+        to_celsius(&mut self.min_soil_temp_f);
+        to_celsius(&mut self.ideal_soil_temp_min_f);
+        to_celsius(&mut self.ideal_soil_temp_max_f);
+    }
+
+    // Most pages load their recommendations separately, so ask the store for them directly
+    fn fetch_related(&mut self, client: &reqwest::blocking::Client, html: &str) {
+        // A discontinued product's page is a search or error page, whose links aren't related
+        if !self.related.is_empty() || self.discontinued == Some(true) {
+            return;
+        }
+        match related::fetch_related_products(client, html, &self.url) {
+            Ok(related) => self.related = related,
+            // Not worth failing the scrape over
+            Err(e) => warn!("Failed to fetch related products for {}: {}", self.url, e),
+        }
+    }
+
+    // Record when and where this page was fetched, which parser read it, and what it said
+    fn record_scrape(&mut self, page: &FetchedPage) {
+        self.scraped_at = Some(Utc::now());
+        self.source_domain = reqwest::Url::parse(&self.url)
+            .ok()
+            .and_then(|url| url.host_str().map(String::from));
+        self.http_status = Some(page.status);
+        self.parser_version = Some(PARSER_VERSION);
+        self.content_hash = Some(updates::content_hash(self));
+        self.etag = page.etag.clone();
+        self.last_modified = page.last_modified.clone();
+    }
+
+    // Whether this file predates the current parser and may be missing what it extracts now
+    fn parsed_by_older_version(&self) -> bool {
+        self.parser_version.unwrap_or(0) < PARSER_VERSION
+    }
+
+    // Days to maturity as a (min, max) range, parsing the raw text if the numbers aren't set
+    fn maturity_days(&self) -> Option<(u32, u32)> {
+        match (self.days_to_maturity_min, self.days_to_maturity_max) {
+            (Some(min), Some(max)) => Some((min, max)),
+            _ => self.days_to_maturity.as_deref().and_then(parse_days_range),
+        }
+    }
+
+    // Whole days since the page was scraped, if the scrape time was recorded
+    fn scrape_age_days(&self, now: DateTime<Utc>) -> Option<i64> {
+        self.scraped_at
+            .map(|scraped_at| (now - scraped_at).num_days())
+    }
+}
+
+// A fetched product page with the response details worth keeping
+struct FetchedPage {
+    status: u16,
+    // Where any redirects ended up; None for pages read from disk
+    final_url: Option<String>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+}
+
+// Wording of the "product unavailable" templates stores show in place of a removed product
+const UNAVAILABLE_PHRASES: [&str; 4] = [
+    "product is no longer available",
+    "this product is unavailable",
+    "product not found",
+    "we no longer carry",
+];
+
+// Why a page is no product at all anymore, if it isn't
+fn discontinued_reason(page: &FetchedPage, url: &str) -> Option<String> {
+    if matches!(page.status, 404 | 410) {
+        return Some(format!("HTTP {}", page.status));
+    }
+    // Stores send removed products to a search or collection page rather than a 404
+    if let Some(final_url) = &page.final_url {
+        let is_product =
+            |url: &str| reqwest::Url::parse(url).is_ok_and(|url| url.path().contains("/products/"));
+        if is_product(url) && !is_product(final_url) {
+            return Some(format!("redirected to {}", final_url));
+        }
+    }
+    let body = page.body.to_lowercase();
+    UNAVAILABLE_PHRASES
+        .iter()
+        .find(|phrase| body.contains(*phrase))
+        .map(|phrase| format!("page says \"{}\"", phrase))
+}
+
+// Parse a fetched page like PlantInfo::from_page, falling back to archive.org's copy of it
+// under --wayback
+fn parse_fetched(
+    client: &reqwest::blocking::Client,
+    page: &FetchedPage,
+    url: String,
+) -> Result<PlantInfo, ScrapingError> {
+    let parsed = PlantInfo::from_page(page, url.clone());
+    if network().wayback {
+        wayback::or_archived(client, parsed, &url)
+    } else {
+        parsed
+    }
+}
+
+// Send a request once any backoff for its host is over, retrying while the store answers
+// 429 Too Many Requests
+fn send_with_backoff(
+    request: reqwest::blocking::RequestBuilder,
+) -> Result<reqwest::blocking::Response, ScrapingError> {
+    let host = request
+        .try_clone()
+        .and_then(|request| request.build().ok())
+        .and_then(|request| request.url().host_str().map(String::from))
+        .unwrap_or_default();
+    let mut request = request;
+    let mut retry = 0;
+    let response = loop {
+        backoff::wait_for(&host);
+        let next = request.try_clone();
+        let response = request.send()?;
+        match next {
+            Some(next)
+                if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+                    && retry < backoff::MAX_RETRIES =>
+            {
+                let wait = backoff::retry_wait(response.headers(), retry);
+                warn!(
+                    "{} asked to slow down; retrying in {}s",
+                    host,
+                    wait.as_secs()
+                );
+                backoff::pause(&host, wait);
+                request = next;
+                retry += 1;
+            }
+            _ => break response,
+        }
+    };
+    Ok(response)
+}
+
+fn fetch_page(request: reqwest::blocking::RequestBuilder) -> Result<FetchedPage, ScrapingError> {
+    let response = send_with_backoff(request)?;
+    let header = |name| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value: &reqwest::header::HeaderValue| value.to_str().ok())
+            .map(String::from)
+    };
+    let etag = header(reqwest::header::ETAG);
+    let last_modified = header(reqwest::header::LAST_MODIFIED);
+    let content_type = header(reqwest::header::CONTENT_TYPE);
+    Ok(FetchedPage {
+        status: response.status().as_u16(),
+        final_url: Some(response.url().to_string()),
+        etag,
+        last_modified,
+        body: charset::decode_page(&response.bytes()?, content_type.as_deref()),
+    })
+}
+
+// Sent when no --user-agent is given
+const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36";
+
+// Idle connections kept open per host, so consecutive pages on a store skip the TLS handshake
+const POOL_IDLE_PER_HOST: usize = 4;
+
+static HTTP_CLIENT: OnceLock<reqwest::blocking::Client> = OnceLock::new();
+
+// The process-wide HTTP client. Clones share one connection pool, so every request to a
+// store after the first reuses an open connection.
+fn create_http_client() -> reqwest::blocking::Client {
+    HTTP_CLIENT.get_or_init(build_http_client).clone()
+}
+
+fn build_http_client() -> reqwest::blocking::Client {
+    let settings = network();
+    let mut builder = reqwest::blocking::Client::builder()
+        .timeout(settings.timeout)
+        .connect_timeout(settings.connect_timeout)
+        .pool_max_idle_per_host(POOL_IDLE_PER_HOST)
+        .pool_idle_timeout(StdDuration::from_secs(90));
+    if let Some(proxy) = &settings.proxy {
+        builder = builder.proxy(proxy.clone());
+    }
+    builder
+        .user_agent(DEFAULT_USER_AGENT)
+        .default_headers({
+            let mut headers = reqwest::header::HeaderMap::new();
+            headers.insert(
+                reqwest::header::ACCEPT,
+                "text/html,application/xhtml+xml,application/xml;q=0.9,image/webp,*/*;q=0.8"
+                    .parse()
+                    .unwrap(),
+            );
+            headers.insert(
+                reqwest::header::ACCEPT_LANGUAGE,
+                "en-US,en;q=0.5".parse().unwrap(),
+            );
+            headers.insert(reqwest::header::CONNECTION, "keep-alive".parse().unwrap());
+            headers
+        })
+        .build()
+        .expect("Failed to create HTTP client")
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TimingType {
+    LastFrost,
     Transplant,
+    FallFrost,
+    // Sown in late fall to sprout in spring, timed from when the ground freezes
+    Overwinter,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -263,42 +2006,77 @@ enum SowingStrategy {
     Outside,
 }
 
-// Add a method to convert SowingStrategy to string for display
-impl SowingStrategy {
-    fn to_string(&self) -> String {
+// Display SowingStrategy the same way users write it in the input CSV
+impl std::fmt::Display for SowingStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            SowingStrategy::Inside => "Inside".to_string(),
-            SowingStrategy::Outside => "Outside".to_string(),
+            SowingStrategy::Inside => write!(f, "Inside"),
+            SowingStrategy::Outside => write!(f, "Outside"),
         }
     }
 }
 
-fn extract_weeks_pattern(text: &str) -> Option<SowingTime> {
-    let re = regex::Regex::new(
-        r"(\d+)\s*to\s*(\d+)\s*weeks\s*(before|after)\s*(your average last frost date|transplanting)",
-    )
-    .unwrap();
+// Every sowing time in the text, in the order it gives them. Matches "2 to 4 weeks before ...",
+// a single "2 weeks before ...", or just "After ...", which is no offset at all
+fn extract_weeks_pattern(text: &str) -> Vec<SowingTime> {
+    static WEEKS: Pattern<regex::Regex> = LazyLock::new(|| {
+        regex(
+            r"(?i)(?:(\d+)\s*(?:(?:to|-)\s*(\d+)\s*)?weeks?\s*)?(before|after)\s*(your average last frost date|transplanting|your average first fall frost date|(?:the )?first fall frost|the ground freezes)",
+        )
+    });
+    static LATE_FALL: Pattern<regex::Regex> = LazyLock::new(|| regex(r"(?i)\blate fall\b"));
 
-    re.captures(text).and_then(|cap| {
-        let timing_type = match cap.get(4).unwrap().as_str() {
-            "your average last frost date" => TimingType::LastFrost,
-            "transplanting" => TimingType::Transplant,
-            _ => return None,
-        };
+    let (Ok(weeks), Ok(late_fall)) = (compiled(&WEEKS), compiled(&LATE_FALL)) else {
+        return Vec::new();
+    };
 
-        let relative_timing = match cap.get(3).unwrap().as_str() {
-            "before" => RelativeTiming::Before,
-            "after" => RelativeTiming::After,
-            _ => unreachable!(),
-        };
+    let mut found: Vec<(usize, SowingTime)> = weeks
+        .captures_iter(text)
+        .filter_map(|cap| {
+            let timing_type = match cap.get(4).unwrap().as_str().to_lowercase().as_str() {
+                "your average last frost date" => TimingType::LastFrost,
+                "transplanting" => TimingType::Transplant,
+                "the ground freezes" => TimingType::Overwinter,
+                _ => TimingType::FallFrost,
+            };
 
-        Some(SowingTime {
-            weeks_min: cap.get(1).unwrap().as_str().parse().unwrap(),
-            weeks_max: cap.get(2).unwrap().as_str().parse().unwrap(),
-            relative_timing,
-            timing_type,
+            let relative_timing = match cap.get(3).unwrap().as_str().to_lowercase().as_str() {
+                "before" => RelativeTiming::Before,
+                "after" => RelativeTiming::After,
+                _ => unreachable!(),
+            };
+
+            let weeks_min = cap.get(1).map_or(Some(0), |m| m.as_str().parse().ok())?;
+            let weeks_max = cap
+                .get(2)
+                .map_or(Some(weeks_min), |m| m.as_str().parse().ok())?;
+            let sowing_time = SowingTime {
+                weeks_min,
+                weeks_max,
+                relative_timing,
+                timing_type,
+            };
+            Some((cap.get(0).unwrap().start(), sowing_time))
         })
-    })
+        .collect();
+
+    // "Sow in late fall" means between the first fall frost and the ground freezing
+    if let Some(m) = late_fall.find(text) {
+        let sowing_time = SowingTime {
+            weeks_min: 0,
+            weeks_max: GROUND_FREEZE_OFFSET_DAYS / 7,
+            relative_timing: RelativeTiming::Before,
+            timing_type: TimingType::Overwinter,
+        };
+        found.push((m.start(), sowing_time));
+    }
+    found.sort_by_key(|(position, _)| *position);
+
+    let mut sowing_times: Vec<SowingTime> = found.into_iter().map(|(_, time)| time).collect();
+    if sowing_times.is_empty() {
+        sowing_times.extend(phrases::match_phrase(text));
+    }
+    sowing_times
 }
 
 fn determine_sowing_strategy(
@@ -324,10 +2102,12 @@ fn determine_sowing_strategy(
     }
 }
 
+// The sowing times for the plant's strategy; the first is the main window and any others
+// are alternates, such as a fall sowing
 fn get_when_to_seed_start(
     info: &PlantInfo,
     user_strategy: Option<SowingStrategy>,
-) -> Option<SowingTime> {
+) -> Vec<SowingTime> {
     // Use the default logic based on recommended strategy
     let strategy = determine_sowing_strategy(info, user_strategy);
     let text = match strategy {
@@ -335,19 +2115,288 @@ fn get_when_to_seed_start(
         Some(SowingStrategy::Outside) => info.when_to_sow_outside.as_deref(),
         None => None,
     };
-    text.and_then(extract_weeks_pattern)
+    text.map(extract_weeks_pattern).unwrap_or_default()
+}
+
+// Find transplant guidance such as "Transplant 1 to 2 weeks after your average last frost date"
+// and return its offset from the frost date in days
+fn extract_transplant_offset(text: &str) -> Option<i64> {
+    static TRANSPLANT: Pattern<regex::Regex> = LazyLock::new(|| {
+        regex(
+            r"(?i)transplant\w*\b[^.]*?(?:(\d+)(?:\s*to\s*\d+)?\s*weeks?\s*)?(before|after)\s*your average last frost date",
+        )
+    });
+
+    compiled(&TRANSPLANT).ok()?.captures(text).and_then(|cap| {
+        let weeks: i64 = cap.get(1).map_or(Some(0), |m| m.as_str().parse().ok())?;
+        let days = weeks.checked_mul(7)?;
+        match cap.get(2).unwrap().as_str().to_lowercase().as_str() {
+            "before" => Some(-days),
+            _ => Some(days),
+        }
+    })
+}
+
+fn calculate_transplant_date(info: &PlantInfo, frost_date: NaiveDate) -> NaiveDate {
+    let offset = info
+        .when_to_start_inside
+        .as_deref()
+        .and_then(extract_transplant_offset)
+        .and_then(Duration::try_days)
+        .unwrap_or(Duration::days(TRANSPLANT_OFFSET_DAYS));
+    frost_date
+        .checked_add_signed(offset)
+        .unwrap_or(frost_date + Duration::days(TRANSPLANT_OFFSET_DAYS))
+}
+
+// Shift a base date by whole weeks in the given direction
+fn offset_by_weeks(base_date: NaiveDate, relative_timing: RelativeTiming, weeks: i64) -> NaiveDate {
+    match relative_timing {
+        RelativeTiming::Before => base_date - Days::new((weeks * 7) as u64),
+        RelativeTiming::After => base_date + Days::new((weeks * 7) as u64),
+    }
+}
+
+// The --fall-frost-date, set once from the global flag
+static FALL_FROST_DATE: OnceLock<NaiveDate> = OnceLock::new();
+
+// The first fall frost after the last spring frost: the configured date's month and day in the
+// same season, or FROST_FREE_DAYS later when none is configured
+fn first_fall_frost(frost_date: NaiveDate, configured: Option<NaiveDate>) -> NaiveDate {
+    let Some(configured) = configured else {
+        return frost_date + Duration::days(FROST_FREE_DAYS);
+    };
+    let fall_frost_date = tasks::frost_date_in_year_of(configured, frost_date);
+    if fall_frost_date > frost_date {
+        fall_frost_date
+    } else {
+        // Southern hemisphere seasons, where the fall frost comes in the next calendar year
+        tasks::frost_date_in_year_of(configured, frost_date + Duration::days(365))
+    }
 }
 
-fn calculate_start_date(sowing_time: &SowingTime, frost_date: NaiveDate) -> NaiveDate {
-    let base_date = match sowing_time.timing_type {
+fn sowing_base_date(
+    sowing_time: &SowingTime,
+    frost_date: NaiveDate,
+    fall_frost_date: NaiveDate,
+    transplant_date: NaiveDate,
+) -> NaiveDate {
+    match sowing_time.timing_type {
         TimingType::LastFrost => frost_date,
-        TimingType::Transplant => frost_date + Days::new(21), // 3 weeks after frost date
+        TimingType::Transplant => transplant_date,
+        TimingType::FallFrost => fall_frost_date,
+        TimingType::Overwinter => fall_frost_date + Duration::days(GROUND_FREEZE_OFFSET_DAYS),
+    }
+}
+
+fn calculate_start_date(
+    sowing_time: &SowingTime,
+    frost_date: NaiveDate,
+    fall_frost_date: NaiveDate,
+    transplant_date: NaiveDate,
+) -> NaiveDate {
+    let base_date = sowing_base_date(sowing_time, frost_date, fall_frost_date, transplant_date);
+    offset_by_weeks(
+        base_date,
+        sowing_time.relative_timing,
+        sowing_time.weeks_min,
+    )
+}
+
+// Return the (earliest, latest) dates of the full sowing window
+fn calculate_start_window(
+    sowing_time: &SowingTime,
+    frost_date: NaiveDate,
+    fall_frost_date: NaiveDate,
+    transplant_date: NaiveDate,
+) -> (NaiveDate, NaiveDate) {
+    let base_date = sowing_base_date(sowing_time, frost_date, fall_frost_date, transplant_date);
+    let near = offset_by_weeks(
+        base_date,
+        sowing_time.relative_timing,
+        sowing_time.weeks_min,
+    );
+    let far = offset_by_weeks(
+        base_date,
+        sowing_time.relative_timing,
+        sowing_time.weeks_max,
+    );
+    (near.min(far), near.max(far))
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct SeedDesignations {
+    organic: bool,
+    heirloom: bool,
+    open_pollinated: bool,
+    hybrid: bool,
+}
+
+impl SeedDesignations {
+    fn detect<'a>(texts: impl IntoIterator<Item = &'a String>) -> Self {
+        static ORGANIC: Pattern<regex::Regex> = LazyLock::new(|| regex(r"(?i)\borganic\b"));
+        static HEIRLOOM: Pattern<regex::Regex> = LazyLock::new(|| regex(r"(?i)\bheirloom\b"));
+        static OPEN_POLLINATED: Pattern<regex::Regex> =
+            LazyLock::new(|| regex(r"(?i)\bopen[- ]pollinated\b"));
+        // Captures a "non-" in front, as in the "Non-Hybrid" badge heirlooms carry
+        static HYBRID: Pattern<regex::Regex> =
+            LazyLock::new(|| regex(r"(?i)\b(non[- ]?)?(F-?1|hybrid)\b"));
+        let matches = |pattern: &'static Pattern<regex::Regex>, text: &str| {
+            compiled(pattern).is_ok_and(|re| re.is_match(text))
+        };
+        let hybrid = |text: &str| {
+            compiled(&HYBRID).is_ok_and(|re| re.captures_iter(text).any(|cap| cap.get(1).is_none()))
+        };
+
+        let mut designations = SeedDesignations::default();
+        for text in texts {
+            designations.organic |= matches(&ORGANIC, text);
+            designations.heirloom |= matches(&HEIRLOOM, text);
+            designations.open_pollinated |= matches(&OPEN_POLLINATED, text);
+            designations.hybrid |= hybrid(text);
+        }
+        // Heirlooms are open-pollinated by definition, even when the page doesn't say so
+        designations.open_pollinated |= designations.heirloom;
+        designations
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct SoilTemperature {
+    min: Option<i32>,
+    ideal: Option<(i32, i32)>,
+}
+
+// Parse soil temperatures in °F from text such as
+// "when soil temperature is at least 45°F, ideally 60°-85°F"
+fn parse_soil_temperature(text: &str) -> SoilTemperature {
+    static MIN: Pattern<regex::Regex> = LazyLock::new(|| regex(r"(?i)at least\s+(\d+)\s*°?\s*F"));
+    static IDEAL: Pattern<regex::Regex> =
+        LazyLock::new(|| regex(r"(?i)ideally\s+(\d+)\s*°?(?:\s*-\s*(\d+)\s*°?)?\s*F"));
+
+    let min = compiled(&MIN)
+        .ok()
+        .and_then(|re| re.captures(text))
+        .and_then(|cap| cap.get(1).unwrap().as_str().parse().ok());
+    let ideal = compiled(&IDEAL).ok().and_then(|re| {
+        let cap = re.captures(text)?;
+        let low: i32 = cap.get(1).unwrap().as_str().parse().ok()?;
+        let high = cap
+            .get(2)
+            .and_then(|m| m.as_str().parse().ok())
+            .unwrap_or(low);
+        Some((low, high))
+    });
+
+    SoilTemperature { min, ideal }
+}
+
+// Parse a day count such as "65 days" or "55-70 days" into a (min, max) range
+fn parse_days_range(text: &str) -> Option<(u32, u32)> {
+    static DAYS: Pattern<regex::Regex> = LazyLock::new(|| regex(r"(\d+)\s*(?:-\s*(\d+))?\s*days"));
+
+    let cap = compiled(&DAYS).ok()?.captures(text)?;
+    let min: u32 = cap.get(1).unwrap().as_str().parse().ok()?;
+    let max = match cap.get(2) {
+        Some(m) => m.as_str().parse().ok()?,
+        None => min,
     };
+    Some((min, max))
+}
+
+// Dates derived from the scraped sowing info and the frost date
+#[derive(Debug, Default)]
+struct CalculatedDates {
+    start: Option<NaiveDate>,
+    earliest_start: Option<NaiveDate>,
+    latest_start: Option<NaiveDate>,
+    transplant: Option<NaiveDate>,
+    first_harvest: Option<NaiveDate>,
+    last_harvest: Option<NaiveDate>,
+    // (earliest, latest) for every sowing window after the first
+    alternate_windows: Vec<(NaiveDate, NaiveDate)>,
+}
 
-    match sowing_time.relative_timing {
-        RelativeTiming::Before => base_date - Days::new((sowing_time.weeks_min * 7) as u64),
-        RelativeTiming::After => base_date + Days::new((sowing_time.weeks_min * 7) as u64),
+fn calculate_dates(
+    info: &PlantInfo,
+    sowing_strategy: Option<SowingStrategy>,
+    sowing_times: &[SowingTime],
+    frost_date: NaiveDate,
+) -> CalculatedDates {
+    let transplant_date = calculate_transplant_date(info, frost_date);
+    let fall_frost_date = first_fall_frost(frost_date, FALL_FROST_DATE.get().copied());
+    let start_date = |t| calculate_start_date(t, frost_date, fall_frost_date, transplant_date);
+    let start_window = |t| calculate_start_window(t, frost_date, fall_frost_date, transplant_date);
+    let sowing_time = sowing_times.first();
+    let start = sowing_time.map(start_date);
+    let window = sowing_time.map(start_window);
+    let alternate_windows = sowing_times.iter().skip(1).map(start_window).collect();
+
+    // Only indoor starts get transplanted, and their days to maturity count from then
+    let transplant = match sowing_strategy {
+        Some(SowingStrategy::Inside) => Some(transplant_date),
+        _ => None,
+    };
+    let maturity_base = transplant.or(start);
+    let (first_harvest, last_harvest) = match (maturity_base, info.maturity_days()) {
+        (Some(base), Some((min, max))) => (
+            Some(base + Days::new(u64::from(min))),
+            Some(base + Days::new(u64::from(max))),
+        ),
+        _ => (None, None),
+    };
+
+    CalculatedDates {
+        start,
+        earliest_start: window.map(|(earliest, _)| earliest),
+        latest_start: window.map(|(_, latest)| latest),
+        transplant,
+        first_harvest,
+        last_harvest,
+        alternate_windows,
+    }
+}
+
+// Alternate sowing windows as "2025-07-20 to 2025-08-03; ...", or NULL when there are none
+fn format_windows(windows: &[(NaiveDate, NaiveDate)]) -> String {
+    if windows.is_empty() {
+        return "NULL".to_string();
     }
+    windows
+        .iter()
+        .map(|(earliest, latest)| {
+            format!(
+                "{} to {}",
+                format_date(Some(*earliest)),
+                format_date(Some(*latest))
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+// Helper function to format an optional date with NULL fallback
+fn format_date(date: Option<NaiveDate>) -> String {
+    date.map(|d| d.format(locale::date_format()).to_string())
+        .unwrap_or_else(|| "NULL".to_string())
+}
+
+// A number as written, or NULL
+fn format_number<T: ToString>(number: Option<T>) -> String {
+    number.map_or_else(|| "NULL".to_string(), |n| n.to_string())
+}
+
+// Yes or No, or NULL when it isn't known
+fn format_flag(flag: Option<bool>) -> String {
+    flag.map_or_else(
+        || "NULL".to_string(),
+        |flag| if flag { "Yes" } else { "No" }.to_string(),
+    )
+}
+
+// Dollars to the cent, or NULL
+fn format_price(price: Option<f32>) -> String {
+    price.map_or_else(|| "NULL".to_string(), |price| format!("{:.2}", price))
 }
 
 // Helper function to get field with NULL fallback
@@ -378,6 +2427,28 @@ struct InputRecord<'a> {
     notes: &'a str,
     user_strategy_str: &'a str,
     user_strategy: Option<SowingStrategy>,
+    // This plant's own last frost date, e.g. for a bed in a colder spot
+    frost_date: Option<NaiveDate>,
+    // A start date chosen by hand, used instead of the computed one
+    start_date: Option<NaiveDate>,
+}
+
+// An optional date column; anything that isn't a YYYY-MM-DD date is warned about and ignored
+fn parse_input_date(plant_name: &str, column: &str, text: &str) -> Option<NaiveDate> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    match text.parse() {
+        Ok(date) => Some(date),
+        Err(_) => {
+            warn!(
+                "Ignoring {} \"{}\" for {}; expected YYYY-MM-DD",
+                column, text, plant_name
+            );
+            None
+        }
+    }
 }
 
 impl<'a> InputRecord<'a> {
@@ -389,6 +2460,8 @@ impl<'a> InputRecord<'a> {
         let purchase_year = record.get(3).unwrap_or("");
         let notes = record.get(4).unwrap_or("");
         let user_strategy_str = record.get(5).unwrap_or("");
+        let frost_date = parse_input_date(plant_name, "frost date", record.get(6).unwrap_or(""));
+        let start_date = parse_input_date(plant_name, "start date", record.get(7).unwrap_or(""));
 
         // Parse the user strategy string
         let user_strategy = match user_strategy_str {
@@ -406,18 +2479,19 @@ impl<'a> InputRecord<'a> {
             notes,
             user_strategy_str,
             user_strategy,
+            frost_date,
+            start_date,
         }
     }
 
     // Check if this plant has JSON data
-    fn has_json_data(&self, json_dir: &str) -> bool {
-        let json_path = format!("{}/{}.json", json_dir, self.plant_name.replace("/", "_"));
-        Path::new(&json_path).exists()
+    fn has_json_data(&self, index: &names::PlantIndex) -> bool {
+        Path::new(&self.json_path(index)).exists()
     }
 
     // Get the path to the JSON file for this plant
-    fn json_path(&self, json_dir: &str) -> String {
-        format!("{}/{}.json", json_dir, self.plant_name.replace("/", "_"))
+    fn json_path(&self, index: &names::PlantIndex) -> String {
+        index.path_for(self.plant_name)
     }
 
     // Validate URL is not empty for scraping
@@ -462,6 +2536,51 @@ struct OutputRecord<'a> {
     sowing_strategy: String,
     when_to_seed_start: String,
     calculated_start_date: String,
+    earliest_start: String,
+    latest_start: String,
+    transplant_date: String,
+    estimated_first_harvest: String,
+    estimated_last_harvest: String,
+    scrape_age: String,
+    price: String,
+    compare_at_price: String,
+    in_stock: String,
+    botanical_name: &'a str,
+    min_soil_temp: String,
+    ideal_soil_temp_min: String,
+    ideal_soil_temp_max: String,
+    days_to_maturity_min: String,
+    days_to_maturity_max: String,
+    height_min: String,
+    height_max: String,
+    spread_min: String,
+    spread_max: String,
+    seed_depth_min: String,
+    seed_depth_max: String,
+    seed_spacing_min: String,
+    seed_spacing_max: String,
+    row_spacing_min: String,
+    row_spacing_max: String,
+    organic: String,
+    heirloom: String,
+    open_pollinated: String,
+    hybrid: String,
+    disease_resistance: String,
+    duplicate_of: String,
+    plant_spacing: String,
+    plants_per_sq_ft: String,
+    plants_per_bed: String,
+    seeds_needed: String,
+    good_neighbors: String,
+    bad_neighbors: String,
+    alternate_windows: String,
+    overridden: String,
+    status: &'static str,
+    germination_rate: String,
+    seeds_on_hand: String,
+    seed_location: String,
+    enough_seed: String,
+    viability: String,
 }
 
 impl<'a> OutputRecord<'a> {
@@ -471,7 +2590,7 @@ impl<'a> OutputRecord<'a> {
         info: &'a PlantInfo,
         sowing_strategy: Option<SowingStrategy>,
         when_to_start_str: String,
-        start_date: String,
+        dates: &CalculatedDates,
     ) -> Self {
         OutputRecord {
             // Input CSV fields
@@ -513,7 +2632,73 @@ impl<'a> OutputRecord<'a> {
                 .as_ref()
                 .map_or_else(|| "NULL".to_string(), |s| s.to_string()),
             when_to_seed_start: when_to_start_str,
-            calculated_start_date: start_date,
+            calculated_start_date: format_date(dates.start),
+            earliest_start: format_date(dates.earliest_start),
+            latest_start: format_date(dates.latest_start),
+            transplant_date: format_date(dates.transplant),
+            estimated_first_harvest: format_date(dates.first_harvest),
+            estimated_last_harvest: format_date(dates.last_harvest),
+            scrape_age: info
+                .scrape_age_days(Utc::now())
+                .map_or_else(|| "NULL".to_string(), |days| days.to_string()),
+            price: format_price(info.price),
+            compare_at_price: format_price(info.compare_at_price),
+            in_stock: format_flag(info.in_stock),
+            botanical_name: get_field(&info.botanical_name),
+            min_soil_temp: format_number(info.min_soil_temp_f),
+            ideal_soil_temp_min: format_number(info.ideal_soil_temp_min_f),
+            ideal_soil_temp_max: format_number(info.ideal_soil_temp_max_f),
+            days_to_maturity_min: format_number(info.maturity_days().map(|(min, _)| min)),
+            days_to_maturity_max: format_number(info.maturity_days().map(|(_, max)| max)),
+            height_min: format_number(info.height_min_in),
+            height_max: format_number(info.height_max_in),
+            spread_min: format_number(info.spread_min_in),
+            spread_max: format_number(info.spread_max_in),
+            seed_depth_min: format_number(info.seed_depth_min_in),
+            seed_depth_max: format_number(info.seed_depth_max_in),
+            seed_spacing_min: format_number(info.seed_spacing_min_in),
+            seed_spacing_max: format_number(info.seed_spacing_max_in),
+            row_spacing_min: format_number(info.row_spacing_min_in),
+            row_spacing_max: format_number(info.row_spacing_max_in),
+            organic: format_flag(info.organic),
+            heirloom: format_flag(info.heirloom),
+            open_pollinated: format_flag(info.open_pollinated),
+            hybrid: format_flag(info.hybrid),
+            disease_resistance: if info.disease_resistance.is_empty() {
+                "NULL".to_string()
+            } else {
+                info.disease_resistance
+                    .iter()
+                    .map(|resistance| format!("{} ({})", resistance.code, resistance.disease))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            },
+            duplicate_of: "NULL".to_string(),
+            plant_spacing: format_number(info.plant_spacing_in),
+            plants_per_sq_ft: "NULL".to_string(),
+            plants_per_bed: "NULL".to_string(),
+            seeds_needed: "NULL".to_string(),
+            good_neighbors: "NULL".to_string(),
+            bad_neighbors: "NULL".to_string(),
+            alternate_windows: format_windows(&dates.alternate_windows),
+            overridden: "NULL".to_string(),
+            status: if info.discontinued == Some(true) {
+                "Discontinued"
+            } else {
+                "Active"
+            },
+            germination_rate: "NULL".to_string(),
+            seeds_on_hand: "NULL".to_string(),
+            seed_location: "NULL".to_string(),
+            enough_seed: "NULL".to_string(),
+            // Estimated for this year's sowing from the packet's age
+            viability: info
+                .family
+                .as_deref()
+                .and_then(|family| {
+                    viability::estimate(family, input.purchase_year, Local::now().year())
+                })
+                .map_or_else(|| "NULL".to_string(), |viability| viability.to_string()),
         }
     }
 
@@ -549,6 +2734,51 @@ impl<'a> OutputRecord<'a> {
             self.sowing_strategy.clone(),
             self.when_to_seed_start.clone(),
             self.calculated_start_date.clone(),
+            self.earliest_start.clone(),
+            self.latest_start.clone(),
+            self.transplant_date.clone(),
+            self.estimated_first_harvest.clone(),
+            self.estimated_last_harvest.clone(),
+            self.scrape_age.clone(),
+            self.price.clone(),
+            self.compare_at_price.clone(),
+            self.in_stock.clone(),
+            self.botanical_name.to_string(),
+            self.min_soil_temp.clone(),
+            self.ideal_soil_temp_min.clone(),
+            self.ideal_soil_temp_max.clone(),
+            self.days_to_maturity_min.clone(),
+            self.days_to_maturity_max.clone(),
+            self.height_min.clone(),
+            self.height_max.clone(),
+            self.spread_min.clone(),
+            self.spread_max.clone(),
+            self.seed_depth_min.clone(),
+            self.seed_depth_max.clone(),
+            self.seed_spacing_min.clone(),
+            self.seed_spacing_max.clone(),
+            self.row_spacing_min.clone(),
+            self.row_spacing_max.clone(),
+            self.organic.clone(),
+            self.heirloom.clone(),
+            self.open_pollinated.clone(),
+            self.hybrid.clone(),
+            self.disease_resistance.clone(),
+            self.duplicate_of.clone(),
+            self.plant_spacing.clone(),
+            self.plants_per_sq_ft.clone(),
+            self.plants_per_bed.clone(),
+            self.seeds_needed.clone(),
+            self.good_neighbors.clone(),
+            self.bad_neighbors.clone(),
+            self.alternate_windows.clone(),
+            self.overridden.clone(),
+            self.status.to_string(),
+            self.germination_rate.clone(),
+            self.seeds_on_hand.clone(),
+            self.seed_location.clone(),
+            self.enough_seed.clone(),
+            self.viability.clone(),
         ];
 
         // Validate record length matches expected field count
@@ -562,286 +2792,1607 @@ impl<'a> OutputRecord<'a> {
     }
 }
 
-fn process_csv(file_path: &str, json_dir: &str) -> Result<()> {
+// Save an image next to the others, named after the plant with the URL's file extension
+fn download_image(
+    client: &reqwest::blocking::Client,
+    image_url: &str,
+    image_dir: &str,
+    plant_name: &str,
+) -> Result<String, ScrapingError> {
+    let extension = reqwest::Url::parse(image_url)
+        .ok()
+        .and_then(|url| {
+            Path::new(url.path())
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(String::from)
+        })
+        .unwrap_or_else(|| "jpg".to_string());
+    let image_path = format!(
+        "{}/{}.{}",
+        image_dir,
+        plant_name.replace("/", "_"),
+        extension
+    );
+
+    // Paced and backed off like the pages, since images usually come from the same store
+    thread::sleep(request_delay(image_url));
+    let bytes = send_with_backoff(page_request(client, image_url))?
+        .error_for_status()?
+        .bytes()?;
+    fs::write(&image_path, bytes).map_err(ScrapingError::io(&image_path))?;
+    Ok(image_path)
+}
+
+// Point out plants whose files an older parser wrote, since re-parsing them may fill gaps
+fn warn_outdated<'a>(infos: impl Iterator<Item = &'a PlantInfo>) {
+    let outdated = infos.filter(|info| info.parsed_by_older_version()).count();
+    if outdated > 0 {
+        warn!(
+            "{} plants were parsed by an older parser than version {}; run reparse --outdated-only to update them",
+            outdated, PARSER_VERSION
+        );
+    }
+}
+
+// The URLs in a plain list, one per line, skipping blank lines and # comments
+fn parse_url_list(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect()
+}
+
+// The address a saved page was downloaded from, as the page itself records it
+fn saved_page_url(document: &Html) -> Option<String> {
+    static PAGE_URL: Pattern<Selector> = LazyLock::new(|| {
+        selector(r#"link[rel="canonical"][href], meta[property="og:url"][content]"#)
+    });
+    let element = document.select(compiled(&PAGE_URL).ok()?).next()?;
+    element
+        .value()
+        .attr("href")
+        .or_else(|| element.value().attr("content"))
+        .map(String::from)
+}
+
+// Parse a product page saved to disk, e.g. one downloaded through a browser to get past a block
+fn parse_saved_page(path: &str, url: Option<&str>) -> Result<PlantInfo> {
+    let bytes = fs::read(path).context(format!("Failed to read {}", path))?;
+    let body = charset::decode_page(&bytes, None);
+    let url = url
+        .map(String::from)
+        .or_else(|| saved_page_url(&Html::parse_document(&body)))
+        .unwrap_or_else(|| format!("file://{}", path));
+
+    let page = FetchedPage {
+        status: 200,
+        final_url: None,
+        etag: None,
+        last_modified: None,
+        body,
+    };
+    let mut info = PlantInfo::from_html(&page.body, url)?;
+    info.record_scrape(&page);
+    // Nothing was fetched, so there's no response status to record
+    info.http_status = None;
+    Ok(info)
+}
+
+// Scrape pages one at a time and print their JSON, an array when there are several.
+// Returns whether every page was scraped.
+fn scrape_single(
+    urls: &[String],
+    output: Option<&str>,
+    output_format: OutputFormat,
+) -> Result<bool> {
+    let client = create_http_client();
+    let mut scraped = Vec::new();
+
+    for (i, url) in urls.iter().enumerate() {
+        let _span = info_span!("page", url = url.as_str()).entered();
+        if i > 0 {
+            // Sleep between requests
+            thread::sleep(request_delay(url));
+        }
+
+        let url = urls::canonical_url(url);
+        let page = match fetch_page(page_request(&client, &url)) {
+            Ok(page) => page,
+            Err(e) => {
+                error!("Failed to fetch page: {:#}", e);
+                continue;
+            }
+        };
+        match parse_fetched(&client, &page, url) {
+            Ok(mut info) => {
+                info.fetch_related(&client, &page.body);
+                info.record_scrape(&page);
+                scraped.push(info);
+            }
+            Err(ScrapingError::Blocked) => {
+                error!("Access blocked by Cloudflare protection");
+                error!("Try again later or verify the URL is correct");
+            }
+            Err(e) => error!("{}", e),
+        }
+    }
+
+    let json = match scraped.as_slice() {
+        [] if urls.len() == 1 => return Ok(false),
+        [info] if urls.len() == 1 => serde_json::to_string_pretty(info)?,
+        _ => serde_json::to_string_pretty(&scraped)?,
+    };
+    println!("{}", json);
+
+    if let Some(output_path) = output {
+        fs::write(output_path, &json)
+            .context(format!("Failed to write output to {}", output_path))?;
+        output_format.print_text(&format!("Results saved to: {}", output_path));
+    }
+    Ok(scraped.len() == urls.len())
+}
+
+// Where a batch stopped early by repeated blocks leaves the rows it didn't get to
+const REMAINING_FILE: &str = "remaining.csv";
+
+// How a batch went overall, reported through the process exit code
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BatchOutcome {
+    Success,
+    PartialFailure,
+    AllFailed,
+    CloudflareBlocked,
+    // Gave up on the rest of the plants after too many blocks in a row
+    Halted,
+}
+
+impl BatchOutcome {
+    // Being blocked is worth reacting to differently, since retrying right away won't help
+    fn from_counts(succeeded: usize, failed: usize, blocked: bool) -> Self {
+        if blocked {
+            BatchOutcome::CloudflareBlocked
+        } else if failed == 0 {
+            BatchOutcome::Success
+        } else if succeeded == 0 {
+            BatchOutcome::AllFailed
+        } else {
+            BatchOutcome::PartialFailure
+        }
+    }
+
+    fn exit_code(self) -> i32 {
+        match self {
+            BatchOutcome::Success => 0,
+            BatchOutcome::PartialFailure => 2,
+            BatchOutcome::AllFailed => 3,
+            BatchOutcome::CloudflareBlocked => 4,
+            BatchOutcome::Halted => 5,
+        }
+    }
+}
+
+fn process_csv(
+    file_path: &str,
+    json_dir: &str,
+    image_dir: Option<&str>,
+    webhook_url: Option<&str>,
+    interactive: bool,
+    max_blocks: u32,
+    output: OutputFormat,
+) -> Result<BatchOutcome> {
     let results_dir = Path::new(json_dir);
     if !results_dir.exists() {
         fs::create_dir(results_dir).context(format!("Failed to create directory: {}", json_dir))?;
     }
+    if let Some(image_dir) = image_dir {
+        fs::create_dir_all(image_dir)
+            .context(format!("Failed to create directory: {}", image_dir))?;
+    }
 
+    let mut index = names::PlantIndex::load(json_dir)?;
+    let mut overrides = overrides::Overrides::load(json_dir)?;
     let mut failed_plants = Vec::new();
-    let mut rdr = csv::Reader::from_path(file_path)
-        .context(format!("Failed to read CSV file: {}", file_path))?;
+    // Also in failed_plants, but worth telling apart since a slow host may answer next time
+    let mut timed_out_plants = Vec::new();
+    // Scraped, but only to record that the store no longer lists them
+    let mut discontinued_plants = Vec::new();
+    let mut scraped = 0;
+    let mut skipped = 0;
+    let mut blocked = false;
+    // The rows blocked since the last page that came through, which are retried along with
+    // the rest if too many of them stop the batch
+    let mut blocked_rows = Vec::new();
+    let mut remaining = Vec::new();
+    if interactive && file_path == "-" {
+        anyhow::bail!(
+            "--interactive reads answers from stdin, so the CSV can't come from there too"
+        );
+    }
+    let mut rdr = open_csv(file_path)?;
+    let headers = rdr.headers()?.clone();
+    let columns = columns::ColumnMap::from_headers(&headers);
+    let client = create_http_client();
+    // Which plant each already-scraped product is stored as, to catch a second link to it
+    let mut stored_urls: HashMap<String, String> = load_json_dir(json_dir)?
+        .into_iter()
+        .map(|(name, info)| (urls::canonical_url(&info.url), name))
+        .collect();
 
-    for result in rdr.records() {
-        let record = match result {
-            Ok(record) => record,
+    let mut records = rdr.records();
+    for result in records.by_ref() {
+        let raw = match result {
+            Ok(raw) => raw,
             Err(e) => {
-                eprintln!("Error reading CSV record: {}", e);
+                warn!("Error reading CSV record: {}", e);
                 continue;
             }
         };
+        let record = columns.normalize(&raw);
 
         // Parse the input record
         let input = InputRecord::from_csv_record(&record);
+        let _span = info_span!("plant", plant = input.plant_name, url = input.url).entered();
 
         // Validate URL for scraping
         if !input.has_valid_url() {
-            eprintln!("Empty URL for plant: {}", input.plant_name);
+            error!("Empty URL for plant: {}", input.plant_name);
             failed_plants.push(input.plant_name.to_string());
             continue;
         }
 
         // Skip if file already exists
-        if input.has_json_data(json_dir) {
-            println!("Skipping {} - result file already exists", input.plant_name);
+        if input.has_json_data(&index) {
+            info!("Skipping {} - result file already exists", input.plant_name);
+            skipped += 1;
+            continue;
+        }
+
+        // The same product under another name shares that plant's file instead of being
+        // scraped again
+        let url = urls::canonical_url(input.url);
+        if let Some(existing) = stored_urls.get(&url) {
+            info!(
+                "Skipping {} - same product as {}",
+                input.plant_name, existing
+            );
+            index.share(input.plant_name, existing);
+            if let Err(e) = index.save() {
+                error!("Failed to update the index for {}: {}", input.plant_name, e);
+            }
+            skipped += 1;
             continue;
         }
 
-        println!("Processing {} from {}", input.plant_name, input.url);
+        info!("Processing {} from {}", input.plant_name, url);
 
         // Sleep between requests
-        thread::sleep(StdDuration::from_secs(2));
+        thread::sleep(request_delay(&url));
 
-        let client = create_http_client();
-        let response = match client.get(input.url).send().and_then(|r| r.text()) {
-            Ok(text) => text,
+        // The page can take all of the plant's time; the optional extras are skipped once
+        // it's used up
+        let deadline = Instant::now() + network().plant_timeout;
+        let request =
+            page_request(&client, &url).timeout(request_timeout(&url).min(network().plant_timeout));
+        let page = match fetch_page(request) {
+            Ok(page) => page,
             Err(e) => {
-                eprintln!("Failed to fetch {}: {}", input.plant_name, e);
+                error!("Failed to fetch {}: {}", input.plant_name, e);
+                if matches!(e, ScrapingError::Timeout(_)) {
+                    timed_out_plants.push(input.plant_name.to_string());
+                }
                 failed_plants.push(input.plant_name.to_string());
                 continue;
             }
         };
+        if page.status == 429 {
+            error!(
+                "Failed to fetch {}: still rate limited after {} retries",
+                input.plant_name,
+                backoff::MAX_RETRIES
+            );
+            failed_plants.push(input.plant_name.to_string());
+            continue;
+        }
+        let out_of_time = || {
+            let out_of_time = Instant::now() >= deadline;
+            if out_of_time {
+                warn!(
+                    "Out of time for {}, skipping its related products and image",
+                    input.plant_name
+                );
+            }
+            out_of_time
+        };
 
-        match PlantInfo::from_html(&response, input.url.to_string()) {
-            Ok(info) => {
+        match parse_fetched(&client, &page, url.clone()) {
+            Ok(mut info) => {
+                blocked_rows.clear();
+                if !out_of_time() {
+                    info.fetch_related(&client, &page.body);
+                }
+                info.record_scrape(&page);
                 let json = match serde_json::to_string_pretty(&info) {
                     Ok(j) => j,
                     Err(e) => {
-                        eprintln!("Failed to serialize JSON for {}: {}", input.plant_name, e);
+                        error!("Failed to serialize JSON for {}: {}", input.plant_name, e);
                         failed_plants.push(input.plant_name.to_string());
                         continue;
                     }
                 };
 
-                if let Err(e) = fs::write(&input.json_path(json_dir), json) {
-                    eprintln!("Failed to write file for {}: {}", input.plant_name, e);
+                let json_path = index.assign(input.plant_name, &url);
+                if let Err(e) = fs::write(&json_path, json)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|_| index.save())
+                {
+                    error!("Failed to write file for {}: {}", input.plant_name, e);
                     failed_plants.push(input.plant_name.to_string());
+                    continue;
+                }
+
+                // A missing image isn't worth failing the plant over; it's named like the JSON
+                let image_name = Path::new(&json_path)
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .unwrap_or(input.plant_name);
+                if let (Some(image_dir), Some(image_url)) = (image_dir, info.images.first()) {
+                    let downloaded = if out_of_time() {
+                        Ok(())
+                    } else {
+                        download_image(&client, image_url, image_dir, image_name).map(|_| ())
+                    };
+                    if let Err(e) = downloaded {
+                        error!("Failed to download image for {}: {}", input.plant_name, e);
+                    }
+                }
+                stored_urls.insert(url, input.plant_name.to_string());
+                scraped += 1;
+
+                if info.discontinued == Some(true) {
+                    discontinued_plants.push(input.plant_name.to_string());
+                } else if interactive {
+                    let filled = interactive::fill_missing(
+                        input.plant_name,
+                        &info,
+                        input.user_strategy,
+                        &mut overrides,
+                    )?;
+                    if filled {
+                        overrides.save()?;
+                    }
+                }
+            }
+            Err(ScrapingError::Blocked) => {
+                error!("Cloudflare blocked {}", input.plant_name);
+                failed_plants.push(input.plant_name.to_string());
+                blocked = true;
+                blocked_rows.push(raw);
+                // Later requests will likely be blocked too, so say so right away
+                if let Some(webhook_url) = webhook_url {
+                    notify::post_event(
+                        webhook_url,
+                        "cloudflare_blocked",
+                        "Scraping blocked by Cloudflare",
+                        &format!("Cloudflare blocked {} ({})", input.plant_name, input.url),
+                        serde_json::json!({"plant": input.plant_name, "url": input.url}),
+                    );
+                }
+                if blocked_rows.len() >= max_blocks as usize {
+                    error!(
+                        "Cloudflare blocked {} plants in a row, stopping",
+                        blocked_rows.len()
+                    );
+                    remaining.append(&mut blocked_rows);
+                    break;
                 }
             }
             Err(e) => {
-                eprintln!("Failed to parse HTML for {}: {}", input.plant_name, e);
+                blocked_rows.clear();
+                error!("Failed to parse HTML for {}: {}", input.plant_name, e);
                 failed_plants.push(input.plant_name.to_string());
             }
         }
     }
+    let halted = !remaining.is_empty();
+    remaining.extend(records.filter_map(Result::ok));
+    let remaining_path = results_dir.join(REMAINING_FILE);
+    if halted {
+        let mut writer = csv::Writer::from_path(&remaining_path)
+            .context(format!("Failed to write {}", remaining_path.display()))?;
+        writer.write_record(&headers)?;
+        for record in &remaining {
+            writer.write_record(record)?;
+        }
+        writer.flush()?;
+    }
 
     if !failed_plants.is_empty() {
         eprintln!("\nFailed to process the following plants:");
         for plant in &failed_plants {
-            eprintln!("- {}", plant);
+            if timed_out_plants.contains(plant) {
+                eprintln!("- {} (timed out)", plant);
+            } else {
+                eprintln!("- {}", plant);
+            }
         }
     } else {
-        println!("All plants processed successfully.");
+        output.print_text("All plants processed successfully.");
+    }
+    if !discontinued_plants.is_empty() {
+        output.print_text(&format!(
+            "Marked as discontinued: {}",
+            discontinued_plants.join(", ")
+        ));
     }
 
-    println!("JSON results saved to directory: {}", json_dir);
-    Ok(())
-}
+    output.print_text(&format!("JSON results saved to directory: {}", json_dir));
+    if halted {
+        eprintln!(
+            "\nStopped early; {} blocked or untried plants were saved to {}. Batch that file once \
+             the store stops blocking requests.",
+            remaining.len(),
+            remaining_path.display()
+        );
+    }
 
-fn export_to_csv(input_file: &str, output_file: &str, json_dir: &str) -> Result<()> {
-    let results_dir = Path::new(json_dir);
-    if !results_dir.exists() {
-        return Err(anyhow::anyhow!("Directory {} does not exist", json_dir));
+    let summary = serde_json::json!({
+        "input_file": file_path,
+        "json_dir": json_dir,
+        "scraped": scraped,
+        "skipped": skipped,
+        "failed": failed_plants,
+        "timed_out": timed_out_plants,
+        "discontinued": discontinued_plants,
+        "remaining": halted.then(|| remaining_path.display().to_string()),
+    });
+    output.print_json(&summary)?;
+    if let Some(webhook_url) = webhook_url {
+        notify::post_event(
+            webhook_url,
+            "batch_complete",
+            "Seed scraping batch finished",
+            &format!(
+                "Scraped {} plants, skipped {}, {} failed",
+                scraped,
+                skipped,
+                failed_plants.len()
+            ),
+            summary,
+        );
+    }
+    if halted {
+        return Ok(BatchOutcome::Halted);
     }
+    Ok(BatchOutcome::from_counts(
+        scraped + skipped,
+        failed_plants.len(),
+        blocked,
+    ))
+}
 
-    // Read the input CSV file
-    let mut input_rdr = csv::Reader::from_path(input_file)
-        .context(format!("Failed to read input CSV file: {}", input_file))?;
+// Scraped data for one plant along with everything calculated from it
+struct PlantExport {
+    info: PlantInfo,
+    sowing_strategy: Option<SowingStrategy>,
+    when_to_start: Vec<SowingTime>,
+    dates: CalculatedDates,
+    // Other plants in the same export that look like the same variety from another vendor
+    duplicate_of: Vec<String>,
+    // Square-foot garden density and the plants and seeds for the export's bed or row
+    layout: spacing::Layout,
+    // Companion plants elsewhere in the same export
+    neighbors: companions::Neighbors,
+    // Fields whose values came from overrides.toml rather than the scrape
+    overridden: Vec<String>,
+    // Percent that sprouted in the latest germination test of this packet's lot
+    germination_rate: Option<f32>,
+    // Seeds on hand from inventory.toml, and whether they cover the planned sowings
+    stock: Option<inventory::Stock>,
+    enough_seed: Option<bool>,
+}
 
-    let mut writer = csv::Writer::from_path(output_file)?;
+impl PlantExport {
+    fn new(info: PlantInfo, user_strategy: Option<SowingStrategy>, frost_date: NaiveDate) -> Self {
+        // Get the sowing strategy as an enum
+        let sowing_strategy = determine_sowing_strategy(&info, user_strategy);
 
-    // Write headers - include the original columns plus the scraped data
-    writer.write_record(&[
-        "Plant Name",
-        "URL",
-        "Brand",                 // New column
-        "Purchase Year",         // New column
-        "Notes",                 // New column
-        "Users Sowing Strategy", // New column to be preserved
-        "Title",
-        "Description",
-        "Days to Maturity",
-        "Family",
-        "Plant Type",
-        "Native",
-        "Hardiness",
-        "Exposure",
-        "Plant Dimensions",
-        "Variety Info",
-        "Attributes",
-        "When to Sow Outside",
-        "When to Start Inside",
-        "Days to Emerge",
-        "Seed Depth",
-        "Seed Spacing",
-        "Row Spacing",
-        "Thinning",
-        "Rating",
-        "Votes",
-        "Sowing Strategy",
-        "When to Seed Start",
-        "Calculated Start Date",
-    ])?;
+        // Get the sowing time based on the strategy enum
+        let when_to_start = get_when_to_seed_start(&info, user_strategy);
 
-    let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
-    let mut processed_count = 0;
-    let mut missing_json_count = 0;
+        let dates = calculate_dates(&info, sowing_strategy, &when_to_start, frost_date);
 
-    // Process each row in the input CSV
-    for result in input_rdr.records() {
-        let record = match result {
-            Ok(record) => record,
-            Err(e) => {
-                eprintln!("Error reading CSV record: {}", e);
-                continue;
+        PlantExport {
+            info,
+            sowing_strategy,
+            when_to_start,
+            dates,
+            duplicate_of: Vec::new(),
+            layout: spacing::Layout::default(),
+            neighbors: companions::Neighbors::default(),
+            overridden: Vec::new(),
+            germination_rate: None,
+            stock: None,
+            enough_seed: None,
+        }
+    }
+
+    // The export for an input row, with that row's frost date and pinned start date if it has them
+    fn for_input(
+        info: PlantInfo,
+        input: &InputRecord,
+        user_strategy: Option<SowingStrategy>,
+        frost_date: NaiveDate,
+    ) -> Self {
+        let mut plant = Self::new(info, user_strategy, input.frost_date.unwrap_or(frost_date));
+        if let Some(start) = input.start_date {
+            plant.pin_start(start);
+        }
+        plant
+    }
+
+    // Replace the computed sowing window with one day, moving the harvest estimate along with
+    // it unless that counts from transplanting
+    fn pin_start(&mut self, start: NaiveDate) {
+        self.dates.start = Some(start);
+        self.dates.earliest_start = Some(start);
+        self.dates.latest_start = Some(start);
+        if self.dates.transplant.is_none() {
+            if let Some((min, max)) = self.info.maturity_days() {
+                self.dates.first_harvest = Some(start + Days::new(u64::from(min)));
+                self.dates.last_harvest = Some(start + Days::new(u64::from(max)));
             }
-        };
+        }
+    }
+}
 
+// A row of the input CSV, joined with its plant data when the JSON file exists
+struct ExportRow<'a> {
+    input: InputRecord<'a>,
+    plant: Option<PlantExport>,
+}
+
+// Describe a sowing time compactly, e.g. "2-4 before LAST_FROST"
+fn format_sowing_time(sowing_time: Option<&SowingTime>) -> String {
+    sowing_time
+        .map(|sowing_time| {
+            let relative = match sowing_time.relative_timing {
+                RelativeTiming::Before => "before",
+                RelativeTiming::After => "after",
+            };
+            let timing = match sowing_time.timing_type {
+                TimingType::LastFrost => "LAST_FROST",
+                TimingType::Transplant => "TRANSPLANT",
+                TimingType::FallFrost => "FALL_FROST",
+                TimingType::Overwinter => "GROUND_FREEZE",
+            };
+            format!(
+                "{}-{} {} {}",
+                sowing_time.weeks_min, sowing_time.weeks_max, relative, timing
+            )
+        })
+        .unwrap_or_else(|| "NULL".to_string())
+}
+
+fn load_export_rows<'a>(
+    records: &'a [csv::StringRecord],
+    json_dir: &str,
+    options: &ExportOptions,
+) -> Result<Vec<ExportRow<'a>>> {
+    let mut rows = Vec::new();
+    let index = names::PlantIndex::load(json_dir)?;
+    let plant_names = index.plant_names();
+    let mut overrides = overrides::Overrides::load(json_dir)?;
+    let germination = germination::GerminationLog::load(json_dir)?;
+    let inventory = inventory::Inventory::load(json_dir)?;
+
+    for record in records {
         // Parse the input record
-        let input = InputRecord::from_csv_record(&record);
+        let input = InputRecord::from_csv_record(record);
+        let _span = info_span!("plant", plant = input.plant_name).entered();
 
-        // Check if JSON data exists for this plant
-        if !input.has_json_data(json_dir) {
-            eprintln!(
-                "Warning: No JSON data found for plant: {}",
-                input.plant_name
+        // Check if JSON data exists for this plant, allowing for small differences in the name
+        let json_path = if input.has_json_data(&index) {
+            input.json_path(&index)
+        } else if let Some(name) = names::closest_match(input.plant_name, &plant_names) {
+            warn!(
+                "No JSON data found for plant: {}; using close match {}",
+                input.plant_name, name
             );
-            // Use the helper function to create the error record
-            let row = create_error_record(&input);
-            writer.write_record(&row)?;
-            missing_json_count += 1;
+            index.path_for(name)
+        } else {
+            warn!("No JSON data found for plant: {}", input.plant_name);
+            rows.push(ExportRow { input, plant: None });
             continue;
-        }
+        };
 
         // Read and parse the JSON file
-        let content = match fs::read_to_string(&input.json_path(json_dir)) {
+        let content = match fs::read_to_string(json_path) {
             Ok(content) => content,
             Err(e) => {
-                eprintln!("Failed to read JSON file for {}: {}", input.plant_name, e);
+                warn!("Failed to read JSON file for {}: {}", input.plant_name, e);
                 continue;
             }
         };
 
-        let info: PlantInfo = match serde_json::from_str(&content) {
+        let mut info = match schema::parse_plant_json(&content) {
             Ok(info) => info,
             Err(e) => {
-                eprintln!("Failed to parse JSON for {}: {}", input.plant_name, e);
+                warn!("Failed to parse JSON for {}: {}", input.plant_name, e);
                 continue;
             }
         };
 
-        // Get the sowing strategy as an enum
-        let sowing_strategy = determine_sowing_strategy(&info, input.user_strategy);
+        let mut overridden = overrides
+            .apply(input.plant_name, &mut info)
+            .unwrap_or_else(|e| {
+                warn!("{:#}", e);
+                Vec::new()
+            });
+        if options.interactive {
+            let user_strategy = input.user_strategy;
+            if interactive::fill_missing(input.plant_name, &info, user_strategy, &mut overrides)? {
+                overrides.save()?;
+                overridden = overrides.apply(input.plant_name, &mut info)?;
+            }
+        }
+        let override_strategy = overrides.strategy(input.plant_name);
+        if input.user_strategy.is_none() && override_strategy.is_some() {
+            overridden.push("sowing_strategy".to_string());
+        }
+        let user_strategy = input.user_strategy.or(override_strategy);
 
-        // Get the sowing time based on the strategy enum
-        let when_to_start = get_when_to_seed_start(&info, input.user_strategy);
+        info.choose_variant(options.variant);
+        // Densities are worked out in inches, before any conversion
+        let layout = spacing::layout(&info, options.bed_size, options.row_length);
+        info.convert_units(options.units);
+        let mut plant = PlantExport::for_input(info, &input, user_strategy, options.frost_date);
+        plant.layout = layout;
+        plant.overridden = overridden;
+        plant.germination_rate = germination
+            .latest(input.plant_name, input.purchase_year)
+            .map(germination::GerminationTest::rate);
+        plant.stock = inventory.get(input.plant_name).cloned();
+        plant.enough_seed = inventory::enough_seed(
+            plant.stock.as_ref(),
+            plant.layout.seeds_needed,
+            options.sowings,
+        );
+        rows.push(ExportRow {
+            input,
+            plant: Some(plant),
+        });
+    }
 
-            let when_to_start_str = when_to_start
-                .as_ref()
-                .map(|sowing_time| {
-                    let relative = match sowing_time.relative_timing {
-                        RelativeTiming::Before => "before",
-                        RelativeTiming::After => "after",
-                    };
-                    let timing = match sowing_time.timing_type {
-                        TimingType::LastFrost => "LAST_FROST",
-                        TimingType::Transplant => "TRANSPLANT",
-                    };
-                    format!(
-                        "{}-{} {} {}",
-                        sowing_time.weeks_min, sowing_time.weeks_max, relative, timing
-                    )
+    Ok(rows)
+}
+
+// Convert an export row to its CSV fields, using ERR placeholders when JSON is missing
+fn export_record(row: &ExportRow) -> Vec<String> {
+    let plant = match &row.plant {
+        Some(plant) => plant,
+        None => {
+            // Use the helper function to create the error record
+            return create_error_record(&row.input)
+                .into_iter()
+                .map(String::from)
+                .collect();
+        }
+    };
+
+    // Create an OutputRecord and convert it to strings
+    let mut output = OutputRecord::new(
+        &row.input,
+        &plant.info,
+        plant.sowing_strategy,
+        format_sowing_time(plant.when_to_start.first()),
+        &plant.dates,
+    );
+    if !plant.duplicate_of.is_empty() {
+        output.duplicate_of = plant.duplicate_of.join(", ");
+    }
+    output.plants_per_sq_ft = format_number(plant.layout.plants_per_sq_ft);
+    output.plants_per_bed = format_number(plant.layout.plants_per_bed);
+    output.seeds_needed = format_number(plant.layout.seeds_needed);
+    if !plant.neighbors.good.is_empty() {
+        output.good_neighbors = plant.neighbors.good.join(", ");
+    }
+    if !plant.neighbors.bad.is_empty() {
+        output.bad_neighbors = plant.neighbors.bad.join(", ");
+    }
+    if !plant.overridden.is_empty() {
+        output.overridden = plant.overridden.join(", ");
+    }
+    output.germination_rate = format_number(plant.germination_rate.map(f32::round));
+    if let Some(stock) = &plant.stock {
+        output.seeds_on_hand = format_number(stock.quantity);
+        output.seed_location = get_field(&stock.location).to_string();
+    }
+    output.enough_seed = format_flag(plant.enough_seed);
+    output.to_record()
+}
+
+// Named column sets for --columns
+const COLUMN_PRESETS: [(&str, &[&str]); 1] = [(
+    "planner",
+    &[
+        "Plant Name",
+        "Sowing Strategy",
+        "Calculated Start Date",
+        "Plant Spacing (in)",
+    ],
+)];
+
+// Positions in CSV_HEADERS of the columns to export, in the order to write them
+#[derive(Debug, Clone, PartialEq)]
+struct ColumnSelection(Vec<usize>);
+
+impl ColumnSelection {
+    fn pick(&self, record: Vec<String>) -> Vec<String> {
+        self.0.iter().map(|&i| record[i].clone()).collect()
+    }
+}
+
+// A preset name or a comma-separated list of headers, matched ignoring case and in either
+// unit system's wording
+fn parse_export_columns(text: &str) -> Result<ColumnSelection, String> {
+    let names: Vec<&str> = match COLUMN_PRESETS
+        .iter()
+        .find(|(preset, _)| preset.eq_ignore_ascii_case(text.trim()))
+    {
+        Some((_, headers)) => headers.to_vec(),
+        None => text.split(',').map(str::trim).collect(),
+    };
+    let positions = names
+        .into_iter()
+        .map(|name| {
+            CSV_HEADERS
+                .iter()
+                .position(|header| {
+                    header.eq_ignore_ascii_case(name)
+                        || Units::Metric.header(header).eq_ignore_ascii_case(name)
                 })
-                .unwrap_or_else(|| "NULL".to_string());
+                .ok_or_else(|| format!("\"{}\" is not an export column or preset", name))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(ColumnSelection(positions))
+}
+
+// Write the export as CSV, with only the selected columns if there's a selection; an output
+// of "-" writes to stdout
+fn write_csv_export(
+    rows: &[ExportRow],
+    units: Units,
+    columns: Option<&ColumnSelection>,
+    output_file: &str,
+) -> Result<()> {
+    let out: Box<dyn io::Write> = if output_file == "-" {
+        Box::new(io::stdout().lock())
+    } else {
+        Box::new(
+            fs::File::create(output_file).context(format!("Failed to create {}", output_file))?,
+        )
+    };
+    let mut writer = csv::Writer::from_writer(out);
 
-            let start_date = when_to_start
-                .map(|t| calculate_start_date(&t, frost_date))
-                .map(|d| d.format("%Y-%m-%d").to_string())
-                .unwrap_or_else(|| "NULL".to_string());
+    let select = |record: Vec<String>| match columns {
+        Some(columns) => columns.pick(record),
+        None => record,
+    };
 
-        // Create an OutputRecord and write it to the CSV
-        let record = OutputRecord::new(
-            &input,
-            &info,
-                sowing_strategy,
-                when_to_start_str,
-                start_date,
-        );
+    // Write headers - include the original columns plus the scraped data
+    writer.write_record(select(
+        CSV_HEADERS
+            .iter()
+            .map(|header| units.header(header))
+            .collect(),
+    ))?;
 
-        // Convert the record to strings and write them to the CSV
-        let string_record = record.to_record();
-        let str_refs: Vec<&str> = string_record.iter().map(|s| s.as_str()).collect();
-        writer.write_record(&str_refs)?;
-        processed_count += 1;
+    for row in rows {
+        writer.write_record(select(export_record(row)))?;
     }
 
     writer.flush()?;
-    println!("Exported data to {}", output_file);
-    println!("Used JSON data from directory: {}", json_dir);
-    println!("Used input CSV file: {}", input_file);
-    println!(
-        "Processed {} plants ({} with missing JSON data marked as ERR)",
-        processed_count + missing_json_count,
+    Ok(())
+}
+
+// A CSV file to read, or stdin when the path is "-"
+fn open_csv(path: &str) -> Result<csv::Reader<Box<dyn io::Read>>, ScrapingError> {
+    let input: Box<dyn io::Read> = if path == "-" {
+        Box::new(io::stdin())
+    } else {
+        Box::new(fs::File::open(path).map_err(ScrapingError::io(path))?)
+    };
+    Ok(csv::Reader::from_reader(input))
+}
+
+// Every readable row of the input CSV, in the original column layout
+fn read_input_csv(input_file: &str) -> Result<Vec<csv::StringRecord>, ScrapingError> {
+    let mut input_rdr = open_csv(input_file)?;
+
+    let columns = columns::ColumnMap::from_headers(input_rdr.headers()?);
+
+    let mut records = Vec::new();
+    for result in input_rdr.records() {
+        match result {
+            Ok(record) => records.push(columns.normalize(&record)),
+            Err(e) => warn!("Error reading CSV record: {}", e),
+        }
+    }
+    Ok(records)
+}
+
+// Each input record joined with its scraped data, filtered and cross-referenced as asked
+fn build_export_rows<'a>(
+    records: &'a [csv::StringRecord],
+    json_dir: &str,
+    options: &ExportOptions,
+) -> Result<Vec<ExportRow<'a>>> {
+    let mut rows = load_export_rows(records, json_dir, options)?;
+    if !options.tags.is_empty() || !options.resistant_to.is_empty() {
+        rows.retain(|row| {
+            row.plant.as_ref().is_some_and(|plant| {
+                plant.info.has_tags(&options.tags)
+                    && options
+                        .resistant_to
+                        .iter()
+                        .all(|disease| plant.info.resists(disease))
+            })
+        });
+    }
+    warn_outdated(
+        rows.iter()
+            .filter_map(|row| row.plant.as_ref().map(|plant| &plant.info)),
+    );
+    duplicates::mark_duplicates(&mut rows);
+    companions::mark_neighbors(&mut rows);
+    if let Some(key) = options.sort_by {
+        sort_rows(&mut rows, key);
+    }
+
+    Ok(rows)
+}
+
+// Order rows by the key and then by name. Plants without a value for the key come after the
+// rest, and rows with no plant data at all come last.
+fn sort_rows(rows: &mut [ExportRow], key: SortKey) {
+    rows.sort_by_cached_key(|row| {
+        let plant = row.plant.as_ref();
+        // Formatted so that comparing the text orders them correctly
+        let value = match key {
+            SortKey::Name => Some(String::new()),
+            SortKey::Family => plant
+                .and_then(|plant| plant.info.family.as_ref())
+                .map(|family| family.to_lowercase()),
+            SortKey::Start => plant
+                .and_then(|plant| plant.dates.start)
+                .map(|start| start.format("%Y-%m-%d").to_string()),
+            SortKey::Maturity => plant
+                .and_then(|plant| plant.info.maturity_days())
+                .map(|(min, max)| format!("{:05}-{:05}", min, max)),
+        };
+        (
+            plant.is_none(),
+            value.is_none(),
+            value,
+            row.input.plant_name.to_lowercase(),
+        )
+    });
+}
+
+fn export(
+    input_file: &str,
+    output_file: &str,
+    json_dir: &str,
+    options: &ExportOptions,
+    output: OutputFormat,
+) -> Result<()> {
+    let results_dir = Path::new(json_dir);
+    if !results_dir.exists() {
+        return Err(anyhow::anyhow!("Directory {} does not exist", json_dir));
+    }
+
+    if options.columns.is_some() && !matches!(options.format, ExportFormat::Csv) {
+        anyhow::bail!("--columns only applies to csv exports");
+    }
+    if options.preset.is_some() && !matches!(options.format, ExportFormat::Csv) {
+        anyhow::bail!("--preset only applies to csv exports");
+    }
+    if matches!(options.format, ExportFormat::Template) != options.template.is_some() {
+        anyhow::bail!("--template and --format template go together");
+    }
+    let streams = matches!(
+        options.format,
+        ExportFormat::Csv | ExportFormat::Jsonl | ExportFormat::Template
+    );
+    if output_file == "-" && !streams {
+        anyhow::bail!("Only csv, jsonl, and template exports can be written to stdout");
+    }
+    if input_file == "-" && options.interactive {
+        anyhow::bail!(
+            "--interactive reads answers from stdin, so the CSV can't come from there too"
+        );
+    }
+    let records = read_input_csv(input_file)?;
+    let rows = build_export_rows(&records, json_dir, options)?;
+
+    match options.format {
+        ExportFormat::Csv => match options.preset {
+            Some(ExportPreset::Schedule) => {
+                formats::schedule::write_schedule_csv(&rows, output_file)?
+            }
+            Some(ExportPreset::Journal) => {
+                let log = planting_log::PlantingLog::load(json_dir)?;
+                formats::journal::write_journal_csv(&rows, &log, options.frost_date, output_file)?
+            }
+            None => write_csv_export(&rows, options.units, options.columns.as_ref(), output_file)?,
+        },
+        ExportFormat::Todoist => formats::todoist::write_todoist_csv(&rows, output_file)?,
+        ExportFormat::Markdown => {
+            formats::markdown::write_markdown(&rows, options.group_by, output_file)?
+        }
+        ExportFormat::Html => formats::html::write_html(&rows, options.units, output_file)?,
+        ExportFormat::Pdf => formats::pdf_schedule::write_pdf_schedule(&rows, output_file)?,
+        ExportFormat::Labels => {
+            let layout = match options.label_grid {
+                Some((columns, rows)) => formats::labels::LabelLayout::grid(columns, rows),
+                None => formats::labels::LabelLayout::avery_5160(),
+            };
+            formats::labels::write_labels(&rows, layout, output_file)?
+        }
+        ExportFormat::Parquet => {
+            formats::parquet_export::write_parquet(&rows, options.units, output_file)?
+        }
+        ExportFormat::Jsonl => formats::jsonl::write_jsonl(&rows, options.units, output_file)?,
+        ExportFormat::Template => {
+            let template = options.template.as_deref().unwrap_or_default();
+            formats::template::write_template(&rows, options.units, template, output_file)?
+        }
+    }
+
+    let missing_json_count = rows.iter().filter(|row| row.plant.is_none()).count();
+    let summary = format!(
+        "Exported data to {}\n\
+         Used JSON data from directory: {}\n\
+         Used input CSV file: {}\n\
+         Processed {} plants ({} with missing JSON data marked as ERR)",
+        output_file,
+        json_dir,
+        input_file,
+        rows.len(),
         missing_json_count
     );
+    // Keep stdout clean when the export itself is being streamed there
+    if output_file == "-" {
+        eprintln!("{}", summary);
+        return Ok(());
+    }
+    output.print_text(&summary);
+    output.print_json(&serde_json::json!({
+        "output_file": output_file,
+        "json_dir": json_dir,
+        "input_file": input_file,
+        "plants": rows.len(),
+        "missing_json": missing_json_count,
+    }))
+}
+
+// Load every plant JSON file in a directory, named after the file it came from
+fn load_json_dir(json_dir: &str) -> Result<Vec<(String, PlantInfo)>> {
+    let mut plants = Vec::new();
+    let index = names::PlantIndex::load(json_dir)?;
+
+    for entry in
+        fs::read_dir(json_dir).context(format!("Failed to read directory: {}", json_dir))?
+    {
+        let path = entry?.path();
+        let is_json = path.extension().and_then(|ext| ext.to_str()) == Some("json");
+        if !is_json || path.ends_with(names::INDEX_FILE) {
+            continue;
+        }
+        let name = match path.file_stem().and_then(|stem| stem.to_str()) {
+            Some(stem) => index.plant_for_file(stem).unwrap_or(stem).to_string(),
+            None => continue,
+        };
+
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                warn!("Failed to read JSON file for {}: {}", name, e);
+                continue;
+            }
+        };
+        match schema::parse_plant_json(&content) {
+            Ok(info) => plants.push((name, info)),
+            Err(e) => warn!("Failed to parse JSON for {}: {}", name, e),
+        }
+    }
+
+    plants.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(plants)
+}
+
+// Log to stderr, and as JSON lines to the log file when one is given, at the chosen level
+// and above
+fn init_logging(level: tracing::Level, log_file: Option<&str>) -> Result<()> {
+    let file_layer = match log_file {
+        Some(path) => {
+            let file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .context(format!("Failed to open log file: {}", path))?;
+            Some(fmt::layer().json().with_writer(std::sync::Mutex::new(file)))
+        }
+        None => None,
+    };
+    tracing_subscriber::registry()
+        .with(
+            fmt::layer()
+                .without_time()
+                .with_target(false)
+                .with_ansi(std::io::stderr().is_terminal())
+                .with_writer(std::io::stderr),
+        )
+        .with(file_layer)
+        .with(LevelFilter::from_level(level))
+        .init();
     Ok(())
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    init_logging(args.log_level, args.log_file.as_deref())?;
+    let proxy = match &args.proxy {
+        Some(proxy) => Some(reqwest::Proxy::all(proxy).context("Invalid --proxy")?),
+        None => None,
+    };
+    if args.delay < 0.0 || !args.delay.is_finite() {
+        anyhow::bail!("--delay must be a number of seconds, not {}", args.delay);
+    }
+    let seconds = |flag: &str, value: f64| {
+        if value <= 0.0 || !value.is_finite() {
+            anyhow::bail!("{} must be a number of seconds, not {}", flag, value);
+        }
+        Ok(StdDuration::from_secs_f64(value))
+    };
+    let _ = NETWORK.set(NetworkSettings {
+        proxy,
+        delay: StdDuration::from_secs_f64(args.delay),
+        timeout: seconds("--timeout", args.timeout)?,
+        connect_timeout: seconds("--connect-timeout", args.connect_timeout)?,
+        plant_timeout: seconds("--plant-timeout", args.plant_timeout)?,
+        wayback: args.wayback,
+    });
+    if let Some(path) = &args.profiles {
+        profiles::set_profiles(profiles::Profiles::load(path)?);
+    }
+    user_agents::set_rotation(user_agents::Rotation {
+        user_agents: args.user_agents,
+        rotate_by: args.rotate_by,
+        browser_headers: args.browser_headers,
+    });
+    columns::set_aliases(args.columns);
+    phrases::set_phrases(args.sowing_phrases);
+    if let Some(fall_frost_date) = args.fall_frost_date {
+        let _ = FALL_FROST_DATE.set(fall_frost_date);
+    }
+    locale::set_locale(args.locale);
 
     match args.command {
-        Commands::Single { url, output } => {
-            let client = create_http_client();
-            let response = client
-                .get(&url)
-                .send()
-                .context("Failed to send request")?
-                .text()
-                .context("Failed to get response text")?;
-
-            match PlantInfo::from_html(&response, url) {
-                Ok(info) => {
-                    let json = serde_json::to_string_pretty(&info)?;
-                    println!("{}", json);
-
-                    if let Some(output_path) = output {
-                        fs::write(&output_path, &json)
-                            .context(format!("Failed to write output to {}", output_path))?;
-                        println!("Results saved to: {}", output_path);
-                    }
-                }
-                Err(ScrapingError::CloudflareBlocked) => {
-                    eprintln!("Error: Access blocked by Cloudflare protection");
-                    eprintln!("Try again later or verify the URL is correct");
-                    std::process::exit(1);
-                }
-                Err(e) => {
-                    eprintln!("Error: {}", e);
-                    std::process::exit(1);
-                }
+        Commands::Single {
+            url,
+            file: Some(file),
+            output,
+            ..
+        } => {
+            if url.len() > 1 {
+                anyhow::bail!("--file is one page, so give at most one --url for it");
             }
+            let info = parse_saved_page(&file, url.first().map(String::as_str))?;
+            let json = serde_json::to_string_pretty(&info)?;
+            println!("{}", json);
+            if let Some(output_path) = output {
+                fs::write(&output_path, &json)
+                    .context(format!("Failed to write output to {}", output_path))?;
+                args.output_format
+                    .print_text(&format!("Results saved to: {}", output_path));
+            }
+        }
+        Commands::Single {
+            url,
+            url_file,
+            file: None,
+            output,
+        } => {
+            let mut urls = url;
+            if let Some(url_file) = url_file {
+                let content = fs::read_to_string(&url_file)
+                    .context(format!("Failed to read URL file: {}", url_file))?;
+                urls.extend(parse_url_list(&content));
+            }
+            if urls.is_empty() {
+                anyhow::bail!("Give at least one --url, or a --url-file listing them");
+            }
+            if !scrape_single(&urls, output.as_deref(), args.output_format)? {
+                std::process::exit(1);
+            }
+        }
+        Commands::Batch {
+            file,
+            json_dir,
+            download_images,
+            webhook_url,
+            interactive,
+            max_blocks,
+        } => {
+            let outcome = process_csv(
+                &file,
+                &json_dir,
+                download_images.as_deref(),
+                webhook_url.as_deref(),
+                interactive,
+                max_blocks,
+                args.output_format,
+            )?;
+            if outcome != BatchOutcome::Success {
+                std::process::exit(outcome.exit_code());
+            }
+        }
+        Commands::Export {
+            input_file,
+            output_file,
+            json_dir,
+            frost_date,
+            format,
+            group_by,
+            label_grid,
+            variant,
+            units,
+            bed_size,
+            row_length,
+            sowings,
+            interactive,
+            tags,
+            resistant_to,
+            sort_by,
+            export_columns,
+            preset,
+            template,
+        } => {
+            let options = ExportOptions {
+                frost_date,
+                format,
+                group_by,
+                label_grid,
+                variant,
+                units: locale::units(units),
+                tags,
+                resistant_to,
+                bed_size,
+                row_length,
+                sowings,
+                interactive,
+                sort_by,
+                columns: export_columns,
+                preset,
+                template,
+            };
+            export(
+                &input_file,
+                &output_file,
+                &json_dir,
+                &options,
+                args.output_format,
+            )?;
+        }
+        Commands::Calendar {
+            json_dir,
+            frost_date,
+            output,
+        } => {
+            calendar::export_calendar(&json_dir, frost_date, &output)?;
+        }
+        Commands::Tasks {
+            json_dir,
+            week,
+            frost_date,
+            location,
+        } => {
+            tasks::print_tasks(
+                &json_dir,
+                week.unwrap_or_else(tasks::this_week),
+                frost_date,
+                location,
+            )?;
+        }
+        Commands::SyncCalendar {
+            json_dir,
+            frost_date,
+            calendar_id,
+            client_id,
+            client_secret,
+            refresh_token,
+        } => {
+            let credentials = google_calendar::OAuthCredentials {
+                client_id,
+                client_secret,
+                refresh_token,
+            };
+            google_calendar::sync_calendar(&json_dir, frost_date, &calendar_id, &credentials)?;
+        }
+        Commands::DownloadSheet {
+            sheet,
+            tab,
+            output,
+            key_file,
+        } => {
+            google_sheets::download_sheet(&sheet, tab.as_deref(), &output, &key_file)?;
+        }
+        Commands::SyncSheet {
+            sheet,
+            input_tab,
+            output_tab,
+            json_dir,
+            frost_date,
+            units,
+            key_file,
+        } => {
+            let options = ExportOptions::for_sync(locale::units(units), frost_date);
+            google_sheets::sync_sheet(
+                &sheet,
+                input_tab.as_deref(),
+                &output_tab,
+                &json_dir,
+                &options,
+                &key_file,
+            )?;
+        }
+        Commands::SyncAirtable {
+            input_file,
+            json_dir,
+            frost_date,
+            base,
+            table,
+            token,
+            fields,
+            units,
+        } => {
+            let options = ExportOptions::for_sync(locale::units(units), frost_date);
+            let records = read_input_csv(&input_file)?;
+            airtable::sync_airtable(
+                &records, &json_dir, &options, &base, &table, &token, &fields,
+            )?;
+        }
+        Commands::Collection {
+            json_dir,
+            input_file,
+            frost_date,
+            output,
+        } => {
+            collection::export_collection(&json_dir, input_file.as_deref(), frost_date, &output)?;
+        }
+        Commands::CheckUpdates { json_dir } => {
+            updates::check_updates(&json_dir, args.output_format)?;
+        }
+        Commands::Reparse {
+            json_dir,
+            outdated_only,
+        } => {
+            updates::reparse(&json_dir, outdated_only, args.output_format)?;
+        }
+        Commands::Watch {
+            json_dir,
+            history,
+            interval_hours,
+            once,
+            notify_webhook,
+            notify_ntfy,
+            smtp_server,
+            smtp_username,
+            smtp_password,
+            email_from,
+            email_to,
+            feed,
+        } => {
+            let history = history.unwrap_or_else(|| format!("{}/price_history.jsonl", json_dir));
+
+            let mut notifiers = Vec::new();
+            if let Some(url) = notify_webhook {
+                notifiers.push(notify::Notifier::Webhook(url));
+            }
+            if let Some(url) = notify_ntfy {
+                notifiers.push(notify::Notifier::Ntfy(url));
+            }
+            if let (Some(server), Some(from), Some(to)) = (smtp_server, email_from, email_to) {
+                notifiers.push(notify::Notifier::Email(notify::SmtpSettings {
+                    server,
+                    username: smtp_username,
+                    password: smtp_password,
+                    from,
+                    to,
+                }));
+            }
+
+            watch::watch(
+                &json_dir,
+                &history,
+                interval_hours,
+                once,
+                &notifiers,
+                feed.as_deref(),
+                args.output_format,
+            )?;
+        }
+        Commands::Notify {
+            json_dir,
+            frost_date,
+            days_ahead,
+            notify_webhook,
+            notify_ntfy,
+            pushover_token,
+            pushover_user,
+        } => {
+            let mut notifiers = Vec::new();
+            if let Some(url) = notify_webhook {
+                notifiers.push(notify::Notifier::Webhook(url));
+            }
+            if let Some(url) = notify_ntfy {
+                notifiers.push(notify::Notifier::Ntfy(url));
+            }
+            if let (Some(token), Some(user)) = (pushover_token, pushover_user) {
+                notifiers.push(notify::Notifier::Pushover { token, user });
+            }
+            if notifiers.is_empty() {
+                anyhow::bail!(
+                    "Give --notify-webhook, --notify-ntfy, or --pushover-token and --pushover-user"
+                );
+            }
+            reminders::send_reminders(
+                &json_dir,
+                Local::now().date_naive(),
+                days_ahead,
+                frost_date,
+                &notifiers,
+            )?;
+        }
+        Commands::Digest {
+            json_dir,
+            week,
+            frost_date,
+            stale_days,
+            dry_run,
+            smtp_server,
+            smtp_username,
+            smtp_password,
+            email_from,
+            email_to,
+        } => {
+            let smtp = match (dry_run, smtp_server, email_from, email_to) {
+                (false, Some(server), Some(from), Some(to)) => Some(notify::SmtpSettings {
+                    server,
+                    username: smtp_username,
+                    password: smtp_password,
+                    from,
+                    to,
+                }),
+                _ => None,
+            };
+            digest::send_digest(
+                &json_dir,
+                week.unwrap_or_else(tasks::this_week),
+                frost_date,
+                stale_days,
+                smtp.as_ref(),
+            )?;
+        }
+        Commands::Diff { old_file, new_file } => {
+            diff::diff_files(&old_file, &new_file)?;
         }
-        Commands::Batch { file, json_dir } => {
-            process_csv(&file, &json_dir)?;
+        Commands::List { json_dir } => {
+            list::list_plants(&json_dir, args.output_format)?;
         }
-        Commands::Export {
-            input_file,
-            output_file,
+        Commands::Query {
+            json_dir,
+            family,
+            exposure,
+            min_maturity,
+            max_maturity,
+            tags,
+            resistant_to,
+            frost_date,
+            output,
+        } => {
+            let filter = query::PlantFilter {
+                family,
+                exposure,
+                min_maturity,
+                max_maturity,
+                tags,
+                resistant_to,
+            };
+            query::query(
+                &json_dir,
+                &filter,
+                frost_date,
+                output.as_deref(),
+                args.output_format,
+            )?;
+        }
+        Commands::Edit { plant, json_dir } => {
+            edit::edit_plant(&json_dir, &plant, args.output_format)?;
+        }
+        Commands::Show {
+            plant,
+            json_dir,
+            frost_date,
+        } => {
+            show::show_plant(&json_dir, &plant, frost_date)?;
+        }
+        Commands::Tui {
+            json_dir,
+            frost_date,
+        } => {
+            tui::browse(&json_dir, frost_date)?;
+        }
+        Commands::Stats { json_dir } => {
+            stats::print_stats(&json_dir, args.output_format)?;
+        }
+        Commands::Companions { plant, json_dir } => {
+            companions::print_companions(&json_dir, plant.as_deref())?;
+        }
+        Commands::Rotate {
+            json_dir,
+            beds,
+            year,
+            years,
+        } => {
+            rotation::rotate(&json_dir, &beds, year, years)?;
+        }
+        Commands::Serve {
+            json_dir,
+            frost_date,
+            host,
+            port,
+            history,
+        } => {
+            let history = history.unwrap_or_else(|| format!("{}/price_history.jsonl", json_dir));
+            server::serve(&json_dir, frost_date, &host, port, &history)?;
+        }
+        Commands::Mcp {
+            json_dir,
+            frost_date,
+        } => {
+            mcp::serve_mcp(&json_dir, frost_date)?;
+        }
+        Commands::Record {
+            url,
+            fixture_dir,
+            name,
+            raw,
+        } => {
+            fixtures::record_fixture(&url, &fixture_dir, name.as_deref(), raw)?;
+        }
+        Commands::TestParse { dir } => {
+            if fixtures::test_parse(&dir)? > 0 {
+                std::process::exit(1);
+            }
+        }
+        Commands::Validate {
             json_dir,
+            input_file,
         } => {
-            export_to_csv(&input_file, &output_file, &json_dir)?;
+            if validate::validate(&json_dir, input_file.as_deref(), args.output_format)? > 0 {
+                std::process::exit(1);
+            }
+        }
+        Commands::Migrate { json_dir, db } => {
+            database::migrate_json_dir(&json_dir, &db)?;
         }
+        Commands::Bundle { action } => match action {
+            BundleCommands::Create {
+                json_dir,
+                input_file,
+                output,
+            } => bundle::create_bundle(&json_dir, input_file.as_deref(), &output)?,
+            BundleCommands::Import {
+                bundle,
+                json_dir,
+                input_file,
+                overwrite,
+            } => bundle::import_bundle(&bundle, &json_dir, input_file.as_deref(), overwrite)?,
+        },
+        Commands::Inventory { action } => match action {
+            InventoryCommands::Set {
+                plant,
+                json_dir,
+                quantity,
+                packets,
+                lot,
+                location,
+            } => {
+                let mut inventory = inventory::Inventory::load(&json_dir)?;
+                let stock = inventory.update(
+                    &plant,
+                    inventory::Stock {
+                        quantity,
+                        packets,
+                        lot,
+                        location,
+                    },
+                );
+                args.output_format.print_text(&format!(
+                    "{}: {} seeds on hand",
+                    plant,
+                    format_number(stock.quantity)
+                ));
+                args.output_format
+                    .print_json(&serde_json::json!({"plant": plant, "stock": stock}))?;
+                inventory.save()?;
+            }
+            InventoryCommands::List { json_dir } => {
+                inventory::list_inventory(&json_dir, args.output_format)?;
+            }
+            InventoryCommands::Remove { plant, json_dir } => {
+                let mut inventory = inventory::Inventory::load(&json_dir)?;
+                let stock = inventory.remove(&plant)?;
+                inventory.save()?;
+                args.output_format
+                    .print_text(&format!("Removed {} from the inventory", plant));
+                args.output_format
+                    .print_json(&serde_json::json!({"plant": plant, "stock": stock}))?;
+            }
+        },
+        Commands::Log { action } => {
+            let (kind, entry) = match action {
+                LogCommands::Sow(entry) => (planting_log::EventKind::Sow, entry),
+                LogCommands::Transplant(entry) => (planting_log::EventKind::Transplant, entry),
+                LogCommands::Germination(entry) => (planting_log::EventKind::Germination, entry),
+            };
+            let event = planting_log::Event {
+                date: entry.date.unwrap_or_else(|| Local::now().date_naive()),
+                plant: entry.plant,
+                kind,
+                notes: entry.notes,
+            };
+            planting_log::append(&entry.json_dir, &event)?;
+            args.output_format
+                .print_text(&format!("Logged {} on {}", event.plant, event.date));
+            args.output_format
+                .print_json(&serde_json::to_value(&event)?)?;
+        }
+        Commands::Germination { action } => match action {
+            GerminationCommands::Record {
+                plant,
+                json_dir,
+                tested,
+                sprouted,
+                lot,
+                date,
+            } => {
+                let test = germination::GerminationTest {
+                    date: date.unwrap_or_else(|| Local::now().date_naive()),
+                    lot,
+                    tested,
+                    sprouted,
+                };
+                let rate = test.rate();
+                let mut log = germination::GerminationLog::load(&json_dir)?;
+                log.record(&plant, test)?;
+                log.save()?;
+                args.output_format.print_text(&format!(
+                    "Recorded a {:.0}% germination rate for {}",
+                    rate, plant
+                ));
+                args.output_format
+                    .print_json(&serde_json::json!({"plant": plant, "rate": rate}))?;
+            }
+            GerminationCommands::List { json_dir } => {
+                germination::list_tests(&json_dir, args.output_format)?;
+            }
+        },
     }
 
     Ok(())
@@ -933,6 +4484,8 @@ mod tests {
             Some("Growers in Danvers, Massachusetts during the late-19th century were searching for a carrot with improved color, yield, and uniformity. After many variations, the 'Danvers 126' carrot was born! It grows particularly well interplanted with onions and in heavy soils due to its high fiber content. Heat-tolerant with high yields, it also has a noticeably sweeter flavor and stores exceptionally well if cleaned after harvest. Resistant to cracks and splits.")
         );
         assert_eq!(info.days_to_maturity.as_deref(), Some("65 days"));
+        assert_eq!(info.days_to_maturity_min, Some(65));
+        assert_eq!(info.days_to_maturity_max, Some(65));
         assert_eq!(info.family.as_deref(), Some("Apiaceae"));
         assert_eq!(info.plant_type.as_deref(), Some("Danvers Type"));
         assert_eq!(info.native.as_deref(), Some("Africa, Eurasia"));
@@ -950,6 +4503,13 @@ mod tests {
             info.attributes.as_deref(),
             Some("Crack Resistant, Frost Tolerant")
         );
+        assert_eq!(info.tags, vec!["Crack Resistant", "Frost Tolerant"]);
+        assert_eq!(info.organic, Some(true));
+        assert_eq!(info.heirloom, Some(true));
+        assert_eq!(info.open_pollinated, Some(true));
+        assert_eq!(info.hybrid, Some(false));
+        assert!(info.has_tags(&["frost tolerant".to_string()]));
+        assert!(!info.has_tags(&["Heat Tolerant".to_string()]));
         assert_eq!(
             info.when_to_sow_outside.as_deref(),
             Some("RECOMMENDED. 2 to 4 weeks before your average last frost date, and when soil temperature is at least 45°F, ideally 60°-85°F. Successive Sowings: Every 3 weeks until 10 to 12 weeks before your average first fall frost date. In very warm climates, carrots are grown primarily in fall, winter, and spring.")
@@ -966,15 +4526,108 @@ mod tests {
             info.thinning.as_deref(),
             Some("When 1\" tall, thin to 1 every 3\"")
         );
+        assert!(info
+            .harvesting
+            .as_deref()
+            .is_some_and(|text| text.starts_with("For best flavor and texture, harvest carrots")));
+        assert_eq!(info.special_care, None);
+        assert_eq!(info.seed_depth_min_in, Some(0.25));
+        assert_eq!(info.seed_spacing_max_in, Some(1.0));
+        assert_eq!(info.row_spacing_min_in, Some(6.0));
+        assert_eq!(info.plant_spacing_in, Some(3.0));
+        assert_eq!(info.min_soil_temp_f, Some(45));
+        assert_eq!(info.ideal_soil_temp_min_f, Some(60));
+        assert_eq!(info.ideal_soil_temp_max_f, Some(85));
         assert_eq!(info.rating, Some(4.5));
         assert_eq!(info.votes, Some(32));
+        assert_eq!(
+            info.images,
+            vec!["https://www.botanicalinterests.com/cdn/shop/files/7614i_L-Carrot-Danvers-126-ORG.jpg?v=1730351447&width=1920"]
+        );
+        // The large packet is the only variant in stock
+        assert_eq!(info.price, Some(5.99));
+        assert_eq!(info.compare_at_price, None);
+        assert_eq!(info.in_stock, Some(true));
+        assert_eq!(
+            info.variants,
+            vec![
+                Variant {
+                    size: "Organic Heirloom Large Packet".to_string(),
+                    sku: Some("7614".to_string()),
+                    price: 5.99,
+                    compare_at_price: None,
+                    available: true,
+                },
+                Variant {
+                    size: "Organic Heirloom".to_string(),
+                    sku: Some("3016".to_string()),
+                    price: 3.49,
+                    compare_at_price: None,
+                    available: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_botanical_name() {
+        let html = r#"
+        <div class="product__info-container">
+            <h1>Danvers 126 Carrot Seeds</h1>
+            <p><em>Successive Sowings:</em> Every 3 weeks</p>
+            <p class="product__text"><em>Daucus carota</em> var. sativus</p>
+        </div>
+        "#;
+        let info = PlantInfo::from_html(html, "http://example.com".to_string()).unwrap();
+        assert_eq!(info.botanical_name.as_deref(), Some("Daucus carota"));
+
+        let html =
+            r#"<div class="tab-content"><p><b>Latin Name:</b> Solanum lycopersicum</p></div>"#;
+        let info = PlantInfo::from_html(html, "http://example.com".to_string()).unwrap();
+        assert_eq!(info.botanical_name.as_deref(), Some("Solanum lycopersicum"));
+    }
+
+    #[test]
+    fn test_choose_variant() {
+        let html = include_str!("../tests/fixtures/seed.html");
+        let mut info = PlantInfo::from_html(html, "http://example.com".to_string()).unwrap();
+
+        info.choose_variant(VariantChoice::Selected);
+        assert_eq!(info.price, Some(5.99));
+
+        info.choose_variant(VariantChoice::Smallest);
+        assert_eq!(info.price, Some(3.49));
+        assert_eq!(info.in_stock, Some(false));
+    }
+
+    #[test]
+    fn test_convert_units() {
+        let mut info = PlantInfo {
+            seed_depth_min_in: Some(0.25),
+            row_spacing_max_in: Some(18.0),
+            ideal_soil_temp_max_f: Some(85),
+            seed_depth: Some("¼\"".to_string()),
+            ..Default::default()
+        };
+        info.convert_units(Units::Metric);
+        assert_eq!(info.seed_depth_min_in, Some(0.6));
+        assert_eq!(info.row_spacing_max_in, Some(45.7));
+        assert_eq!(info.ideal_soil_temp_max_f, Some(29));
+        assert_eq!(info.seed_depth.as_deref(), Some("¼\""));
+
+        assert_eq!(
+            Units::Metric.header("Min Soil Temp (F)"),
+            "Min Soil Temp (°C)"
+        );
+        assert_eq!(Units::Metric.field_name("height_max_in"), "height_max_cm");
+        assert_eq!(Units::Imperial.field_name("height_max_in"), "height_max_in");
     }
 
     #[test]
     fn test_extract_weeks_pattern() {
         // Test before last frost
         let text = "2 to 4 weeks before your average last frost date";
-        let result = extract_weeks_pattern(text).unwrap();
+        let result = extract_weeks_pattern(text)[0];
         assert_eq!(result.weeks_min, 2);
         assert_eq!(result.weeks_max, 4);
         assert!(matches!(result.relative_timing, RelativeTiming::Before));
@@ -982,7 +4635,7 @@ mod tests {
 
         // Test after last frost
         let text = "1 to 2 weeks after your average last frost date";
-        let result = extract_weeks_pattern(text).unwrap();
+        let result = extract_weeks_pattern(text)[0];
         assert_eq!(result.weeks_min, 1);
         assert_eq!(result.weeks_max, 2);
         assert!(matches!(result.relative_timing, RelativeTiming::After));
@@ -990,20 +4643,43 @@ mod tests {
 
         // Test before transplanting
         let text = "6 to 8 weeks before transplanting";
-        let result = extract_weeks_pattern(text).unwrap();
+        let result = extract_weeks_pattern(text)[0];
         assert_eq!(result.weeks_min, 6);
         assert_eq!(result.weeks_max, 8);
         assert!(matches!(result.relative_timing, RelativeTiming::Before));
         assert!(matches!(result.timing_type, TimingType::Transplant));
 
+        // Test a single week count
+        let text = "2 weeks before your average last frost date";
+        let result = extract_weeks_pattern(text)[0];
+        assert_eq!((result.weeks_min, result.weeks_max), (2, 2));
+        assert!(matches!(result.relative_timing, RelativeTiming::Before));
+
+        // Test no offset at all
+        let text = "After your average last frost date";
+        let result = extract_weeks_pattern(text)[0];
+        assert_eq!((result.weeks_min, result.weeks_max), (0, 0));
+        assert!(matches!(result.relative_timing, RelativeTiming::After));
+        assert!(matches!(result.timing_type, TimingType::LastFrost));
+
+        // Test timing from the fall
+        let text = "4 to 6 weeks before your average first fall frost date";
+        let result = extract_weeks_pattern(text)[0];
+        assert!(matches!(result.timing_type, TimingType::FallFrost));
+        let text = "Sow in late fall for an early spring crop";
+        let result = extract_weeks_pattern(text)[0];
+        assert!(matches!(result.timing_type, TimingType::Overwinter));
+
         // Test invalid format
         let text = "plant whenever you feel like it";
-        assert!(extract_weeks_pattern(text).is_none());
+        assert!(extract_weeks_pattern(text).is_empty());
     }
 
     #[test]
     fn test_calculate_start_date() {
         let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        let fall_frost_date = first_fall_frost(frost_date, None);
+        let transplant_date = frost_date + Days::new(21); // 3 weeks after frost date
 
         // Test before last frost
         let sowing_time = SowingTime {
@@ -1012,7 +4688,8 @@ mod tests {
             relative_timing: RelativeTiming::Before,
             timing_type: TimingType::LastFrost,
         };
-        let result = calculate_start_date(&sowing_time, frost_date);
+        let result =
+            calculate_start_date(&sowing_time, frost_date, fall_frost_date, transplant_date);
         assert_eq!(result, NaiveDate::from_ymd_opt(2025, 4, 26).unwrap()); // 2 weeks before May 10
 
         // Test after last frost
@@ -1022,7 +4699,8 @@ mod tests {
             relative_timing: RelativeTiming::After,
             timing_type: TimingType::LastFrost,
         };
-        let result = calculate_start_date(&sowing_time, frost_date);
+        let result =
+            calculate_start_date(&sowing_time, frost_date, fall_frost_date, transplant_date);
         assert_eq!(result, NaiveDate::from_ymd_opt(2025, 5, 17).unwrap()); // 1 week after May 10
 
         // Test before transplant
@@ -1032,8 +4710,8 @@ mod tests {
             relative_timing: RelativeTiming::Before,
             timing_type: TimingType::Transplant,
         };
-        let result = calculate_start_date(&sowing_time, frost_date);
-        let transplant_date = frost_date + Days::new(21); // 3 weeks after frost date
+        let result =
+            calculate_start_date(&sowing_time, frost_date, fall_frost_date, transplant_date);
         assert_eq!(result, transplant_date - Days::new(42)); // 6 weeks before transplant
 
         // Test after transplant
@@ -1043,11 +4721,301 @@ mod tests {
             relative_timing: RelativeTiming::After,
             timing_type: TimingType::Transplant,
         };
-        let result = calculate_start_date(&sowing_time, frost_date);
-        let transplant_date = frost_date + Days::new(21); // 3 weeks after frost date
+        let result =
+            calculate_start_date(&sowing_time, frost_date, fall_frost_date, transplant_date);
         assert_eq!(result, transplant_date + Days::new(7)); // 1 week after transplant
     }
 
+    #[test]
+    fn test_calculate_start_window() {
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        let fall_frost_date = first_fall_frost(frost_date, None);
+        let transplant_date = frost_date + Days::new(21);
+
+        // Before the frost date the window runs from weeks_max to weeks_min
+        let sowing_time = SowingTime {
+            weeks_min: 2,
+            weeks_max: 4,
+            relative_timing: RelativeTiming::Before,
+            timing_type: TimingType::LastFrost,
+        };
+        let (earliest, latest) =
+            calculate_start_window(&sowing_time, frost_date, fall_frost_date, transplant_date);
+        assert_eq!(earliest, NaiveDate::from_ymd_opt(2025, 4, 12).unwrap());
+        assert_eq!(latest, NaiveDate::from_ymd_opt(2025, 4, 26).unwrap());
+
+        // After the frost date the window runs from weeks_min to weeks_max
+        let sowing_time = SowingTime {
+            weeks_min: 1,
+            weeks_max: 2,
+            relative_timing: RelativeTiming::After,
+            timing_type: TimingType::LastFrost,
+        };
+        let (earliest, latest) =
+            calculate_start_window(&sowing_time, frost_date, fall_frost_date, transplant_date);
+        assert_eq!(earliest, NaiveDate::from_ymd_opt(2025, 5, 17).unwrap());
+        assert_eq!(latest, NaiveDate::from_ymd_opt(2025, 5, 24).unwrap());
+
+        // Late fall sowing runs from the first fall frost until the ground freezes
+        let sowing_time = extract_weeks_pattern("Sow in late fall")[0];
+        let (earliest, latest) =
+            calculate_start_window(&sowing_time, frost_date, fall_frost_date, transplant_date);
+        assert_eq!(earliest, NaiveDate::from_ymd_opt(2025, 10, 10).unwrap());
+        assert_eq!(latest, NaiveDate::from_ymd_opt(2025, 11, 7).unwrap());
+
+        // A configured fall frost date moves the fall-anchored windows with it
+        let fall_frost_date = first_fall_frost(frost_date, NaiveDate::from_ymd_opt(2020, 9, 20));
+        let (earliest, latest) =
+            calculate_start_window(&sowing_time, frost_date, fall_frost_date, transplant_date);
+        assert_eq!(earliest, NaiveDate::from_ymd_opt(2025, 9, 20).unwrap());
+        assert_eq!(latest, NaiveDate::from_ymd_opt(2025, 10, 18).unwrap());
+    }
+
+    #[test]
+    fn test_first_fall_frost() {
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        assert_eq!(
+            first_fall_frost(frost_date, None),
+            NaiveDate::from_ymd_opt(2025, 10, 10).unwrap()
+        );
+        assert_eq!(
+            first_fall_frost(frost_date, NaiveDate::from_ymd_opt(2024, 10, 1)),
+            NaiveDate::from_ymd_opt(2025, 10, 1).unwrap()
+        );
+
+        // A September last frost has its fall frost the following April
+        let frost_date = NaiveDate::from_ymd_opt(2025, 9, 15).unwrap();
+        assert_eq!(
+            first_fall_frost(frost_date, NaiveDate::from_ymd_opt(2025, 4, 20)),
+            NaiveDate::from_ymd_opt(2026, 4, 20).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_alternate_sowing_windows() {
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        let info = PlantInfo {
+            url: "test".to_string(),
+            when_to_sow_outside: Some(
+                "Spring: 2 to 4 weeks before your average last frost date. Fall: 10 to 12 weeks before your average first fall frost date.".to_string(),
+            ),
+            ..Default::default()
+        };
+        let sowing_times = get_when_to_seed_start(&info, None);
+        assert_eq!(sowing_times.len(), 2);
+        assert!(matches!(sowing_times[0].timing_type, TimingType::LastFrost));
+
+        let dates = calculate_dates(&info, None, &sowing_times, frost_date);
+        assert_eq!(dates.earliest_start, NaiveDate::from_ymd_opt(2025, 4, 12));
+        assert_eq!(
+            format_windows(&dates.alternate_windows),
+            "2025-07-18 to 2025-08-01"
+        );
+    }
+
+    #[test]
+    fn test_detect_seed_designations() {
+        let texts = ["Sungold F1 Tomato Seeds".to_string(), "Organic".to_string()];
+        assert_eq!(
+            SeedDesignations::detect(&texts),
+            SeedDesignations {
+                organic: true,
+                hybrid: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            SeedDesignations::detect(&["Heirloom Open-Pollinated".to_string()]),
+            SeedDesignations {
+                heirloom: true,
+                open_pollinated: true,
+                ..Default::default()
+            }
+        );
+        let badges = ["Heirloom, Non-Hybrid".to_string(), "Non GMO".to_string()];
+        assert_eq!(
+            SeedDesignations::detect(&badges),
+            SeedDesignations {
+                heirloom: true,
+                open_pollinated: true,
+                ..Default::default()
+            }
+        );
+        assert!(SeedDesignations::detect(&["Non-hybrid or F1 Hybrid".to_string()]).hybrid);
+    }
+
+    #[test]
+    fn test_parse_soil_temperature() {
+        assert_eq!(
+            parse_soil_temperature(
+                "2 to 4 weeks before your average last frost date, and when soil temperature is at least 45°F, ideally 60°-85°F."
+            ),
+            SoilTemperature {
+                min: Some(45),
+                ideal: Some((60, 85))
+            }
+        );
+        assert_eq!(
+            parse_soil_temperature("when soil temperature is ideally 70°F"),
+            SoilTemperature {
+                min: None,
+                ideal: Some((70, 70))
+            }
+        );
+        assert_eq!(
+            parse_soil_temperature("After the last frost"),
+            SoilTemperature::default()
+        );
+    }
+
+    #[test]
+    fn test_parse_days_range() {
+        assert_eq!(parse_days_range("65 days"), Some((65, 65)));
+        assert_eq!(parse_days_range("55-70 days"), Some((55, 70)));
+        assert_eq!(parse_days_range("10 - 25 days"), Some((10, 25)));
+        assert_eq!(parse_days_range("varies"), None);
+    }
+
+    #[test]
+    fn test_calculate_dates_harvest() {
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        let mut info = PlantInfo {
+            url: "test".to_string(),
+            title: None,
+            description: None,
+            days_to_maturity: Some("55-70 days".to_string()),
+            family: None,
+            plant_type: None,
+            native: None,
+            hardiness: None,
+            exposure: None,
+            plant_dimensions: None,
+            variety_info: None,
+            attributes: None,
+            when_to_sow_outside: Some(
+                "2 to 4 weeks before your average last frost date".to_string(),
+            ),
+            when_to_start_inside: None,
+            days_to_emerge: None,
+            seed_depth: None,
+            seed_spacing: None,
+            row_spacing: None,
+            thinning: None,
+            rating: None,
+            votes: None,
+            ..Default::default()
+        };
+
+        // Direct sowing counts maturity from the start date
+        let sowing_time = get_when_to_seed_start(&info, None);
+        let dates = calculate_dates(
+            &info,
+            Some(SowingStrategy::Outside),
+            &sowing_time,
+            frost_date,
+        );
+        assert_eq!(dates.start, NaiveDate::from_ymd_opt(2025, 4, 26));
+        assert_eq!(dates.first_harvest, NaiveDate::from_ymd_opt(2025, 6, 20));
+        assert_eq!(dates.last_harvest, NaiveDate::from_ymd_opt(2025, 7, 5));
+
+        // Indoor starts count maturity from the transplant date
+        info.when_to_sow_outside = None;
+        info.when_to_start_inside = Some("6 to 8 weeks before transplanting".to_string());
+        let sowing_time = get_when_to_seed_start(&info, None);
+        let dates = calculate_dates(
+            &info,
+            Some(SowingStrategy::Inside),
+            &sowing_time,
+            frost_date,
+        );
+        let transplant_date = frost_date + Days::new(21);
+        assert_eq!(dates.transplant, Some(transplant_date));
+        assert_eq!(dates.first_harvest, Some(transplant_date + Days::new(55)));
+        assert_eq!(dates.last_harvest, Some(transplant_date + Days::new(70)));
+    }
+
+    #[test]
+    fn test_extract_transplant_offset() {
+        let text = "RECOMMENDED. 6 to 8 weeks before your average last frost date. Transplant 1 to 2 weeks after your average last frost date.";
+        assert_eq!(extract_transplant_offset(text), Some(7));
+
+        let text = "4 weeks before transplanting outside after your average last frost date";
+        assert_eq!(extract_transplant_offset(text), Some(0));
+
+        // Sowing guidance relative to transplanting is not transplant guidance
+        let text = "6 to 8 weeks before transplanting";
+        assert_eq!(extract_transplant_offset(text), None);
+
+        // A number too big to be weeks is ignored rather than panicking
+        let text = "Transplant 99999999999999999999 weeks after your average last frost date.";
+        assert_eq!(extract_transplant_offset(text), None);
+        let text = "Transplant 9000000000000000 weeks after your average last frost date.";
+        let info = PlantInfo {
+            when_to_start_inside: Some(text.to_string()),
+            ..Default::default()
+        };
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        assert_eq!(
+            calculate_transplant_date(&info, frost_date),
+            frost_date + Days::new(21)
+        );
+    }
+
+    #[test]
+    fn test_discontinued_pages() {
+        let url = "https://www.botanicalinterests.com/products/carrot";
+        let page = |status, final_url: &str, body: &str| FetchedPage {
+            status,
+            final_url: Some(final_url.to_string()),
+            etag: None,
+            last_modified: None,
+            body: body.to_string(),
+        };
+
+        let gone = page(404, url, "<h1>Page not found</h1>");
+        assert_eq!(discontinued_reason(&gone, url).as_deref(), Some("HTTP 404"));
+        let redirected = page(
+            200,
+            "https://www.botanicalinterests.com/search?q=carrot",
+            "",
+        );
+        assert!(discontinued_reason(&redirected, url).is_some());
+        let notice = page(200, url, "<p>Sorry, this product is unavailable.</p>");
+        let info = PlantInfo::from_page(&notice, url.to_string()).unwrap();
+        assert_eq!(info.discontinued, Some(true));
+        assert_eq!(info.title, None);
+
+        let listed = page(200, url, "<h1>Carrot</h1>");
+        assert_eq!(discontinued_reason(&listed, url), None);
+    }
+
+    #[test]
+    fn test_record_scrape() {
+        let mut info = PlantInfo {
+            url: "https://www.botanicalinterests.com/products/carrot".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(info.scrape_age_days(Utc::now()), None);
+
+        info.record_scrape(&FetchedPage {
+            status: 200,
+            final_url: None,
+            etag: Some("\"abc\"".to_string()),
+            last_modified: None,
+            body: String::new(),
+        });
+        assert_eq!(
+            info.source_domain.as_deref(),
+            Some("www.botanicalinterests.com")
+        );
+        assert_eq!(info.http_status, Some(200));
+        assert_eq!(info.parser_version, Some(PARSER_VERSION));
+        assert_eq!(info.etag.as_deref(), Some("\"abc\""));
+        assert!(info.content_hash.is_some());
+        let later = info.scraped_at.unwrap() + Duration::days(3);
+        assert_eq!(info.scrape_age_days(later), Some(3));
+    }
+
     #[test]
     fn test_get_when_to_seed_start() {
         let info = PlantInfo {
@@ -1074,17 +5042,18 @@ mod tests {
             thinning: None,
             rating: None,
             votes: None,
+            ..Default::default()
         };
 
         // Test with no user strategy - should use outside (default)
-        let result = get_when_to_seed_start(&info, None).unwrap();
+        let result = get_when_to_seed_start(&info, None)[0];
         assert_eq!(result.weeks_min, 2);
         assert_eq!(result.weeks_max, 4);
         assert!(matches!(result.relative_timing, RelativeTiming::Before));
         assert!(matches!(result.timing_type, TimingType::LastFrost));
 
         // Test with Inside strategy - should use inside timing
-        let result = get_when_to_seed_start(&info, Some(SowingStrategy::Inside)).unwrap();
+        let result = get_when_to_seed_start(&info, Some(SowingStrategy::Inside))[0];
         assert_eq!(result.weeks_min, 6);
         assert_eq!(result.weeks_max, 8);
         assert!(matches!(result.relative_timing, RelativeTiming::Before));
@@ -1117,6 +5086,7 @@ mod tests {
             thinning: None,
             rating: None,
             votes: None,
+            ..Default::default()
         };
 
         // Test with outside recommended
@@ -1156,6 +5126,37 @@ mod tests {
         assert_eq!(input.notes, "Test notes");
         assert_eq!(input.user_strategy_str, "Inside");
         assert_eq!(input.user_strategy, Some(SowingStrategy::Inside));
+        assert_eq!(input.frost_date, None);
+    }
+
+    #[test]
+    fn test_input_dates_override_computed_dates() {
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        let info = || PlantInfo {
+            url: "test".to_string(),
+            days_to_maturity: Some("240 days".to_string()),
+            when_to_sow_outside: Some(
+                "2 to 4 weeks before your average last frost date".to_string(),
+            ),
+            ..Default::default()
+        };
+
+        let record = csv::StringRecord::from(vec!["Garlic", "", "", "", "", "", "2025-05-24", ""]);
+        let input = InputRecord::from_csv_record(&record);
+        let plant = PlantExport::for_input(info(), &input, None, frost_date);
+        assert_eq!(plant.dates.start, NaiveDate::from_ymd_opt(2025, 5, 10));
+
+        let record = csv::StringRecord::from(vec!["Garlic", "", "", "", "", "", "", "2025-10-15"]);
+        let input = InputRecord::from_csv_record(&record);
+        let plant = PlantExport::for_input(info(), &input, None, frost_date);
+        assert_eq!(
+            plant.dates.earliest_start,
+            NaiveDate::from_ymd_opt(2025, 10, 15)
+        );
+        assert_eq!(
+            plant.dates.first_harvest,
+            NaiveDate::from_ymd_opt(2026, 6, 12)
+        );
     }
 
     #[test]
@@ -1176,6 +5177,37 @@ mod tests {
         assert_eq!(input.user_strategy, None);
     }
 
+    #[test]
+    fn test_saved_page_url() {
+        let html = include_str!("../tests/fixtures/seed.html");
+        assert_eq!(
+            saved_page_url(&Html::parse_document(html)).as_deref(),
+            Some("https://www.botanicalinterests.com/products/danvers-126-carrot-seeds")
+        );
+        assert_eq!(
+            saved_page_url(&Html::parse_document("<p>No links</p>")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_url_list() {
+        assert_eq!(
+            parse_url_list("https://a.example/carrot\n\n# beans\n  https://a.example/bean  \n"),
+            vec!["https://a.example/carrot", "https://a.example/bean"]
+        );
+    }
+
+    #[test]
+    fn test_batch_outcome() {
+        assert_eq!(BatchOutcome::from_counts(3, 0, false).exit_code(), 0);
+        assert_eq!(BatchOutcome::from_counts(0, 0, false).exit_code(), 0);
+        assert_eq!(BatchOutcome::from_counts(2, 1, false).exit_code(), 2);
+        assert_eq!(BatchOutcome::from_counts(0, 3, false).exit_code(), 3);
+        assert_eq!(BatchOutcome::from_counts(2, 1, true).exit_code(), 4);
+        assert_eq!(BatchOutcome::Halted.exit_code(), 5);
+    }
+
     #[test]
     fn test_create_error_record() {
         // Create a mock input record
@@ -1207,6 +5239,62 @@ mod tests {
         assert_eq!(error_record[CSV_FIELD_COUNT - 1], "ERR");
     }
 
+    #[test]
+    fn test_sort_rows() {
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        let records: Vec<csv::StringRecord> = ["bean", "Carrot", "Arugula", "Missing"]
+            .into_iter()
+            .map(|name| csv::StringRecord::from(vec![name]))
+            .collect();
+        let mut rows: Vec<ExportRow> = records
+            .iter()
+            .map(|record| {
+                let input = InputRecord::from_csv_record(record);
+                let maturity = match input.plant_name {
+                    "bean" => Some("50 days"),
+                    "Carrot" => Some("70 days"),
+                    _ => None,
+                };
+                let plant = (input.plant_name != "Missing").then(|| {
+                    let info = PlantInfo {
+                        days_to_maturity: maturity.map(String::from),
+                        ..Default::default()
+                    };
+                    PlantExport::new(info, None, frost_date)
+                });
+                ExportRow { input, plant }
+            })
+            .collect();
+        let names = |rows: &[ExportRow]| -> Vec<String> {
+            rows.iter()
+                .map(|row| row.input.plant_name.to_string())
+                .collect()
+        };
+
+        sort_rows(&mut rows, SortKey::Name);
+        assert_eq!(names(&rows), ["Arugula", "bean", "Carrot", "Missing"]);
+        sort_rows(&mut rows, SortKey::Maturity);
+        assert_eq!(names(&rows), ["bean", "Carrot", "Arugula", "Missing"]);
+    }
+
+    #[test]
+    fn test_parse_export_columns() {
+        let planner = parse_export_columns("planner").unwrap();
+        let record: Vec<String> = CSV_HEADERS.iter().map(|h| h.to_string()).collect();
+        assert_eq!(
+            planner.pick(record.clone()),
+            [
+                "Plant Name",
+                "Sowing Strategy",
+                "Calculated Start Date",
+                "Plant Spacing (in)"
+            ]
+        );
+        let chosen = parse_export_columns("family, plant spacing (cm)").unwrap();
+        assert_eq!(chosen.pick(record), ["Family", "Plant Spacing (in)"]);
+        assert!(parse_export_columns("Plant Name,Cost").is_err());
+    }
+
     #[test]
     fn test_output_record_creation() {
         // Create a mock input record
@@ -1246,6 +5334,7 @@ mod tests {
             thinning: None,
             rating: Some(4.5),
             votes: Some(10),
+            ..Default::default()
         };
 
         // Create OutputRecord
@@ -1254,7 +5343,10 @@ mod tests {
             &info,
             Some(SowingStrategy::Inside),
             "6-8 before TRANSPLANT".to_string(),
-            "2025-03-15".to_string(),
+            &CalculatedDates {
+                start: NaiveDate::from_ymd_opt(2025, 3, 15),
+                ..Default::default()
+            },
         );
 
         // Verify input fields are copied correctly
@@ -1277,6 +5369,7 @@ mod tests {
         assert_eq!(output.sowing_strategy, "Inside");
         assert_eq!(output.when_to_seed_start, "6-8 before TRANSPLANT");
         assert_eq!(output.calculated_start_date, "2025-03-15");
+        assert_eq!(output.estimated_first_harvest, "NULL");
 
         // Verify converted to record
         let record_vec = output.to_record();
@@ -0,0 +1,250 @@
+// Model Context Protocol server over stdio, so assistants can call the scraper as tools
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+use tracing::info;
+
+use crate::graphql::Sowing;
+use crate::query::PlantFilter;
+use crate::{create_http_client, fetch_page, load_json_dir, page_request, show, PlantInfo};
+
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "scrape_url",
+            "description": "Scrape a seed product page and return its growing information. Nothing is saved to the collection.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "url": {"type": "string", "description": "Product page URL"}
+                },
+                "required": ["url"]
+            }
+        },
+        {
+            "name": "search_collection",
+            "description": "Find plants in the seed collection. Every given criterion must match.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "text": {"type": "string", "description": "Text in the plant's name, title, or description"},
+                    "family": {"type": "string", "description": "Botanical family, e.g. Solanaceae"},
+                    "exposure": {"type": "string", "description": "Sun exposure, e.g. Full Sun"},
+                    "min_maturity": {"type": "integer", "description": "Fewest days to maturity"},
+                    "max_maturity": {"type": "integer", "description": "Most days to maturity"},
+                    "tags": {"type": "array", "items": {"type": "string"}, "description": "Attribute tags the plant must have"},
+                    "resistant_to": {"type": "array", "items": {"type": "string"}, "description": "Diseases the plant must resist, by code or name"}
+                }
+            }
+        },
+        {
+            "name": "compute_sowing_date",
+            "description": "Calculate when to start, transplant, and harvest a plant in the collection.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "plant": {"type": "string", "description": "Plant name, as in its JSON file name"},
+                    "frost_date": {"type": "string", "description": "Average last frost date, YYYY-MM-DD"}
+                },
+                "required": ["plant"]
+            }
+        }
+    ])
+}
+
+fn string_list(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| item.as_str().map(String::from))
+        .collect()
+}
+
+fn scrape_url(args: &Value) -> Result<Value> {
+    let url = args["url"]
+        .as_str()
+        .ok_or_else(|| anyhow!("url is required"))?;
+    let client = create_http_client();
+    let page = fetch_page(page_request(&client, url))?;
+    let mut info = PlantInfo::from_page(&page, url.to_string())?;
+    info.fetch_related(&client, &page.body);
+    info.record_scrape(&page);
+    Ok(serde_json::to_value(info)?)
+}
+
+fn search_collection(json_dir: &str, args: &Value) -> Result<Value> {
+    let text = args["text"].as_str().map(str::to_lowercase);
+    let filter = PlantFilter {
+        family: args["family"].as_str().map(String::from),
+        exposure: args["exposure"].as_str().map(String::from),
+        min_maturity: args["min_maturity"].as_u64().map(|days| days as u32),
+        max_maturity: args["max_maturity"].as_u64().map(|days| days as u32),
+        tags: string_list(&args["tags"]),
+        resistant_to: string_list(&args["resistant_to"]),
+    };
+
+    let plants: Vec<Value> = load_json_dir(json_dir)?
+        .into_iter()
+        .filter(|(name, info)| {
+            let text_matches = text.as_ref().is_none_or(|text| {
+                [Some(name), info.title.as_ref(), info.description.as_ref()]
+                    .into_iter()
+                    .flatten()
+                    .any(|field| field.to_lowercase().contains(text))
+            });
+            text_matches && filter.matches(info)
+        })
+        .map(|(name, info)| {
+            json!({
+                "name": name,
+                "title": info.title,
+                "family": info.family,
+                "days_to_maturity": info.days_to_maturity,
+                "exposure": info.exposure,
+                "url": info.url,
+            })
+        })
+        .collect();
+    Ok(json!(plants))
+}
+
+fn compute_sowing_date(
+    json_dir: &str,
+    default_frost_date: NaiveDate,
+    args: &Value,
+) -> Result<Value> {
+    let plant = args["plant"]
+        .as_str()
+        .ok_or_else(|| anyhow!("plant is required"))?;
+    let frost_date = match args["frost_date"].as_str() {
+        Some(date) => date.parse()?,
+        None => default_frost_date,
+    };
+    let (name, info) = show::find_plant(json_dir, plant)?;
+    let mut result = serde_json::to_value(Sowing::calculate(&info, frost_date))?;
+    result["plant"] = json!(name);
+    result["frost_date"] = json!(frost_date);
+    Ok(result)
+}
+
+// Tool failures are reported to the model as results, not as protocol errors
+fn call_tool(
+    json_dir: &str,
+    frost_date: NaiveDate,
+    params: &Value,
+) -> Result<Value, (i64, String)> {
+    let args = &params["arguments"];
+    let result = match params["name"].as_str() {
+        Some("scrape_url") => scrape_url(args),
+        Some("search_collection") => search_collection(json_dir, args),
+        Some("compute_sowing_date") => compute_sowing_date(json_dir, frost_date, args),
+        Some(name) => return Err((INVALID_PARAMS, format!("Unknown tool: {}", name))),
+        None => return Err((INVALID_PARAMS, "Missing tool name".to_string())),
+    };
+    Ok(match result {
+        Ok(value) => json!({
+            "content": [{"type": "text", "text": serde_json::to_string_pretty(&value).unwrap_or_default()}],
+            "isError": false,
+        }),
+        Err(e) => json!({
+            "content": [{"type": "text", "text": e.to_string()}],
+            "isError": true,
+        }),
+    })
+}
+
+// The response to one JSON-RPC message, or None for notifications
+pub fn handle_message(json_dir: &str, frost_date: NaiveDate, message: &Value) -> Option<Value> {
+    let id = message.get("id")?.clone();
+    let params = &message["params"];
+    let result = match message["method"].as_str().unwrap_or_default() {
+        "initialize" => Ok(json!({
+            "protocolVersion": params["protocolVersion"].as_str().unwrap_or(PROTOCOL_VERSION),
+            "capabilities": {"tools": {}},
+            "serverInfo": {"name": "seeds-scraper", "version": env!("CARGO_PKG_VERSION")},
+        })),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({"tools": tool_definitions()})),
+        "tools/call" => call_tool(json_dir, frost_date, params),
+        method => Err((METHOD_NOT_FOUND, format!("Unknown method: {}", method))),
+    };
+
+    Some(match result {
+        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        Err((code, message)) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {"code": code, "message": message},
+        }),
+    })
+}
+
+// Answer newline-delimited JSON-RPC messages on stdin until it closes; stdout carries
+// only protocol messages, so anything else goes to stderr
+pub fn serve_mcp(json_dir: &str, frost_date: NaiveDate) -> Result<()> {
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    info!("MCP server ready on stdio for {}", json_dir);
+
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => handle_message(json_dir, frost_date, &message),
+            Err(e) => Some(json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": {"code": PARSE_ERROR, "message": e.to_string()},
+            })),
+        };
+        if let Some(response) = response {
+            writeln!(stdout, "{}", response)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_message() {
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 15).unwrap();
+        let call = |method: &str, params: Value| {
+            let message = json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params});
+            handle_message("/nonexistent", frost_date, &message).unwrap()
+        };
+
+        let tools = call("tools/list", json!({}));
+        assert_eq!(tools["result"]["tools"].as_array().unwrap().len(), 3);
+
+        // A tool that fails is still a successful call
+        let sowing = call(
+            "tools/call",
+            json!({"name": "compute_sowing_date", "arguments": {}}),
+        );
+        assert_eq!(sowing["result"]["isError"], true);
+        assert_eq!(sowing["result"]["content"][0]["text"], "plant is required");
+
+        assert_eq!(
+            call("tools/call", json!({"name": "bogus"}))["error"]["code"],
+            INVALID_PARAMS
+        );
+        assert_eq!(call("bogus", json!({}))["error"]["code"], METHOD_NOT_FOUND);
+        let notification = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        assert!(handle_message("/nonexistent", frost_date, &notification).is_none());
+    }
+}
@@ -0,0 +1,162 @@
+// Parsing of the free-text lengths on product pages, e.g. ¼", 1"-2", 18"-24" tall, 3'
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::error::{compiled, regex, Pattern};
+
+const NUMBER: &str = r"\d+\s*[¼½¾⅛⅜⅝⅞⅓⅔]|\d+\s+\d+/\d+|\d+/\d+|\d+(?:\.\d+)?|[¼½¾⅛⅜⅝⅞⅓⅔]";
+const UNIT: &str = r#"["”″]|''|inch(?:es)?\b|in\b|feet\b|foot\b|ft\b|['’′]"#;
+
+fn vulgar_fraction(c: char) -> Option<f32> {
+    match c {
+        '¼' => Some(0.25),
+        '½' => Some(0.5),
+        '¾' => Some(0.75),
+        '⅛' => Some(0.125),
+        '⅜' => Some(0.375),
+        '⅝' => Some(0.625),
+        '⅞' => Some(0.875),
+        '⅓' => Some(1.0 / 3.0),
+        '⅔' => Some(2.0 / 3.0),
+        _ => None,
+    }
+}
+
+// Parse a number such as "2", "1.5", "¼", "1½", "1 1/2", or "3/4"
+fn parse_number(text: &str) -> Option<f32> {
+    let text = text.trim();
+    let mut total = 0.0;
+    for part in text.split_whitespace() {
+        if let Some((numerator, denominator)) = part.split_once('/') {
+            let numerator: f32 = numerator.parse().ok()?;
+            let denominator: f32 = denominator.parse().ok()?;
+            total += numerator / denominator;
+        } else if let Some(fraction) = part.chars().last().and_then(vulgar_fraction) {
+            let whole = &part[..part.len() - part.chars().last()?.len_utf8()];
+            total += if whole.is_empty() {
+                0.0
+            } else {
+                whole.parse().ok()?
+            };
+            total += fraction;
+        } else {
+            total += part.parse::<f32>().ok()?;
+        }
+    }
+    Some(total)
+}
+
+fn inches_per_unit(unit: &str) -> f32 {
+    match unit {
+        "'" | "’" | "′" | "feet" | "foot" | "ft" => 12.0,
+        _ => 1.0,
+    }
+}
+
+// The first length or length range in the text, in inches, as (min, max)
+pub fn parse_inch_range(text: &str) -> Option<(f32, f32)> {
+    static RANGE: Pattern<Regex> = LazyLock::new(|| {
+        regex(&format!(
+            r"({n})\s*({u})?\s*(?:-|to)\s*({n})\s*({u})",
+            n = NUMBER,
+            u = UNIT
+        ))
+    });
+    static SINGLE: Pattern<Regex> =
+        LazyLock::new(|| regex(&format!(r"({n})\s*({u})", n = NUMBER, u = UNIT)));
+    let (range, single) = (compiled(&RANGE).ok()?, compiled(&SINGLE).ok()?);
+
+    if let Some(cap) = range.captures(text) {
+        let max_unit = inches_per_unit(&cap[4]);
+        let min_unit = cap.get(2).map_or(max_unit, |u| inches_per_unit(u.as_str()));
+        let min = parse_number(&cap[1])? * min_unit;
+        let max = parse_number(&cap[3])? * max_unit;
+        return Some((min, max));
+    }
+
+    let cap = single.captures(text)?;
+    let value = parse_number(&cap[1])? * inches_per_unit(&cap[2]);
+    Some((value, value))
+}
+
+// The spacing thinned seedlings end up at, from text such as `When 1" tall, thin to 1 every 3"`
+pub fn parse_thinning_spacing(text: &str) -> Option<(f32, f32)> {
+    let lower = text.to_lowercase();
+    let (_, rest) = lower
+        .split_once("every")
+        .or_else(|| lower.split_once("thin to"))?;
+    parse_inch_range(rest)
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct PlantDimensions {
+    pub height: Option<(f32, f32)>,
+    pub spread: Option<(f32, f32)>,
+}
+
+// Split text such as `18"-24" tall, 12" wide` into height and spread ranges in inches
+pub fn parse_plant_dimensions(text: &str) -> PlantDimensions {
+    let mut dimensions = PlantDimensions::default();
+
+    for part in text.split([',', ';']) {
+        let lower = part.to_lowercase();
+        let is_height = ["tall", "high", "height"]
+            .iter()
+            .any(|word| lower.contains(word));
+        let is_spread = ["wide", "spread", "width", "across"]
+            .iter()
+            .any(|word| lower.contains(word));
+
+        if is_height && dimensions.height.is_none() {
+            dimensions.height = parse_inch_range(part);
+        } else if is_spread && dimensions.spread.is_none() {
+            dimensions.spread = parse_inch_range(part);
+        }
+    }
+
+    dimensions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_inch_range() {
+        assert_eq!(parse_inch_range("¼\""), Some((0.25, 0.25)));
+        assert_eq!(parse_inch_range("1\"-2\""), Some((1.0, 2.0)));
+        assert_eq!(parse_inch_range("1 ½\" to 2\""), Some((1.5, 2.0)));
+        assert_eq!(parse_inch_range("12-18 inches"), Some((12.0, 18.0)));
+        assert_eq!(parse_inch_range("3'"), Some((36.0, 36.0)));
+        assert_eq!(parse_inch_range("thin to 1 every 3\""), Some((3.0, 3.0)));
+        assert_eq!(parse_inch_range("Not recommended"), None);
+    }
+
+    #[test]
+    fn test_parse_thinning_spacing() {
+        assert_eq!(
+            parse_thinning_spacing("When 1\" tall, thin to 1 every 3\""),
+            Some((3.0, 3.0))
+        );
+        assert_eq!(
+            parse_thinning_spacing("Thin to 12\"-18\" apart"),
+            Some((12.0, 18.0))
+        );
+        assert_eq!(parse_thinning_spacing("Not necessary"), None);
+    }
+
+    #[test]
+    fn test_parse_plant_dimensions() {
+        assert_eq!(
+            parse_plant_dimensions("18\"-24\" tall, 12\" wide"),
+            PlantDimensions {
+                height: Some((18.0, 24.0)),
+                spread: Some((12.0, 12.0)),
+            }
+        );
+        assert_eq!(
+            parse_plant_dimensions("Roots are 6\"-7\" long at their peak."),
+            PlantDimensions::default()
+        );
+    }
+}
@@ -0,0 +1,282 @@
+// Where each plant's JSON file lives, and matching plant names from the input CSV to
+// stored plants whose names differ slightly, e.g. "Sugar Snap Pea" and "Sugar Snap Peas"
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+// Maps plant names to the JSON files they're stored in, inside the results directory
+pub const INDEX_FILE: &str = "index.json";
+
+// A file name safe on every platform: the product handle from the URL, or a hash of the
+// plant name when the URL has none
+fn file_slug(plant_name: &str, url: &str) -> String {
+    let handle = reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| {
+            url.path_segments()?
+                .rev()
+                .find(|segment| !segment.is_empty())
+                .map(String::from)
+        })
+        .unwrap_or_default();
+    let slug = handle
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if slug.is_empty() {
+        name_hash(plant_name)
+    } else {
+        slug
+    }
+}
+
+// A short, stable stand-in for a name with no usable ASCII characters
+pub fn name_hash(name: &str) -> String {
+    Sha256::digest(name)
+        .iter()
+        .take(6)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+pub struct PlantIndex {
+    json_dir: String,
+    files: BTreeMap<String, String>,
+}
+
+impl PlantIndex {
+    // A results directory without an index holds only files named after their plants
+    pub fn load(json_dir: &str) -> Result<Self> {
+        let path = Path::new(json_dir).join(INDEX_FILE);
+        let files = if path.exists() {
+            let content =
+                fs::read_to_string(&path).context(format!("Failed to read {}", path.display()))?;
+            serde_json::from_str(&content).context(format!("Failed to parse {}", path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(PlantIndex {
+            json_dir: json_dir.to_string(),
+            files,
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Path::new(&self.json_dir).join(INDEX_FILE);
+        fs::write(&path, serde_json::to_string_pretty(&self.files)?)
+            .context(format!("Failed to write {}", path.display()))
+    }
+
+    pub fn contains(&self, plant_name: &str) -> bool {
+        self.files.contains_key(plant_name)
+    }
+
+    // The name of a plant's JSON file, or what it would be if it was scraped before the
+    // index existed
+    fn file_for(&self, plant_name: &str) -> String {
+        match self.files.get(plant_name) {
+            Some(file) => file.clone(),
+            None => format!("{}.json", plant_name.replace("/", "_")),
+        }
+    }
+
+    // Where a plant's JSON is
+    pub fn path_for(&self, plant_name: &str) -> String {
+        format!("{}/{}", self.json_dir, self.file_for(plant_name))
+    }
+
+    // Store a plant in another plant's file, for a second name for the same product
+    pub fn share(&mut self, plant_name: &str, existing: &str) -> String {
+        let file = self.file_for(existing);
+        self.files.insert(plant_name.to_string(), file);
+        self.path_for(plant_name)
+    }
+
+    // The slug's file name, with a suffix if another plant already has it
+    fn free_file(&self, slug: &str) -> String {
+        let taken = |file: &str| {
+            file == INDEX_FILE
+                || self.files.values().any(|existing| existing == file)
+                || Path::new(&self.json_dir).join(file).exists()
+        };
+        (1..)
+            .map(|n| match n {
+                1 => format!("{}.json", slug),
+                n => format!("{}-{}.json", slug, n),
+            })
+            .find(|file| !taken(file))
+            .unwrap()
+    }
+
+    // Give a plant a file of its own, adding a suffix if another plant already has the slug
+    pub fn assign(&mut self, plant_name: &str, url: &str) -> String {
+        if !self.files.contains_key(plant_name) {
+            let file = self.free_file(&file_slug(plant_name, url));
+            self.files.insert(plant_name.to_string(), file);
+        }
+        self.path_for(plant_name)
+    }
+
+    // Like assign, but keeping the file name the plant had in another results directory
+    pub fn adopt(&mut self, plant_name: &str, file: &str) -> String {
+        if !self.files.contains_key(plant_name) {
+            let slug = file.strip_suffix(".json").unwrap_or(file);
+            let file = self.free_file(slug);
+            self.files.insert(plant_name.to_string(), file);
+        }
+        self.path_for(plant_name)
+    }
+
+    // The plant stored in a file, given the file's name without its extension
+    pub fn plant_for_file(&self, stem: &str) -> Option<&str> {
+        let file = format!("{}.json", stem);
+        self.files
+            .iter()
+            .find(|(_, existing)| **existing == file)
+            .map(|(name, _)| name.as_str())
+    }
+
+    // Every stored plant: indexed names, plus older files named after their plants
+    pub fn plant_names(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(&self.json_dir) else {
+            return Vec::new();
+        };
+        entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+            .filter_map(|path| {
+                let stem = path.file_stem()?.to_str()?;
+                match self.plant_for_file(stem) {
+                    Some(name) => Some(name.to_string()),
+                    None if path.file_name()? == INDEX_FILE => None,
+                    None => Some(stem.to_string()),
+                }
+            })
+            .collect()
+    }
+}
+
+// Lowercase words with punctuation and plural endings dropped
+fn normalize_name(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            if word.len() > 4 && word.ends_with("oes") {
+                &word[..word.len() - 2]
+            } else if word.len() > 3 && word.ends_with('s') && !word.ends_with("ss") {
+                &word[..word.len() - 1]
+            } else {
+                word
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+// The candidate closest to the name, if exactly one is close enough to be the same plant
+pub fn closest_match<'a>(name: &str, candidates: &'a [String]) -> Option<&'a str> {
+    let name = normalize_name(name);
+    // Allow roughly one typo per eight characters
+    let limit = (name.chars().count() / 8).max(1);
+
+    let mut best: Option<(usize, &str)> = None;
+    let mut tied = false;
+    for candidate in candidates {
+        let distance = edit_distance(&name, &normalize_name(candidate));
+        if distance > limit {
+            continue;
+        }
+        match best {
+            Some((best_distance, _)) if distance > best_distance => {}
+            Some((best_distance, _)) if distance == best_distance => tied = true,
+            _ => {
+                best = Some((distance, candidate));
+                tied = false;
+            }
+        }
+    }
+    best.filter(|_| !tied).map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closest_match() {
+        let candidates = vec![
+            "Sugar Snap Peas".to_string(),
+            "Cherokee Purple Tomatoes".to_string(),
+            "Basil".to_string(),
+            "Basin".to_string(),
+        ];
+        assert_eq!(
+            closest_match("Sugar Snap Pea", &candidates),
+            Some("Sugar Snap Peas")
+        );
+        assert_eq!(
+            closest_match("cherokee purple tomato", &candidates),
+            Some("Cherokee Purple Tomatoes")
+        );
+        assert_eq!(
+            closest_match("Cherokee Purpel Tomato", &candidates),
+            Some("Cherokee Purple Tomatoes")
+        );
+        // Equally close to two files, so neither is assumed
+        assert_eq!(closest_match("Basix", &candidates), None);
+        assert_eq!(closest_match("Carrot", &candidates), None);
+    }
+
+    #[test]
+    fn test_assign_files() {
+        let mut index = PlantIndex {
+            json_dir: "results".to_string(),
+            files: BTreeMap::new(),
+        };
+        let url = "https://example.com/products/danvers-126-carrot-seeds?variant=1";
+
+        assert_eq!(
+            index.assign("Carrot: Danvers", url),
+            "results/danvers-126-carrot-seeds.json"
+        );
+        assert_eq!(
+            index.assign("Carrot: Danvers", url),
+            "results/danvers-126-carrot-seeds.json"
+        );
+        // The same product listed twice still gets two files
+        assert_eq!(
+            index.assign("Carrot (spare packet)", url),
+            "results/danvers-126-carrot-seeds-2.json"
+        );
+        assert_eq!(index.assign("Ñame", "").len(), "results/".len() + 12 + 5);
+        assert_eq!(
+            index.plant_for_file("danvers-126-carrot-seeds-2"),
+            Some("Carrot (spare packet)")
+        );
+        assert_eq!(index.path_for("Peas/Beans"), "results/Peas_Beans.json");
+        assert_eq!(
+            index.share("Danvers Carrot", "Carrot: Danvers"),
+            "results/danvers-126-carrot-seeds.json"
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use anyhow::{Context, Result};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use serde_json::{json, Value};
+use tracing::error;
+
+use crate::create_http_client;
+
+const PUSHOVER_API_URL: &str = "https://api.pushover.net/1/messages.json";
+
+pub struct SmtpSettings {
+    pub server: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub to: String,
+}
+
+// A channel to alert on, configured from the watch command's flags
+pub enum Notifier {
+    // POST a JSON body to any URL, e.g. a Slack or Discord webhook
+    Webhook(String),
+    // A full ntfy topic URL, e.g. https://ntfy.sh/my-seeds
+    Ntfy(String),
+    // An application token and the user or group key to deliver to
+    Pushover { token: String, user: String },
+    Email(SmtpSettings),
+}
+
+pub struct Notification<'a> {
+    pub title: String,
+    pub message: String,
+    pub url: &'a str,
+}
+
+impl Notifier {
+    // The HTTP request that delivers the notification; None for email, which goes over SMTP
+    fn request(
+        &self,
+        client: &reqwest::blocking::Client,
+        notification: &Notification,
+    ) -> Option<reqwest::blocking::RequestBuilder> {
+        match self {
+            Notifier::Webhook(webhook_url) => Some(client.post(webhook_url).json(&json!({
+                "title": notification.title,
+                "text": notification.message,
+                "url": notification.url,
+            }))),
+            Notifier::Ntfy(topic_url) => Some(
+                client
+                    .post(topic_url)
+                    .header("Title", &notification.title)
+                    .header("Tags", "seedling")
+                    .header("Click", notification.url)
+                    .body(notification.message.clone()),
+            ),
+            Notifier::Pushover { token, user } => Some(client.post(PUSHOVER_API_URL).form(&[
+                ("token", token.as_str()),
+                ("user", user.as_str()),
+                ("title", &notification.title),
+                ("message", &notification.message),
+                ("url", notification.url),
+            ])),
+            Notifier::Email(_) => None,
+        }
+    }
+
+    fn send(&self, notification: &Notification) -> Result<()> {
+        match (self, self.request(&create_http_client(), notification)) {
+            (_, Some(request)) => {
+                request.send()?.error_for_status()?;
+            }
+            (Notifier::Email(smtp), None) => send_email(
+                smtp,
+                &notification.title,
+                format!("{}\n\n{}", notification.message, notification.url),
+            )?,
+            (_, None) => {}
+        }
+        Ok(())
+    }
+
+    fn describe(&self) -> &'static str {
+        match self {
+            Notifier::Webhook(_) => "webhook",
+            Notifier::Ntfy(_) => "ntfy",
+            Notifier::Pushover { .. } => "Pushover",
+            Notifier::Email(_) => "email",
+        }
+    }
+}
+
+pub fn send_email(smtp: &SmtpSettings, subject: &str, body: String) -> Result<()> {
+    let email = Message::builder()
+        .from(smtp.from.parse().context("Invalid --email-from address")?)
+        .to(smtp.to.parse().context("Invalid --email-to address")?)
+        .subject(subject)
+        .body(body)?;
+
+    let mut transport = SmtpTransport::relay(&smtp.server)?;
+    if let (Some(username), Some(password)) = (&smtp.username, &smtp.password) {
+        transport = transport.credentials(Credentials::new(username.clone(), password.clone()));
+    }
+    transport.build().send(&email)?;
+    Ok(())
+}
+
+// Send through every configured channel; a failing channel doesn't stop the others.
+// Returns whether any channel delivered it.
+pub fn notify_all(notifiers: &[Notifier], notification: &Notification) -> bool {
+    let mut delivered = false;
+    for notifier in notifiers {
+        match notifier.send(notification) {
+            Ok(()) => delivered = true,
+            Err(e) => error!(
+                "Failed to send {} notification: {:#}",
+                notifier.describe(),
+                e
+            ),
+        }
+    }
+    delivered
+}
+
+// POST an event from an unattended run, such as a batch finishing, to a webhook;
+// `title` and `text` are included so chat webhooks have something to show
+pub fn post_event(webhook_url: &str, event: &str, title: &str, text: &str, details: Value) {
+    let mut body = json!({"event": event, "title": title, "text": text});
+    if let (Value::Object(body), Value::Object(details)) = (&mut body, details) {
+        body.extend(details);
+    }
+    let result = create_http_client()
+        .post(webhook_url)
+        .json(&body)
+        .send()
+        .and_then(|response| response.error_for_status());
+    if let Err(e) = result {
+        error!("Failed to send {} webhook: {}", event, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn built(notifier: &Notifier) -> reqwest::blocking::Request {
+        let notification = Notification {
+            title: "Carrot is back in stock".to_string(),
+            message: "Carrot is available again at $3.49.".to_string(),
+            url: "http://example.com/carrot",
+        };
+        notifier
+            .request(&reqwest::blocking::Client::new(), &notification)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    fn body(request: &reqwest::blocking::Request) -> String {
+        let bytes = request.body().and_then(|body| body.as_bytes()).unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_notification_requests() {
+        let webhook = built(&Notifier::Webhook("http://hooks.example.com/x".to_string()));
+        assert_eq!(webhook.url().as_str(), "http://hooks.example.com/x");
+        assert_eq!(
+            serde_json::from_str::<Value>(&body(&webhook)).unwrap(),
+            json!({
+                "title": "Carrot is back in stock",
+                "text": "Carrot is available again at $3.49.",
+                "url": "http://example.com/carrot",
+            })
+        );
+
+        let ntfy = built(&Notifier::Ntfy("https://ntfy.sh/my-seeds".to_string()));
+        assert_eq!(ntfy.headers()["Title"], "Carrot is back in stock");
+        assert_eq!(ntfy.headers()["Click"], "http://example.com/carrot");
+        assert_eq!(body(&ntfy), "Carrot is available again at $3.49.");
+
+        let pushover = built(&Notifier::Pushover {
+            token: "app".to_string(),
+            user: "me".to_string(),
+        });
+        assert_eq!(pushover.url().as_str(), PUSHOVER_API_URL);
+        assert_eq!(
+            body(&pushover),
+            "token=app&user=me&title=Carrot+is+back+in+stock\
+             &message=Carrot+is+available+again+at+%243.49.&url=http%3A%2F%2Fexample.com%2Fcarrot"
+        );
+    }
+}
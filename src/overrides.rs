@@ -0,0 +1,200 @@
+// Field values supplied by hand, kept beside the plant JSON so a re-scrape doesn't lose them
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{PlantInfo, SowingStrategy};
+
+pub const OVERRIDES_FILE: &str = "overrides.toml";
+
+// Not a PlantInfo field: stands in for the input CSV's strategy column when that's empty
+const STRATEGY_KEY: &str = "sowing_strategy";
+
+// Tables of field values keyed by plant name, e.g. `[Carrot]` then `family = "Apiaceae"`
+#[derive(Debug, Default)]
+pub struct Overrides {
+    path: PathBuf,
+    plants: BTreeMap<String, toml::Table>,
+}
+
+impl Overrides {
+    pub fn load(json_dir: &str) -> Result<Self> {
+        let path = Path::new(json_dir).join(OVERRIDES_FILE);
+        let plants = match fs::read_to_string(&path) {
+            Ok(content) => {
+                toml::from_str(&content).context(format!("Failed to parse {}", path.display()))?
+            }
+            Err(_) => BTreeMap::new(),
+        };
+        Ok(Overrides { path, plants })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, toml::to_string(&self.plants)?)
+            .context(format!("Failed to write {}", self.path.display()))
+    }
+
+    pub fn set(&mut self, plant: &str, field: &str, value: impl Into<toml::Value>) {
+        self.plants
+            .entry(plant.to_string())
+            .or_default()
+            .insert(field.to_string(), value.into());
+    }
+
+    pub fn strategy(&self, plant: &str) -> Option<SowingStrategy> {
+        match self.plants.get(plant)?.get(STRATEGY_KEY)?.as_str()? {
+            "Inside" => Some(SowingStrategy::Inside),
+            "Outside" => Some(SowingStrategy::Outside),
+            _ => None,
+        }
+    }
+
+    pub fn set_strategy(&mut self, plant: &str, strategy: SowingStrategy) {
+        self.set(plant, STRATEGY_KEY, strategy.to_string());
+    }
+
+    // Replace the plant's fields with any overridden values, returning the names of the
+    // fields that were replaced
+    pub fn apply(&self, plant: &str, info: &mut PlantInfo) -> Result<Vec<String>> {
+        let Some(fields) = self.plants.get(plant) else {
+            return Ok(Vec::new());
+        };
+        let mut merged = serde_json::to_value(&*info)?;
+        let mut applied = Vec::new();
+        for (field, value) in fields.iter().filter(|(field, _)| *field != STRATEGY_KEY) {
+            merged[field] = serde_json::to_value(value)?;
+            applied.push(field.clone());
+        }
+        // Parsed again from the overridden text, unless overridden themselves
+        if let Some(record) = merged.as_object_mut() {
+            for field in &applied {
+                for derived in PlantInfo::derived_fields(field) {
+                    if !fields.contains_key(*derived) {
+                        record.remove(*derived);
+                    }
+                }
+            }
+        }
+        *info = serde_json::from_value(merged).context(format!(
+            "Invalid override for {} in {}",
+            plant, OVERRIDES_FILE
+        ))?;
+        info.fill_derived_fields();
+        Ok(applied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate_dates, get_when_to_seed_start};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_apply_overrides() {
+        let plants = toml::from_str(
+            r#"
+            [Garlic]
+            when_to_sow_outside = "RECOMMENDED. 4 weeks after your average last frost date"
+            sowing_strategy = "Outside"
+            "#,
+        )
+        .unwrap();
+        let overrides = Overrides {
+            path: PathBuf::new(),
+            plants,
+        };
+
+        let mut info = PlantInfo {
+            url: "http://example.com/garlic".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            overrides.apply("Garlic", &mut info).unwrap(),
+            vec!["when_to_sow_outside"]
+        );
+        assert_eq!(
+            info.when_to_sow_outside.as_deref(),
+            Some("RECOMMENDED. 4 weeks after your average last frost date")
+        );
+        assert_eq!(info.url, "http://example.com/garlic");
+        assert_eq!(overrides.strategy("Garlic"), Some(SowingStrategy::Outside));
+        assert_eq!(overrides.strategy("Onion"), None);
+    }
+
+    #[test]
+    fn test_override_reparses_derived_fields() {
+        let plants = toml::from_str(
+            r#"
+            [Carrot]
+            days_to_maturity = "80 days"
+            "#,
+        )
+        .unwrap();
+        let overrides = Overrides {
+            path: PathBuf::new(),
+            plants,
+        };
+
+        let mut info = PlantInfo {
+            days_to_maturity: Some("65 days".to_string()),
+            days_to_maturity_min: Some(65),
+            days_to_maturity_max: Some(65),
+            when_to_sow_outside: Some(
+                "2 to 4 weeks before your average last frost date".to_string(),
+            ),
+            ..Default::default()
+        };
+        overrides.apply("Carrot", &mut info).unwrap();
+        assert_eq!(info.maturity_days(), Some((80, 80)));
+
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        let sowing_times = get_when_to_seed_start(&info, None);
+        let dates = calculate_dates(&info, None, &sowing_times, frost_date);
+        // Sown Apr 26, 80 rather than 65 days before harvest
+        assert_eq!(dates.first_harvest, NaiveDate::from_ymd_opt(2025, 7, 15));
+    }
+
+    #[test]
+    fn test_override_redetects_designations_and_resistance() {
+        let plants = toml::from_str(
+            r#"
+            [Tomato]
+            title = "Organic Heirloom Tomato"
+            variety_info = "Disease resistance: V, F1."
+            hybrid = true
+            "#,
+        )
+        .unwrap();
+        let overrides = Overrides {
+            path: PathBuf::new(),
+            plants,
+        };
+
+        let mut info = PlantInfo {
+            title: Some("Tomato".to_string()),
+            organic: Some(false),
+            heirloom: Some(false),
+            open_pollinated: Some(false),
+            hybrid: Some(false),
+            variety_info: Some("Disease resistance: TMV.".to_string()),
+            ..Default::default()
+        };
+        info.fill_derived_fields();
+        assert_eq!(info.disease_resistance[0].code, "TMV");
+
+        overrides.apply("Tomato", &mut info).unwrap();
+        assert_eq!(info.organic, Some(true));
+        assert_eq!(info.heirloom, Some(true));
+        assert_eq!(info.open_pollinated, Some(true));
+        // Overridden itself, so kept rather than redetected
+        assert_eq!(info.hybrid, Some(true));
+        let codes: Vec<&str> = info
+            .disease_resistance
+            .iter()
+            .map(|resistance| resistance.code.as_str())
+            .collect();
+        assert_eq!(codes, vec!["V", "F1"]);
+    }
+}
@@ -0,0 +1,182 @@
+// Minimal PDF writer for text-only documents using the built-in Helvetica fonts
+
+// US Letter in PDF points
+pub const LETTER_WIDTH: f32 = 612.0;
+pub const LETTER_HEIGHT: f32 = 792.0;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource_name(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+pub struct Page {
+    width: f32,
+    height: f32,
+    content: Vec<u8>,
+}
+
+impl Page {
+    pub fn new(width: f32, height: f32) -> Self {
+        Page {
+            width,
+            height,
+            content: Vec::new(),
+        }
+    }
+
+    // Draw text with its baseline at (x, y), measured from the bottom-left corner
+    pub fn text(&mut self, x: f32, y: f32, size: f32, font: Font, text: &str) {
+        self.content.extend_from_slice(
+            format!(
+                "BT /{} {} Tf {:.2} {:.2} Td (",
+                font.resource_name(),
+                size,
+                x,
+                y
+            )
+            .as_bytes(),
+        );
+        self.content.extend(encode_text(text));
+        self.content.extend_from_slice(b") Tj ET\n");
+    }
+
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32) {
+        self.content.extend_from_slice(
+            format!("{:.2} {:.2} m {:.2} {:.2} l S\n", x1, y1, x2, y2).as_bytes(),
+        );
+    }
+}
+
+// Encode text as a WinAnsi string literal, escaping PDF delimiters
+fn encode_text(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                bytes.push(b'\\');
+                bytes.push(c as u8);
+            }
+            '\u{2013}' | '\u{2014}' => bytes.push(b'-'),
+            '\u{2018}' | '\u{2019}' => bytes.push(b'\''),
+            '\u{201C}' | '\u{201D}' => bytes.push(b'"'),
+            c if (c as u32) < 0x80 && !c.is_control() => bytes.push(c as u8),
+            // Latin-1 supplement matches WinAnsi (covers ¼, ½, °)
+            c if (0xA0..=0xFF).contains(&(c as u32)) => bytes.push(c as u32 as u8),
+            _ => bytes.push(b'?'),
+        }
+    }
+    bytes
+}
+
+#[derive(Default)]
+pub struct Document {
+    pages: Vec<Page>,
+}
+
+impl Document {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_page(&mut self, page: Page) {
+        self.pages.push(page);
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut objects: Vec<Vec<u8>> = vec![
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            Vec::new(), // Page tree, filled in once page object numbers are known
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+                .to_vec(),
+        ];
+
+        let mut kids = Vec::new();
+        for page in &self.pages {
+            let page_id = objects.len() + 1;
+            let content_id = page_id + 1;
+            kids.push(format!("{} 0 R", page_id));
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                     /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    page.width, page.height, content_id
+                )
+                .into_bytes(),
+            );
+
+            let mut stream = format!("<< /Length {} >>\nstream\n", page.content.len()).into_bytes();
+            stream.extend_from_slice(&page.content);
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+        objects[1] = format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            self.pages.len()
+        )
+        .into_bytes();
+
+        let mut out = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::new();
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+            out.extend_from_slice(object);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_offset = out.len();
+        out.extend_from_slice(format!("xref\n0 {}\n", objects.len() + 1).as_bytes());
+        out.extend_from_slice(b"0000000000 65535 f \n");
+        for offset in offsets {
+            out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        out.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref_offset
+            )
+            .as_bytes(),
+        );
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_text() {
+        assert_eq!(encode_text("Sow (¼\")"), b"Sow \\(\xBC\"\\)".to_vec());
+        assert_eq!(encode_text("60°\u{2013}85°F"), b"60\xB0-85\xB0F".to_vec());
+    }
+
+    #[test]
+    fn test_document_structure() {
+        let mut document = Document::new();
+        let mut page = Page::new(LETTER_WIDTH, LETTER_HEIGHT);
+        page.text(72.0, 720.0, 12.0, Font::Regular, "Hello");
+        document.add_page(page);
+        let bytes = document.to_bytes();
+        let text = String::from_utf8_lossy(&bytes);
+
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.contains("/Count 1"));
+        assert!(text.contains("(Hello) Tj"));
+        assert!(text.ends_with("%%EOF\n"));
+    }
+}
@@ -0,0 +1,92 @@
+// Sowing instructions given as phrases rather than week counts, e.g. "as soon as the soil can
+// be worked", read as a number of weeks from the last frost date
+use std::sync::OnceLock;
+
+use crate::{RelativeTiming, SowingTime, TimingType};
+
+// Phrases recognized without any configuration, compared ignoring case, with their offset in
+// weeks from the last frost date
+const DEFAULT_PHRASES: [(&str, i64); 7] = [
+    ("soil can be worked", -4),
+    ("early spring", -4),
+    ("danger of frost", 0),
+    ("after last frost", 0),
+    ("soil has warmed", 2),
+    ("soil warms", 2),
+    ("late spring", 2),
+];
+
+// A phrase and the weeks from the last frost it means, e.g. "early spring=-6"
+#[derive(Debug, Clone, PartialEq)]
+pub struct SowingPhrase {
+    pub phrase: String,
+    pub weeks: i64,
+}
+
+pub fn parse_sowing_phrase(text: &str) -> Result<SowingPhrase, String> {
+    let (phrase, weeks) = text
+        .rsplit_once('=')
+        .ok_or_else(|| format!("expected PHRASE=WEEKS, got \"{}\"", text))?;
+    let weeks = weeks
+        .trim()
+        .parse()
+        .map_err(|_| format!("\"{}\" is not a whole number of weeks", weeks.trim()))?;
+    Ok(SowingPhrase {
+        phrase: phrase.trim().to_lowercase(),
+        weeks,
+    })
+}
+
+static PHRASES: OnceLock<Vec<SowingPhrase>> = OnceLock::new();
+
+// Set once from the global --sowing-phrase flags
+pub fn set_phrases(phrases: Vec<SowingPhrase>) {
+    let _ = PHRASES.set(phrases);
+}
+
+fn match_with(text: &str, phrases: &[SowingPhrase]) -> Option<SowingTime> {
+    let text = text.to_lowercase();
+    let weeks = phrases
+        .iter()
+        .map(|phrase| (phrase.phrase.as_str(), phrase.weeks))
+        .chain(DEFAULT_PHRASES)
+        .find(|(phrase, _)| text.contains(phrase))
+        .map(|(_, weeks)| weeks)?;
+    Some(SowingTime {
+        weeks_min: weeks.abs(),
+        weeks_max: weeks.abs(),
+        relative_timing: if weeks < 0 {
+            RelativeTiming::Before
+        } else {
+            RelativeTiming::After
+        },
+        timing_type: TimingType::LastFrost,
+    })
+}
+
+// The sowing time a phrase in the text stands for, checking configured phrases first
+pub fn match_phrase(text: &str) -> Option<SowingTime> {
+    match_with(text, PHRASES.get().map_or(&[], Vec::as_slice))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_phrase() {
+        let time = match_with("Sow as soon as the soil can be worked in spring.", &[]).unwrap();
+        assert_eq!((time.weeks_min, time.weeks_max), (4, 4));
+        assert!(matches!(time.relative_timing, RelativeTiming::Before));
+
+        let time = match_with("Sow after all danger of frost has passed.", &[]).unwrap();
+        assert_eq!(time.weeks_min, 0);
+
+        let phrases = [parse_sowing_phrase("Early Spring=-6").unwrap()];
+        let time = match_with("Direct sow in early spring", &phrases).unwrap();
+        assert_eq!(time.weeks_min, 6);
+
+        assert!(match_with("whenever you like", &[]).is_none());
+        assert!(parse_sowing_phrase("early spring=soon").is_err());
+    }
+}
@@ -0,0 +1,119 @@
+// What actually happened to each plant this season, appended as it happens
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::path::Path;
+use tracing::warn;
+
+pub const PLANTING_LOG_FILE: &str = "planting_log.csv";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventKind {
+    Sow,
+    Transplant,
+    // The first seedlings came up
+    Germination,
+}
+
+// One row of the log, e.g. "2025-03-20,Carrot,sow,Bed 2"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    #[serde(rename = "Date")]
+    pub date: NaiveDate,
+    #[serde(rename = "Plant")]
+    pub plant: String,
+    #[serde(rename = "Event")]
+    pub kind: EventKind,
+    #[serde(rename = "Notes", default)]
+    pub notes: String,
+}
+
+// Add an event to the end of the log in the JSON directory, starting the file if needed
+pub fn append(json_dir: &str, event: &Event) -> Result<()> {
+    let path = Path::new(json_dir).join(PLANTING_LOG_FILE);
+    let is_new = !path.exists();
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .context(format!("Failed to open {}", path.display()))?;
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(is_new)
+        .from_writer(file);
+    writer.serialize(event)?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[derive(Debug, Default)]
+pub struct PlantingLog {
+    events: Vec<Event>,
+}
+
+impl PlantingLog {
+    // Every event in the JSON directory's log, or none if nothing has been logged yet
+    pub fn load(json_dir: &str) -> Result<Self> {
+        let path = Path::new(json_dir).join(PLANTING_LOG_FILE);
+        if !path.exists() {
+            return Ok(PlantingLog::default());
+        }
+        let mut reader =
+            csv::Reader::from_path(&path).context(format!("Failed to read {}", path.display()))?;
+        let mut events = Vec::new();
+        for result in reader.deserialize() {
+            match result {
+                Ok(event) => events.push(event),
+                Err(e) => warn!("Error reading {}: {}", path.display(), e),
+            }
+        }
+        Ok(PlantingLog { events })
+    }
+
+    // When the plant first had this happen in the season's year, e.g. its first sowing of a
+    // succession; earlier seasons' events stay in the log but don't count
+    pub fn first(&self, plant: &str, kind: EventKind, season: i32) -> Option<NaiveDate> {
+        self.events
+            .iter()
+            .filter(|event| event.kind == kind && event.date.year() == season)
+            .filter(|event| event.plant.eq_ignore_ascii_case(plant))
+            .map(|event| event.date)
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_round_trip() {
+        let dir = std::env::temp_dir().join(format!("seed-scraper-log-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let json_dir = dir.to_str().unwrap();
+        let event = |date: &str, kind| Event {
+            date: date.parse().unwrap(),
+            plant: "Carrot".to_string(),
+            kind,
+            notes: String::new(),
+        };
+        append(json_dir, &event("2025-04-20", EventKind::Sow)).unwrap();
+        append(json_dir, &event("2025-04-06", EventKind::Sow)).unwrap();
+        append(json_dir, &event("2025-04-19", EventKind::Germination)).unwrap();
+        append(json_dir, &event("2024-04-02", EventKind::Sow)).unwrap();
+
+        let log = PlantingLog::load(json_dir).unwrap();
+        assert_eq!(log.events.len(), 4);
+        assert_eq!(
+            log.first("carrot", EventKind::Sow, 2025),
+            NaiveDate::from_ymd_opt(2025, 4, 6)
+        );
+        assert_eq!(
+            log.first("carrot", EventKind::Sow, 2024),
+            NaiveDate::from_ymd_opt(2024, 4, 2)
+        );
+        assert_eq!(log.first("Carrot", EventKind::Transplant, 2025), None);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,147 @@
+// Settings for one store's domain, so a batch mixing vendors can be gentler with some, e.g.
+//
+//   ["www.botanicalinterests.com"]
+//   delay = 3.0
+//   user_agents = ["Mozilla/5.0 ..."]
+//   headers = { Referer = "https://www.botanicalinterests.com/" }
+use anyhow::{anyhow, Context, Result};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProfileFile {
+    delay: Option<f64>,
+    timeout: Option<f64>,
+    #[serde(default)]
+    user_agents: Vec<String>,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default)]
+pub struct DomainProfile {
+    // Replace the global --delay and --timeout for this domain
+    pub delay: Option<Duration>,
+    pub timeout: Option<Duration>,
+    // Taken in turn instead of the global --user-agent list
+    pub user_agents: Vec<String>,
+    // Sent with every page request, replacing the default header of the same name
+    pub headers: HeaderMap,
+}
+
+fn seconds(domain: &str, key: &str, value: Option<f64>) -> Result<Option<Duration>> {
+    value
+        .map(|value| {
+            Duration::try_from_secs_f64(value)
+                .map_err(|_| anyhow!("{} for {} must be a number of seconds", key, domain))
+        })
+        .transpose()
+}
+
+impl DomainProfile {
+    fn from_file(domain: &str, file: ProfileFile) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in file.headers {
+            let name = HeaderName::try_from(name.as_str())
+                .context(format!("Invalid header name {} for {}", name, domain))?;
+            let value = HeaderValue::try_from(value.as_str())
+                .context(format!("Invalid {} header for {}", name, domain))?;
+            headers.insert(name, value);
+        }
+        Ok(DomainProfile {
+            delay: seconds(domain, "delay", file.delay)?,
+            timeout: seconds(domain, "timeout", file.timeout)?,
+            user_agents: file.user_agents,
+            headers,
+        })
+    }
+}
+
+// Profiles keyed by domain; a domain also covers its subdomains
+#[derive(Debug, Default)]
+pub struct Profiles(BTreeMap<String, DomainProfile>);
+
+impl Profiles {
+    pub fn parse(content: &str) -> Result<Self> {
+        let files: BTreeMap<String, ProfileFile> = toml::from_str(content)?;
+        files
+            .into_iter()
+            .map(|(domain, file)| {
+                let profile = DomainProfile::from_file(&domain, file)?;
+                Ok((domain.to_lowercase(), profile))
+            })
+            .collect::<Result<_>>()
+            .map(Profiles)
+    }
+
+    pub fn load(path: &str) -> Result<Self> {
+        let content = fs::read_to_string(path).context(format!("Failed to read {}", path))?;
+        Self::parse(&content).context(format!("Failed to parse {}", path))
+    }
+
+    // The profile for the most specific domain the URL's host falls under
+    fn for_url(&self, url: &str) -> Option<&DomainProfile> {
+        let host = reqwest::Url::parse(url).ok()?.host_str()?.to_lowercase();
+        self.0
+            .iter()
+            .filter(|(domain, _)| {
+                host == **domain
+                    || host
+                        .strip_suffix(domain.as_str())
+                        .is_some_and(|sub| sub.ends_with('.'))
+            })
+            .max_by_key(|(domain, _)| domain.len())
+            .map(|(_, profile)| profile)
+    }
+}
+
+static PROFILES: OnceLock<Profiles> = OnceLock::new();
+
+// Set once from the global --profiles file
+pub fn set_profiles(profiles: Profiles) {
+    let _ = PROFILES.set(profiles);
+}
+
+pub fn for_url(url: &str) -> Option<&'static DomainProfile> {
+    PROFILES.get()?.for_url(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_for_url() {
+        let profiles = Profiles::parse(
+            r#"
+            ["botanicalinterests.com"]
+            delay = 5
+            headers = { Referer = "https://www.botanicalinterests.com/" }
+
+            ["shop.botanicalinterests.com"]
+            timeout = 60.0
+            "#,
+        )
+        .unwrap();
+        let profile = profiles
+            .for_url("https://www.botanicalinterests.com/products/carrot")
+            .unwrap();
+        assert_eq!(profile.delay, Some(Duration::from_secs(5)));
+        assert_eq!(profile.headers.len(), 1);
+        let shop = profiles
+            .for_url("https://shop.botanicalinterests.com/products/carrot")
+            .unwrap();
+        assert_eq!(shop.timeout, Some(Duration::from_secs(60)));
+        assert!(profiles
+            .for_url("https://notbotanicalinterests.com/")
+            .is_none());
+
+        assert!(Profiles::parse("[\"example.com\"]\nconcurrency = 4").is_err());
+        assert!(Profiles::parse("[\"example.com\"]\ndelay = -1").is_err());
+    }
+}
@@ -0,0 +1,135 @@
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use std::fs;
+
+use crate::formats::jsonl::plant_object;
+use crate::list::{format_table, summary_row};
+use crate::names::PlantIndex;
+use crate::{load_json_dir, ExportRow, InputRecord, OutputFormat, PlantExport, PlantInfo};
+
+// Criteria a plant must meet every one of; unset criteria match anything
+#[derive(Debug, Default)]
+pub struct PlantFilter {
+    pub family: Option<String>,
+    pub exposure: Option<String>,
+    pub min_maturity: Option<u32>,
+    pub max_maturity: Option<u32>,
+    pub tags: Vec<String>,
+    pub resistant_to: Vec<String>,
+}
+
+impl PlantFilter {
+    pub fn matches(&self, info: &PlantInfo) -> bool {
+        let contains = |field: &Option<String>, wanted: &Option<String>| match wanted {
+            Some(wanted) => field
+                .as_ref()
+                .is_some_and(|field| field.to_lowercase().contains(&wanted.to_lowercase())),
+            None => true,
+        };
+        let family_matches = match &self.family {
+            Some(family) => info
+                .family
+                .as_ref()
+                .is_some_and(|f| f.eq_ignore_ascii_case(family)),
+            None => true,
+        };
+        // A plant only fits a maturity limit if its whole range does
+        let maturity = info.maturity_days();
+        let maturity_matches = match (self.min_maturity, self.max_maturity) {
+            (None, None) => true,
+            (min, max) => maturity.is_some_and(|(low, high)| {
+                min.is_none_or(|min| low >= min) && max.is_none_or(|max| high <= max)
+            }),
+        };
+
+        family_matches
+            && contains(&info.exposure, &self.exposure)
+            && maturity_matches
+            && info.has_tags(&self.tags)
+            && self
+                .resistant_to
+                .iter()
+                .all(|disease| info.resists(disease))
+    }
+}
+
+// Print the plants matching a filter, optionally also writing them to a JSON file
+pub fn query(
+    json_dir: &str,
+    filter: &PlantFilter,
+    frost_date: NaiveDate,
+    output_file: Option<&str>,
+    output: OutputFormat,
+) -> Result<()> {
+    let plants: Vec<(String, PlantInfo)> = load_json_dir(json_dir)?
+        .into_iter()
+        .filter(|(_, info)| filter.matches(info))
+        .collect();
+    let index = PlantIndex::load(json_dir)?;
+
+    let rows: Vec<[String; 5]> = plants
+        .iter()
+        .map(|(name, info)| summary_row(&index, name, info))
+        .collect();
+    if output == OutputFormat::Text {
+        println!("{}", format_table(&rows));
+    }
+    output.print_text(&format!("\n{} matching plants", rows.len()));
+
+    if output_file.is_some() || output == OutputFormat::Json {
+        let objects: Vec<serde_json::Value> = plants
+            .into_iter()
+            .map(|(name, info)| {
+                let record = csv::StringRecord::from(vec![name.as_str(), info.url.as_str()]);
+                let plant = PlantExport::new(info, None, frost_date);
+                plant_object(&ExportRow {
+                    input: InputRecord::from_csv_record(&record),
+                    plant: Some(plant),
+                })
+            })
+            .collect();
+        if let Some(output_file) = output_file {
+            fs::write(output_file, serde_json::to_string_pretty(&objects)?)
+                .context(format!("Failed to write matches to {}", output_file))?;
+            output.print_text(&format!(
+                "Wrote {} plants to {}",
+                objects.len(),
+                output_file
+            ));
+        }
+        output.print_json(&objects.into())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plant_filter() {
+        let info: PlantInfo = serde_json::from_str(
+            r#"{"url": "u", "family": "Solanaceae", "exposure": "Full sun",
+                "days_to_maturity": "55-70 days", "attributes": "Heat Tolerant"}"#,
+        )
+        .unwrap();
+
+        let filter = PlantFilter {
+            family: Some("solanaceae".to_string()),
+            exposure: Some("full sun".to_string()),
+            max_maturity: Some(70),
+            ..Default::default()
+        };
+        assert!(filter.matches(&info));
+        assert!(!PlantFilter {
+            max_maturity: Some(60),
+            ..Default::default()
+        }
+        .matches(&info));
+        assert!(!PlantFilter {
+            tags: vec!["Frost Tolerant".to_string()],
+            ..Default::default()
+        }
+        .matches(&info));
+    }
+}
@@ -0,0 +1,166 @@
+// The "You may also like" products a store shows alongside a plant
+use anyhow::{bail, Context, Result};
+use reqwest::Url;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::LazyLock;
+use std::thread;
+
+use crate::error::{compiled, selector, Pattern, ScrapingError};
+use crate::{fetch_page, page_request, request_delay};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RelatedPlant {
+    pub title: String,
+    pub url: String,
+}
+
+// Resolve a store-relative link against the page, dropping Shopify's tracking query
+fn absolute_url(page_url: &str, href: &str) -> Option<String> {
+    let mut url = Url::parse(page_url).ok()?.join(href).ok()?;
+    url.set_query(None);
+    url.set_fragment(None);
+    Some(url.to_string())
+}
+
+fn push_unique(related: &mut Vec<RelatedPlant>, plant: RelatedPlant) {
+    if !related.iter().any(|existing| existing.url == plant.url) {
+        related.push(plant);
+    }
+}
+
+// Product cards already rendered into the page's recommendations section
+pub fn parse_related_products(
+    document: &Html,
+    page_url: &str,
+) -> Result<Vec<RelatedPlant>, ScrapingError> {
+    static PRODUCT_LINKS: Pattern<Selector> =
+        LazyLock::new(|| selector(r#"product-recommendations a[href*="/products/"]"#));
+    let mut related = Vec::new();
+
+    for link in document.select(compiled(&PRODUCT_LINKS)?) {
+        let title = link.text().collect::<String>().trim().to_string();
+        let url = link
+            .value()
+            .attr("href")
+            .and_then(|href| absolute_url(page_url, href));
+        match url {
+            Some(url) if !title.is_empty() && url != page_url => {
+                push_unique(&mut related, RelatedPlant { title, url })
+            }
+            _ => {}
+        }
+    }
+    Ok(related)
+}
+
+// Most themes load recommendations after the page does; this is the JSON endpoint behind them
+pub fn recommendations_url(document: &Html, page_url: &str) -> Option<String> {
+    static RECOMMENDATIONS: Pattern<Selector> =
+        LazyLock::new(|| selector("product-recommendations[data-url]"));
+    let data_url = document
+        .select(compiled(&RECOMMENDATIONS).ok()?)
+        .next()?
+        .value()
+        .attr("data-url")?;
+    let section_url = Url::parse(page_url).ok()?.join(data_url).ok()?;
+
+    let mut url = section_url.join("/recommendations/products.json").ok()?;
+    url.query_pairs_mut().extend_pairs(
+        section_url
+            .query_pairs()
+            .filter(|(key, _)| key == "product_id" || key == "limit"),
+    );
+    Some(url.to_string())
+}
+
+fn parse_recommendations_json(data: &Value, page_url: &str) -> Vec<RelatedPlant> {
+    let mut related = Vec::new();
+    for product in data["products"].as_array().into_iter().flatten() {
+        let title = product["title"].as_str();
+        let url = product["url"]
+            .as_str()
+            .or(product["handle"].as_str())
+            .map(|href| {
+                if href.starts_with('/') {
+                    href.to_string()
+                } else {
+                    format!("/products/{}", href)
+                }
+            })
+            .and_then(|href| absolute_url(page_url, &href));
+        if let (Some(title), Some(url)) = (title, url) {
+            push_unique(
+                &mut related,
+                RelatedPlant {
+                    title: title.to_string(),
+                    url,
+                },
+            );
+        }
+    }
+    related
+}
+
+pub fn fetch_related_products(
+    client: &reqwest::blocking::Client,
+    html: &str,
+    page_url: &str,
+) -> Result<Vec<RelatedPlant>> {
+    let document = Html::parse_document(html);
+    let Some(url) = recommendations_url(&document, page_url) else {
+        return Ok(Vec::new());
+    };
+
+    // Another request to the same store, so paced and backed off like its pages
+    thread::sleep(request_delay(&url));
+    let page = fetch_page(page_request(client, &url))?;
+    if !(200..300).contains(&page.status) {
+        bail!("Recommendations request failed with HTTP {}", page.status);
+    }
+    let data: Value = serde_json::from_str(&page.body)
+        .context(format!("Invalid recommendations from {}", url))?;
+    Ok(parse_recommendations_json(&data, page_url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const PAGE_URL: &str = "https://www.botanicalinterests.com/products/danvers-126-carrot-seeds";
+
+    #[test]
+    fn test_recommendations_url() {
+        let html = include_str!("../tests/fixtures/seed.html");
+        assert_eq!(
+            recommendations_url(&Html::parse_document(html), PAGE_URL).as_deref(),
+            Some("https://www.botanicalinterests.com/recommendations/products.json?product_id=8083614695677&limit=4")
+        );
+    }
+
+    #[test]
+    fn test_parse_recommendations_json() {
+        let data = json!({"products": [
+            {"title": "Scarlet Nantes Carrot Seeds", "url": "/products/scarlet-nantes-carrot-seeds?pr_prod_strat=e5"},
+            {"title": "Bunching Onion Seeds", "handle": "bunching-onion-seeds"},
+            {"title": "No link"},
+        ]});
+        assert_eq!(
+            parse_recommendations_json(&data, PAGE_URL),
+            vec![
+                RelatedPlant {
+                    title: "Scarlet Nantes Carrot Seeds".to_string(),
+                    url: "https://www.botanicalinterests.com/products/scarlet-nantes-carrot-seeds"
+                        .to_string(),
+                },
+                RelatedPlant {
+                    title: "Bunching Onion Seeds".to_string(),
+                    url: "https://www.botanicalinterests.com/products/bunching-onion-seeds"
+                        .to_string(),
+                },
+            ]
+        );
+    }
+}
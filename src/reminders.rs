@@ -0,0 +1,152 @@
+// Reminders of tasks coming up in the next few days, for running daily from cron
+use anyhow::{bail, Context, Result};
+use chrono::{Days, NaiveDate};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use crate::load_json_dir;
+use crate::notify::{notify_all, Notification, Notifier};
+use crate::tasks::{frost_date_in_year_of, plant_tasks, Task};
+use crate::PlantInfo;
+
+// Tasks already reminded about, one per line, so a daily run doesn't repeat them
+const SENT_REMINDERS_FILE: &str = "reminders_sent.txt";
+
+fn reminder_key(task: &Task) -> String {
+    format!("{}|{}|{}", task.kind, task.plant, task.from)
+}
+
+// Tasks that can be done on some day from today through days_ahead days from now
+fn upcoming_tasks(
+    plants: &[(String, PlantInfo)],
+    today: NaiveDate,
+    days_ahead: u64,
+    frost_date: NaiveDate,
+) -> Vec<(Task, &PlantInfo)> {
+    let frost_date = frost_date_in_year_of(frost_date, today);
+    let last_day = today + Days::new(days_ahead);
+    let mut tasks: Vec<(Task, &PlantInfo)> = plants
+        .iter()
+        .flat_map(|(name, info)| {
+            plant_tasks(name, info, frost_date)
+                .into_iter()
+                .map(move |task| (task, info))
+        })
+        .filter(|(task, _)| task.from <= last_day && task.until >= today)
+        .collect();
+    tasks.sort_by(|(a, _), (b, _)| a.from.cmp(&b.from).then(a.kind.cmp(&b.kind)));
+    tasks
+}
+
+fn describe(task: &Task) -> String {
+    if task.from == task.until {
+        format!(
+            "{} {} on {}",
+            task.kind,
+            task.plant,
+            task.from.format("%a %b %-d")
+        )
+    } else {
+        format!(
+            "{} {} any day from {} to {}",
+            task.kind,
+            task.plant,
+            task.from.format("%b %-d"),
+            task.until.format("%b %-d")
+        )
+    }
+}
+
+// Send a reminder for each upcoming task that hasn't had one yet
+pub fn send_reminders(
+    json_dir: &str,
+    today: NaiveDate,
+    days_ahead: u64,
+    frost_date: NaiveDate,
+    notifiers: &[Notifier],
+) -> Result<()> {
+    let plants = load_json_dir(json_dir)?;
+    let sent_path = Path::new(json_dir).join(SENT_REMINDERS_FILE);
+    let mut sent: BTreeSet<String> = fs::read_to_string(&sent_path)
+        .map(|content| content.lines().map(String::from).collect())
+        .unwrap_or_default();
+
+    let mut reminded = 0;
+    let mut undelivered = 0;
+    for (task, info) in upcoming_tasks(&plants, today, days_ahead, frost_date) {
+        let key = reminder_key(&task);
+        if sent.contains(&key) {
+            continue;
+        }
+        let delivered = notify_all(
+            notifiers,
+            &Notification {
+                title: format!("{}: {}", task.kind, task.plant),
+                message: describe(&task),
+                url: &info.url,
+            },
+        );
+        // Left unrecorded so the next run tries again
+        if !delivered {
+            undelivered += 1;
+            continue;
+        }
+        sent.insert(key);
+        println!("Reminded: {}", describe(&task));
+        reminded += 1;
+    }
+
+    // Reminders for tasks that are over can't come up again
+    sent.retain(|key| {
+        key.rsplit('|')
+            .next()
+            .and_then(|from| from.parse::<NaiveDate>().ok())
+            .is_some_and(|from| from + Days::new(365) > today)
+    });
+    let content: String = sent.iter().map(|key| format!("{}\n", key)).collect();
+    fs::write(&sent_path, content).context(format!("Failed to write {}", sent_path.display()))?;
+    if undelivered > 0 {
+        bail!(
+            "{} reminders couldn't be delivered on any channel",
+            undelivered
+        );
+    }
+    if reminded == 0 {
+        println!("No new tasks in the next {} days", days_ahead);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::TaskKind;
+
+    #[test]
+    fn test_upcoming_tasks() {
+        let carrot = PlantInfo {
+            when_to_sow_outside: Some(
+                "2 to 4 weeks before your average last frost date".to_string(),
+            ),
+            ..Default::default()
+        };
+        let plants = vec![("Carrot".to_string(), carrot)];
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        let day = |m, d| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+
+        // Sown Apr 12 to Apr 26
+        assert!(upcoming_tasks(&plants, day(4, 1), 3, frost_date).is_empty());
+        let tasks = upcoming_tasks(&plants, day(4, 9), 3, frost_date);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].0.kind, TaskKind::SowOutside);
+        assert_eq!(
+            describe(&tasks[0].0),
+            "Sow outside Carrot any day from Apr 12 to Apr 26"
+        );
+        assert_eq!(reminder_key(&tasks[0].0), "Sow outside|Carrot|2025-04-12");
+        assert!(upcoming_tasks(&plants, day(4, 27), 3, frost_date).is_empty());
+        let next_year = NaiveDate::from_ymd_opt(2026, 4, 9).unwrap();
+        assert_eq!(upcoming_tasks(&plants, next_year, 3, frost_date).len(), 1);
+    }
+}
@@ -0,0 +1,193 @@
+// Disease resistance codes from catalog text, e.g. "Disease resistance: V, F1, F2, N, TMV" or "(VFN)"
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+use crate::error::{compiled, regex, Pattern};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiseaseResistance {
+    pub code: String,
+    pub disease: String,
+}
+
+// Codes as seed catalogs print them; matching ignores case for anything not listed exactly
+const CODES: [(&str, &str); 33] = [
+    ("V", "Verticillium wilt"),
+    ("F", "Fusarium wilt"),
+    ("F1", "Fusarium wilt race 1"),
+    ("F2", "Fusarium wilt race 2"),
+    ("F3", "Fusarium wilt race 3"),
+    ("FOR", "Fusarium crown and root rot"),
+    ("N", "Root-knot nematodes"),
+    ("T", "Tobacco mosaic virus"),
+    ("TMV", "Tobacco mosaic virus"),
+    ("ToMV", "Tomato mosaic virus"),
+    ("TSWV", "Tomato spotted wilt virus"),
+    ("TYLCV", "Tomato yellow leaf curl virus"),
+    ("A", "Alternaria stem canker"),
+    ("ASC", "Alternaria stem canker"),
+    ("EB", "Early blight"),
+    ("LB", "Late blight"),
+    ("LM", "Leaf mold"),
+    ("GLS", "Gray leaf spot"),
+    ("St", "Gray leaf spot"),
+    ("BLS", "Bacterial leaf spot"),
+    ("PM", "Powdery mildew"),
+    ("DM", "Downy mildew"),
+    ("ALS", "Angular leaf spot"),
+    ("ANTH", "Anthracnose"),
+    ("CLS", "Cercospora leaf spot"),
+    ("CMV", "Cucumber mosaic virus"),
+    ("ZYMV", "Zucchini yellow mosaic virus"),
+    ("WMV", "Watermelon mosaic virus"),
+    ("PRSV", "Papaya ringspot virus"),
+    ("BCMV", "Bean common mosaic virus"),
+    ("CR", "Common rust"),
+    ("NCLB", "Northern corn leaf blight"),
+    ("SW", "Stewart's wilt"),
+];
+
+fn lookup(token: &str) -> Option<(&'static str, &'static str)> {
+    CODES
+        .iter()
+        .find(|(code, _)| *code == token)
+        .or_else(|| {
+            CODES
+                .iter()
+                .find(|(code, _)| code.len() > 1 && code.eq_ignore_ascii_case(token))
+        })
+        .copied()
+}
+
+// Expand a run of single-letter codes such as "VFN" or "VFFNT"; repeated Fs count races
+fn expand_compact(token: &str) -> Option<Vec<&'static str>> {
+    let is_compact = token.len() >= 2
+        && token.chars().all(|c| "VFNTA".contains(c))
+        && token.contains(['V', 'F']);
+    if !is_compact {
+        return None;
+    }
+
+    let races = token.matches('F').count();
+    let mut codes = Vec::new();
+    let mut races_added = false;
+    for c in token.chars() {
+        match c {
+            'V' => codes.push("V"),
+            'F' if races == 1 => codes.push("F"),
+            'F' if !races_added => {
+                codes.extend(["F1", "F2", "F3"].into_iter().take(races));
+                races_added = true;
+            }
+            'F' => {}
+            'N' => codes.push("N"),
+            'T' => codes.push("TMV"),
+            _ => codes.push("A"),
+        }
+    }
+    Some(codes)
+}
+
+// Every code in a list such as "V, F1/F2, N and TMV", or None if anything isn't a code
+fn parse_code_list(list: &str) -> Option<Vec<&'static str>> {
+    static SEPARATORS: Pattern<Regex> = LazyLock::new(|| regex(r"[,;/\s]+|\band\b"));
+    let mut codes = Vec::new();
+    for token in compiled(&SEPARATORS)
+        .ok()?
+        .split(list)
+        .filter(|token| !token.is_empty())
+    {
+        match lookup(token) {
+            Some((code, _)) => codes.push(code),
+            None => codes.extend(expand_compact(token)?),
+        }
+    }
+    (!codes.is_empty()).then_some(codes)
+}
+
+pub fn parse_disease_resistance(text: &str) -> Vec<DiseaseResistance> {
+    static LABELED: Pattern<Regex> =
+        LazyLock::new(|| regex(r"(?i)resist(?:ance|ant)(?:\s+to)?\s*:\s*([^.\n]+)"));
+    static PARENTHESIZED: Pattern<Regex> = LazyLock::new(|| regex(r"\(([^()]+)\)"));
+    static COMPACT: Pattern<Regex> = LazyLock::new(|| regex(r"\b[VFNTA]{2,6}\b"));
+    let (Ok(labeled), Ok(parenthesized), Ok(compact)) = (
+        compiled(&LABELED),
+        compiled(&PARENTHESIZED),
+        compiled(&COMPACT),
+    ) else {
+        return Vec::new();
+    };
+
+    let mut codes = Vec::new();
+    for cap in labeled.captures_iter(text) {
+        // Labeled lists may trail off into prose, so keep whatever codes lead them
+        let tokens: Vec<&str> = cap[1]
+            .split([',', ';', '/'])
+            .map(|token| token.trim().trim_matches(['(', ')']))
+            .collect();
+        for token in tokens {
+            match parse_code_list(token) {
+                Some(found) => codes.extend(found),
+                None => break,
+            }
+        }
+    }
+    for cap in parenthesized.captures_iter(text) {
+        codes.extend(parse_code_list(&cap[1]).unwrap_or_default());
+    }
+    for m in compact.find_iter(text) {
+        codes.extend(expand_compact(m.as_str()).unwrap_or_default());
+    }
+
+    let mut resistances: Vec<DiseaseResistance> = Vec::new();
+    for code in codes {
+        if resistances.iter().any(|r| r.code == code) {
+            continue;
+        }
+        if let Some((code, disease)) = lookup(code) {
+            resistances.push(DiseaseResistance {
+                code: code.to_string(),
+                disease: disease.to_string(),
+            });
+        }
+    }
+    resistances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(text: &str) -> Vec<String> {
+        parse_disease_resistance(text)
+            .into_iter()
+            .map(|r| r.code)
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_disease_resistance() {
+        assert_eq!(
+            codes("Disease resistance: V, F1, F2, N, TMV, LB. Great flavor."),
+            vec!["V", "F1", "F2", "N", "TMV", "LB"]
+        );
+        assert_eq!(codes("A reliable slicer (VFN)."), vec!["V", "F", "N"]);
+        assert_eq!(
+            codes("Better Boy VFFNT hybrid"),
+            vec!["V", "F1", "F2", "N", "TMV"]
+        );
+        assert_eq!(
+            parse_disease_resistance("Resistant to: PM")[0].disease,
+            "Powdery mildew"
+        );
+        assert_eq!(
+            codes("Resistant to cracks and splits."),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            codes("Plant in a sunny spot (zones 3-9)."),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -0,0 +1,188 @@
+// Crop rotation: keeping a plant family out of a bed it grew in recently
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeSet, HashMap};
+use tracing::warn;
+
+use crate::load_json_dir;
+
+// One row of the planting record: which bed a plant went in, in which year
+#[derive(Debug, Clone, Deserialize)]
+pub struct Planting {
+    #[serde(alias = "Year")]
+    pub year: i32,
+    #[serde(alias = "Bed")]
+    pub bed: String,
+    #[serde(alias = "Plant")]
+    pub plant: String,
+}
+
+#[derive(Debug)]
+pub struct Conflict<'a> {
+    pub planting: &'a Planting,
+    pub family: &'a str,
+    // The earlier plantings of the same family in the same bed
+    pub previous: Vec<&'a Planting>,
+}
+
+fn read_plantings(beds_file: &str) -> Result<Vec<Planting>> {
+    let mut reader = csv::Reader::from_path(beds_file)
+        .context(format!("Failed to read bed record: {}", beds_file))?;
+    let mut plantings = Vec::new();
+    for result in reader.deserialize() {
+        match result {
+            Ok(planting) => plantings.push(planting),
+            Err(e) => warn!("Error reading bed record: {}", e),
+        }
+    }
+    Ok(plantings)
+}
+
+// Whether a family grew in the bed in the years before `year`, up to `years` back
+fn grew_recently<'a>(
+    plantings: &'a [Planting],
+    families: &HashMap<String, String>,
+    bed: &str,
+    family: &str,
+    year: i32,
+    years: i32,
+) -> Vec<&'a Planting> {
+    plantings
+        .iter()
+        .filter(|p| p.bed == bed && p.year < year && p.year >= year - years)
+        .filter(|p| families.get(&p.plant.to_lowercase()).map(String::as_str) == Some(family))
+        .collect()
+}
+
+// Plantings in `year` whose family already grew in the same bed within `years` years;
+// families are keyed by lowercased plant name
+pub fn find_conflicts<'a>(
+    plantings: &'a [Planting],
+    families: &'a HashMap<String, String>,
+    year: i32,
+    years: i32,
+) -> Vec<Conflict<'a>> {
+    plantings
+        .iter()
+        .filter(|p| p.year == year)
+        .filter_map(|planting| {
+            let family = families.get(&planting.plant.to_lowercase())?;
+            let previous = grew_recently(plantings, families, &planting.bed, family, year, years);
+            (!previous.is_empty()).then_some(Conflict {
+                planting,
+                family,
+                previous,
+            })
+        })
+        .collect()
+}
+
+// Beds from the record where a family hasn't grown within `years` years
+pub fn safe_beds<'a>(
+    plantings: &'a [Planting],
+    families: &HashMap<String, String>,
+    family: &str,
+    year: i32,
+    years: i32,
+) -> Vec<&'a str> {
+    let beds: BTreeSet<&str> = plantings.iter().map(|p| p.bed.as_str()).collect();
+    beds.into_iter()
+        .filter(|bed| grew_recently(plantings, families, bed, family, year, years).is_empty())
+        .collect()
+}
+
+// Warn about families repeating in a bed within `years` years of `year`, the latest year
+// in the record by default, and suggest beds they could go in instead
+pub fn rotate(json_dir: &str, beds_file: &str, year: Option<i32>, years: i32) -> Result<()> {
+    let plantings = read_plantings(beds_file)?;
+    let Some(year) = year.or_else(|| plantings.iter().map(|p| p.year).max()) else {
+        bail!("No plantings in {}", beds_file);
+    };
+
+    let families: HashMap<String, String> = load_json_dir(json_dir)?
+        .into_iter()
+        .filter_map(|(name, info)| Some((name.to_lowercase(), info.family?.trim().to_string())))
+        .collect();
+    for planting in plantings.iter().filter(|p| p.year == year) {
+        if !families.contains_key(&planting.plant.to_lowercase()) {
+            warn!("No family known for {}, skipping it", planting.plant);
+        }
+    }
+
+    let conflicts = find_conflicts(&plantings, &families, year, years);
+    for conflict in &conflicts {
+        let previous: Vec<String> = conflict
+            .previous
+            .iter()
+            .map(|p| format!("{} in {}", p.plant, p.year))
+            .collect();
+        println!(
+            "Warning: {} ({}) in {} for {}: {} grew there within {} years ({})",
+            conflict.planting.plant,
+            conflict.family,
+            conflict.planting.bed,
+            year,
+            conflict.family,
+            years,
+            previous.join(", ")
+        );
+        match safe_beds(&plantings, &families, conflict.family, year, years)[..] {
+            [] => println!("  No bed has been free of {} that long", conflict.family),
+            ref beds => println!("  Try instead: {}", beds.join(", ")),
+        }
+    }
+
+    println!(
+        "\nChecked {} plantings for {}: {} rotation conflicts",
+        plantings.iter().filter(|p| p.year == year).count(),
+        year,
+        conflicts.len()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planting(year: i32, bed: &str, plant: &str) -> Planting {
+        Planting {
+            year,
+            bed: bed.to_string(),
+            plant: plant.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_find_conflicts() {
+        let plantings = vec![
+            planting(2023, "Bed 1", "Tomato"),
+            planting(2024, "Bed 2", "Pepper"),
+            planting(2024, "Bed 3", "Carrot"),
+            planting(2025, "Bed 1", "Potato"),
+            planting(2025, "Bed 2", "Bean"),
+        ];
+        let families: HashMap<String, String> = [
+            ("tomato", "Solanaceae"),
+            ("pepper", "Solanaceae"),
+            ("potato", "Solanaceae"),
+            ("carrot", "Apiaceae"),
+            ("bean", "Fabaceae"),
+        ]
+        .into_iter()
+        .map(|(plant, family)| (plant.to_string(), family.to_string()))
+        .collect();
+
+        let conflicts = find_conflicts(&plantings, &families, 2025, 3);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].planting.plant, "Potato");
+        assert_eq!(conflicts[0].previous[0].plant, "Tomato");
+
+        // Bed 2 had peppers the year before; only Bed 3 has been free of nightshades
+        assert_eq!(
+            safe_beds(&plantings, &families, "Solanaceae", 2025, 3),
+            vec!["Bed 3"]
+        );
+        assert!(find_conflicts(&plantings, &families, 2025, 1).is_empty());
+    }
+}
@@ -0,0 +1,78 @@
+// Versioning for stored plant JSON, so older results directories keep loading
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+
+use crate::error::ScrapingError;
+use crate::PlantInfo;
+
+// Bump when a PlantInfo field is renamed or reshaped, and add an upgrade step below
+pub const SCHEMA_VERSION: u32 = 1;
+
+// Upgrade a stored record by one version, from `version` to `version + 1`
+fn upgrade_step(version: u32, record: &mut Value) -> Result<()> {
+    match version {
+        // Version 1 only introduced the schema_version field itself
+        0 => {}
+        _ => return Err(anyhow!("No upgrade from schema version {}", version)),
+    }
+    record["schema_version"] = json!(version + 1);
+    Ok(())
+}
+
+// Bring a stored record up to the current schema version
+pub fn upgrade(mut record: Value) -> Result<Value> {
+    if !record.is_object() {
+        return Err(anyhow!("Plant JSON must be an object"));
+    }
+    let mut version = record
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as u32;
+    if version > SCHEMA_VERSION {
+        return Err(anyhow!(
+            "Schema version {} is newer than this version of the tool supports ({})",
+            version,
+            SCHEMA_VERSION
+        ));
+    }
+
+    while version < SCHEMA_VERSION {
+        upgrade_step(version, &mut record)?;
+        version += 1;
+    }
+    Ok(record)
+}
+
+// Parse a stored plant JSON file, upgrading it from older schema versions
+pub fn parse_plant_json(content: &str) -> Result<PlantInfo, ScrapingError> {
+    let record: Value =
+        serde_json::from_str(content).map_err(|e| ScrapingError::Parse(e.to_string()))?;
+    let record = upgrade(record).map_err(|e| ScrapingError::Parse(e.to_string()))?;
+    let mut info: PlantInfo = serde_json::from_value(record).map_err(|e| {
+        ScrapingError::Parse(format!("JSON does not match the plant schema: {}", e))
+    })?;
+    info.fill_derived_fields();
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upgrade_unversioned_record() {
+        let info =
+            parse_plant_json(r#"{"url": "http://example.com", "family": "Apiaceae"}"#).unwrap();
+        assert_eq!(info.schema_version, SCHEMA_VERSION);
+        assert_eq!(info.family.as_deref(), Some("Apiaceae"));
+    }
+
+    #[test]
+    fn test_reject_newer_schema() {
+        let content = format!(
+            r#"{{"schema_version": {}, "url": "http://example.com"}}"#,
+            SCHEMA_VERSION + 1
+        );
+        assert!(parse_plant_json(&content).is_err());
+    }
+}
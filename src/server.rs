@@ -0,0 +1,145 @@
+// Server mode: the collection over HTTP, as a GraphQL endpoint and an Atom feed
+use anyhow::{anyhow, Result};
+use async_graphql::http::GraphiQLSource;
+use chrono::NaiveDate;
+use tiny_http::{Header, Method, Request, Response, Server};
+use tracing::error;
+
+use crate::graphql::{build_schema, CollectionSchema};
+use crate::{feed, load_json_dir};
+
+fn respond(body: String, content_type: &str, status: u16) -> Response<std::io::Cursor<Vec<u8>>> {
+    let header = Header::from_bytes("Content-Type", content_type).unwrap();
+    Response::from_string(body)
+        .with_header(header)
+        .with_status_code(status)
+}
+
+// Where the feed is built from; it's rebuilt on every request so watch's changes show up
+struct FeedSource<'a> {
+    json_dir: &'a str,
+    history_file: &'a str,
+}
+
+fn handle(
+    schema: &CollectionSchema,
+    feed_source: &FeedSource,
+    request: &mut Request,
+) -> Response<std::io::Cursor<Vec<u8>>> {
+    let path = request.url().split('?').next().unwrap_or_default();
+    match (request.method(), path) {
+        (Method::Get, "/graphql") => respond(
+            GraphiQLSource::build().endpoint("/graphql").finish(),
+            "text/html; charset=utf-8",
+            200,
+        ),
+        (Method::Post, "/graphql") => {
+            let mut body = String::new();
+            if let Err(e) = request.as_reader().read_to_string(&mut body) {
+                return respond(e.to_string(), "text/plain", 400);
+            }
+            let graphql_request: async_graphql::Request = match serde_json::from_str(&body) {
+                Ok(graphql_request) => graphql_request,
+                Err(e) => {
+                    return respond(format!("Invalid GraphQL request: {}", e), "text/plain", 400)
+                }
+            };
+            let response = pollster::block_on(schema.execute(graphql_request));
+            match serde_json::to_string(&response) {
+                Ok(json) => respond(json, "application/json", 200),
+                Err(e) => respond(e.to_string(), "text/plain", 500),
+            }
+        }
+        (Method::Get, "/feed.atom") => {
+            match feed::collection_feed(feed_source.json_dir, feed_source.history_file) {
+                Ok(feed) => respond(feed, "application/atom+xml; charset=utf-8", 200),
+                Err(e) => respond(e.to_string(), "text/plain", 500),
+            }
+        }
+        _ => respond("Not found".to_string(), "text/plain", 404),
+    }
+}
+
+// Serve the collection until interrupted; the plants are loaded once at startup
+pub fn serve(
+    json_dir: &str,
+    frost_date: NaiveDate,
+    host: &str,
+    port: u16,
+    history_file: &str,
+) -> Result<()> {
+    let plants = load_json_dir(json_dir)?;
+    let count = plants.len();
+    let schema = build_schema(plants, frost_date);
+    let feed_source = FeedSource {
+        json_dir,
+        history_file,
+    };
+
+    let server = Server::http((host, port))
+        .map_err(|e| anyhow!("Failed to listen on {}:{}: {}", host, port, e))?;
+    println!(
+        "Serving {} plants at http://{}:{}/graphql",
+        count, host, port
+    );
+
+    for mut request in server.incoming_requests() {
+        let response = handle(&schema, &feed_source, &mut request);
+        if let Err(e) = request.respond(response) {
+            error!("Failed to send response: {}", e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PlantInfo;
+    use std::io::Read;
+    use tiny_http::TestRequest;
+
+    #[test]
+    fn test_handle() {
+        let carrot = PlantInfo {
+            family: Some("Apiaceae".to_string()),
+            ..Default::default()
+        };
+        let schema = build_schema(
+            vec![("Carrot".to_string(), carrot)],
+            NaiveDate::from_ymd_opt(2025, 5, 10).unwrap(),
+        );
+        let feed_source = FeedSource {
+            json_dir: "",
+            history_file: "",
+        };
+        let send = |request: TestRequest| {
+            let response = handle(&schema, &feed_source, &mut request.into());
+            let status = response.status_code().0;
+            let mut body = String::new();
+            response.into_reader().read_to_string(&mut body).unwrap();
+            (status, body)
+        };
+
+        let (status, body) = send(
+            TestRequest::new()
+                .with_method(Method::Post)
+                .with_path("/graphql")
+                .with_body(r#"{"query": "{ plant(name: \"carrot\") { info { family } } }"}"#),
+        );
+        assert_eq!(status, 200);
+        assert_eq!(body, r#"{"data":{"plant":{"info":{"family":"Apiaceae"}}}}"#);
+
+        let (status, _) = send(
+            TestRequest::new()
+                .with_method(Method::Post)
+                .with_path("/graphql")
+                .with_body("not json"),
+        );
+        assert_eq!(status, 400);
+        let (status, body) = send(TestRequest::new().with_path("/graphql?x=1"));
+        assert_eq!(status, 200);
+        assert!(body.contains("GraphiQL"));
+        assert_eq!(send(TestRequest::new().with_path("/missing")).0, 404);
+    }
+}
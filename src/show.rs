@@ -0,0 +1,194 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::NaiveDate;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::IsTerminal;
+
+use crate::names::PlantIndex;
+use crate::{format_sowing_time, load_json_dir, schema, PlantExport, PlantInfo};
+
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const GREEN: &str = "\x1b[32m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+struct Painter {
+    color: bool,
+}
+
+impl Painter {
+    fn paint(&self, codes: &str, text: &str) -> String {
+        if self.color {
+            format!("{}{}{}", codes, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+// Find the plant's JSON file, falling back to a case-insensitive match on its name
+pub fn find_plant(json_dir: &str, plant: &str) -> Result<(String, PlantInfo)> {
+    let path = PlantIndex::load(json_dir)?.path_for(plant);
+    if let Ok(content) = fs::read_to_string(&path) {
+        let info =
+            schema::parse_plant_json(&content).context(format!("Failed to parse {}", path))?;
+        return Ok((plant.to_string(), info));
+    }
+
+    load_json_dir(json_dir)?
+        .into_iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(plant))
+        .ok_or_else(|| anyhow!("No plant named {} in {}", plant, json_dir))
+}
+
+// A titled group of labeled values; rows without a value are left out
+type Section<'a> = (String, Vec<(&'a str, Option<String>)>);
+
+fn render(name: &str, plant: &PlantExport, frost_date: NaiveDate, painter: &Painter) -> String {
+    let info = &plant.info;
+    let date = |d: Option<NaiveDate>| d.map(|d| d.format("%b %-d, %Y").to_string());
+    let mut out = String::new();
+
+    let title = info.title.as_deref().unwrap_or(name);
+    let _ = writeln!(
+        out,
+        "{}",
+        painter.paint(&format!("{}{}", BOLD, GREEN), title)
+    );
+    if let Some(botanical_name) = &info.botanical_name {
+        let _ = writeln!(out, "{}", painter.paint(DIM, botanical_name));
+    }
+    let _ = writeln!(out, "{}", painter.paint(DIM, &info.url));
+
+    let start_window = match (plant.dates.earliest_start, plant.dates.latest_start) {
+        (Some(earliest), Some(latest)) if earliest != latest => Some(format!(
+            "{} to {}",
+            date(Some(earliest)).unwrap_or_default(),
+            date(Some(latest)).unwrap_or_default()
+        )),
+        _ => date(plant.dates.start),
+    };
+    let tags = (!info.tags.is_empty()).then(|| info.tags.join(", "));
+    let sections: [Section; 4] = [
+        (
+            "Growing".to_string(),
+            vec![
+                ("Family", info.family.clone()),
+                ("Type", info.plant_type.clone()),
+                ("Days to Maturity", info.days_to_maturity.clone()),
+                ("Exposure", info.exposure.clone()),
+                ("Hardiness", info.hardiness.clone()),
+                ("Dimensions", info.plant_dimensions.clone()),
+                ("Attributes", tags),
+            ],
+        ),
+        (
+            "Sowing".to_string(),
+            vec![
+                ("Sow Outside", info.when_to_sow_outside.clone()),
+                ("Start Inside", info.when_to_start_inside.clone()),
+                ("Days to Emerge", info.days_to_emerge.clone()),
+                ("Seed Depth", info.seed_depth.clone()),
+                ("Seed Spacing", info.seed_spacing.clone()),
+                ("Row Spacing", info.row_spacing.clone()),
+                ("Thinning", info.thinning.clone()),
+            ],
+        ),
+        (
+            format!(
+                "Schedule (last frost {})",
+                date(Some(frost_date)).unwrap_or_default()
+            ),
+            vec![
+                ("Strategy", plant.sowing_strategy.map(|s| s.to_string())),
+                (
+                    "Start",
+                    plant
+                        .when_to_start
+                        .first()
+                        .map(|t| format_sowing_time(Some(t))),
+                ),
+                ("Start Date", start_window),
+                ("Transplant", date(plant.dates.transplant)),
+                ("First Harvest", date(plant.dates.first_harvest)),
+                ("Last Harvest", date(plant.dates.last_harvest)),
+            ],
+        ),
+        (
+            "Store".to_string(),
+            vec![
+                ("Price", info.price.map(|price| format!("${:.2}", price))),
+                (
+                    "In Stock",
+                    info.in_stock
+                        .map(|in_stock| if in_stock { "Yes" } else { "No" }.to_string()),
+                ),
+            ],
+        ),
+    ];
+
+    let width = sections
+        .iter()
+        .flat_map(|(_, rows)| rows.iter().map(|(label, _)| label.len()))
+        .max()
+        .unwrap_or(0);
+    for (heading, rows) in &sections {
+        let rows: Vec<_> = rows
+            .iter()
+            .filter_map(|(label, value)| value.as_ref().map(|value| (label, value)))
+            .collect();
+        if rows.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out, "\n{}", painter.paint(BOLD, heading));
+        for (label, value) in rows {
+            let label = format!("{:<width$}", label, width = width);
+            let _ = writeln!(out, "  {}  {}", painter.paint(CYAN, &label), value);
+        }
+    }
+
+    out
+}
+
+// Print a readable summary of one plant with its schedule for the given frost date
+// The summary without escape codes, for display somewhere other than a terminal
+pub fn render_plain(name: &str, plant: &PlantExport, frost_date: NaiveDate) -> String {
+    render(name, plant, frost_date, &Painter { color: false })
+}
+
+pub fn show_plant(json_dir: &str, plant: &str, frost_date: NaiveDate) -> Result<()> {
+    let (name, info) = find_plant(json_dir, plant)?;
+    let plant = PlantExport::new(info, None, frost_date);
+    // Respect https://no-color.org and skip escape codes when piped
+    let painter = Painter {
+        color: std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+    };
+    print!("{}", render(&name, &plant, frost_date, &painter));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let info: PlantInfo = serde_json::from_str(
+            r#"{"url": "http://example.com", "title": "Danvers 126 Carrot Seeds",
+                "days_to_maturity": "65 days",
+                "when_to_sow_outside": "RECOMMENDED. 2 to 4 weeks before your average last frost date"}"#,
+        )
+        .unwrap();
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 10).unwrap();
+        let plant = PlantExport::new(info, None, frost_date);
+        let text = render("Carrot", &plant, frost_date, &Painter { color: false });
+
+        assert!(text.starts_with("Danvers 126 Carrot Seeds\nhttp://example.com\n"));
+        assert!(text.contains("\nSchedule (last frost May 10, 2025)\n"));
+        assert!(text.contains("  Strategy          Outside\n"));
+        assert!(text.contains("  Start Date        Apr 12, 2025 to Apr 26, 2025\n"));
+        assert!(!text.contains("Store"));
+        assert!(!text.contains('\x1b'));
+    }
+}
@@ -0,0 +1,114 @@
+// Square-foot garden layout from a plant's final spacing
+use crate::PlantInfo;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BedSize {
+    pub width_ft: f32,
+    pub length_ft: f32,
+}
+
+// Parse a bed size in feet such as "4x8"
+pub fn parse_bed_size(text: &str) -> Result<BedSize, String> {
+    let (width, length) = text
+        .split_once(['x', 'X'])
+        .ok_or_else(|| format!("expected WIDTHxLENGTH in feet, got \"{}\"", text))?;
+    let width_ft: f32 = width.trim().parse().map_err(|_| "invalid bed width")?;
+    let length_ft: f32 = length.trim().parse().map_err(|_| "invalid bed length")?;
+    if width_ft <= 0.0 || length_ft <= 0.0 {
+        return Err("bed must be wider and longer than zero".to_string());
+    }
+    Ok(BedSize {
+        width_ft,
+        length_ft,
+    })
+}
+
+// Plants on a square grid at the given spacing; under one means a plant needs several squares
+pub fn plants_per_sq_ft(spacing_in: f32) -> f32 {
+    let per_side = 12.0 / spacing_in;
+    (per_side * per_side * 100.0).round() / 100.0
+}
+
+// Whole plants that fit on a square grid across the bed, never fewer than one
+pub fn plants_per_bed(spacing_in: f32, bed: BedSize) -> u32 {
+    let across = (bed.width_ft * 12.0 / spacing_in).floor().max(1.0);
+    let along = (bed.length_ft * 12.0 / spacing_in).floor().max(1.0);
+    (across * along) as u32
+}
+
+// Whole plants along a single row of the given length
+pub fn plants_per_row(spacing_in: f32, row_length_ft: f32) -> u32 {
+    (row_length_ft * 12.0 / spacing_in).floor().max(1.0) as u32
+}
+
+// Seeds sown for each plant that's kept: seeds go in at the seed spacing and are
+// thinned out to the plant spacing
+pub fn seeds_per_plant(info: &PlantInfo) -> u32 {
+    match (info.plant_spacing_in, info.seed_spacing_min_in) {
+        (Some(plant), Some(seed)) if seed > 0.0 && plant > seed => {
+            // Tolerate float noise so 3" over 1" doesn't become 4 seeds
+            (plant / seed - 0.01).ceil() as u32
+        }
+        _ => 1,
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Layout {
+    pub plants_per_sq_ft: Option<f32>,
+    pub plants_per_bed: Option<u32>,
+    pub seeds_needed: Option<u32>,
+}
+
+// Layout columns for a plant; seeds are counted for the row when given, otherwise the bed
+pub fn layout(info: &PlantInfo, bed: Option<BedSize>, row_length_ft: Option<f32>) -> Layout {
+    let Some(spacing) = info.plant_spacing_in.filter(|spacing| *spacing > 0.0) else {
+        return Layout::default();
+    };
+    let plants_per_bed = bed.map(|bed| plants_per_bed(spacing, bed));
+    let plants = row_length_ft
+        .map(|length| plants_per_row(spacing, length))
+        .or(plants_per_bed);
+    Layout {
+        plants_per_sq_ft: Some(plants_per_sq_ft(spacing)),
+        plants_per_bed,
+        seeds_needed: plants.map(|plants| plants * seeds_per_plant(info)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plant_density() {
+        assert_eq!(plants_per_sq_ft(3.0), 16.0);
+        assert_eq!(plants_per_sq_ft(18.0), 0.44);
+
+        let bed = parse_bed_size("4x8").unwrap();
+        assert_eq!(plants_per_bed(3.0, bed), 16 * 32);
+        assert_eq!(plants_per_bed(18.0, bed), 2 * 5);
+        assert_eq!(plants_per_bed(60.0, bed), 1);
+        assert!(parse_bed_size("4 by 8").is_err());
+    }
+
+    #[test]
+    fn test_seeds_needed() {
+        // Sown 1" apart and thinned to one every 3"
+        let carrot = PlantInfo {
+            seed_spacing_min_in: Some(1.0),
+            plant_spacing_in: Some(3.0),
+            ..Default::default()
+        };
+        assert_eq!(seeds_per_plant(&carrot), 3);
+        assert_eq!(layout(&carrot, None, Some(10.0)).seeds_needed, Some(120));
+
+        let bed = BedSize {
+            width_ft: 1.0,
+            length_ft: 1.0,
+        };
+        let layout = layout(&carrot, Some(bed), None);
+        assert_eq!(layout.plants_per_bed, Some(16));
+        assert_eq!(layout.seeds_needed, Some(48));
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::Result;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+use crate::{determine_sowing_strategy, load_json_dir, OutputFormat, PlantInfo};
+
+// A field that's missing from a plant, by name
+type MissingCheck = (&'static str, fn(&PlantInfo) -> bool);
+
+// Width in days of each bar in the maturity histogram
+const MATURITY_BUCKET_DAYS: u32 = 15;
+const BAR_WIDTH: usize = 40;
+
+fn count_by(values: impl Iterator<Item = Option<String>>) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for value in values {
+        *counts
+            .entry(value.unwrap_or_else(|| "(unknown)".to_string()))
+            .or_insert(0) += 1;
+    }
+    counts
+}
+
+// Plants per maturity bucket, keyed by each bucket's first day; a range counts at its midpoint
+fn maturity_histogram(plants: &[(String, PlantInfo)]) -> BTreeMap<u32, usize> {
+    let mut buckets = BTreeMap::new();
+    for (_, info) in plants {
+        if let Some((min, max)) = info.maturity_days() {
+            let bucket = (min + max) / 2 / MATURITY_BUCKET_DAYS * MATURITY_BUCKET_DAYS;
+            *buckets.entry(bucket).or_insert(0) += 1;
+        }
+    }
+    buckets
+}
+
+fn print_counts(heading: &str, counts: &BTreeMap<String, usize>) {
+    println!("\n{}", heading);
+    let mut counts: Vec<_> = counts.iter().collect();
+    // Most common first, then alphabetically
+    counts.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    let width = counts.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    for (name, count) in counts {
+        println!("  {:<width$}  {}", name, count, width = width);
+    }
+}
+
+// Print counts by family, type, and strategy, a maturity histogram, and missing-field totals
+pub fn print_stats(json_dir: &str, output: OutputFormat) -> Result<()> {
+    let plants = load_json_dir(json_dir)?;
+    let by_family = count_by(plants.iter().map(|(_, info)| info.family.clone()));
+    let by_plant_type = count_by(plants.iter().map(|(_, info)| info.plant_type.clone()));
+    let by_strategy = count_by(
+        plants
+            .iter()
+            .map(|(_, info)| determine_sowing_strategy(info, None).map(|s| s.to_string())),
+    );
+    let histogram = maturity_histogram(&plants);
+
+    let missing_checks: [MissingCheck; 6] = [
+        ("Family", |info| info.family.is_none()),
+        ("Days to maturity", |info| info.maturity_days().is_none()),
+        ("Sowing info", |info| {
+            info.when_to_sow_outside.is_none() && info.when_to_start_inside.is_none()
+        }),
+        ("Seed depth", |info| info.seed_depth.is_none()),
+        ("Seed spacing", |info| info.seed_spacing.is_none()),
+        ("Exposure", |info| info.exposure.is_none()),
+    ];
+    let missing = missing_checks.map(|(field, is_missing)| {
+        let count = plants.iter().filter(|(_, info)| is_missing(info)).count();
+        (field, count)
+    });
+
+    if output == OutputFormat::Json {
+        return output.print_json(&json!({
+            "plants": plants.len(),
+            "json_dir": json_dir,
+            "by_family": by_family,
+            "by_plant_type": by_plant_type,
+            "by_sowing_strategy": by_strategy,
+            "maturity_days": histogram
+                .iter()
+                .map(|(start, count)| json!({
+                    "from": start,
+                    "to": start + MATURITY_BUCKET_DAYS - 1,
+                    "plants": count,
+                }))
+                .collect::<Vec<_>>(),
+            "missing_fields": BTreeMap::from(missing),
+        }));
+    }
+
+    println!("{} plants in {}", plants.len(), json_dir);
+    if plants.is_empty() {
+        return Ok(());
+    }
+
+    print_counts("By family", &by_family);
+    print_counts("By plant type", &by_plant_type);
+    print_counts("By sowing strategy", &by_strategy);
+
+    let largest = histogram.values().copied().max().unwrap_or(1);
+    println!("\nDays to maturity");
+    for (start, count) in &histogram {
+        let bar = "#".repeat((count * BAR_WIDTH).div_ceil(largest));
+        println!(
+            "  {:>3}-{:<3}  {:>3}  {}",
+            start,
+            start + MATURITY_BUCKET_DAYS - 1,
+            count,
+            bar
+        );
+    }
+
+    println!("\nMissing fields");
+    for (field, count) in missing {
+        println!("  {:<16}  {}", field, count);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maturity_histogram() {
+        let plant = |days: &str| {
+            let info = PlantInfo {
+                days_to_maturity: Some(days.to_string()),
+                ..Default::default()
+            };
+            ("Plant".to_string(), info)
+        };
+        let plants = [plant("65 days"), plant("55-70 days"), plant("90 days")];
+        assert_eq!(
+            maturity_histogram(&plants),
+            BTreeMap::from([(60, 2), (90, 1)])
+        );
+    }
+}
@@ -0,0 +1,213 @@
+// A week's garden to-do list, from each plant's calculated dates
+use anyhow::Result;
+use chrono::{Datelike, Days, Local, NaiveDate};
+use tracing::warn;
+
+use crate::{
+    calculate_dates, determine_sowing_strategy, get_when_to_seed_start, load_json_dir, weather,
+    PlantInfo, SowingStrategy,
+};
+
+// Seedlings started indoors move to bigger pots once they have true leaves
+const POT_UP_AFTER_DAYS: u64 = 21;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TaskKind {
+    StartIndoors,
+    SowOutside,
+    PotUp,
+    Transplant,
+}
+
+impl std::fmt::Display for TaskKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            TaskKind::StartIndoors => "Start indoors",
+            TaskKind::SowOutside => "Sow outside",
+            TaskKind::PotUp => "Pot up",
+            TaskKind::Transplant => "Transplant",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+// Something to do for a plant on any day from `from` through `until`
+#[derive(Debug, PartialEq)]
+pub struct Task {
+    pub kind: TaskKind,
+    pub plant: String,
+    pub from: NaiveDate,
+    pub until: NaiveDate,
+}
+
+impl Task {
+    // The day, or the span of days, to do it, e.g. "Mon Apr 21" or "Apr 12 - Apr 26"
+    pub fn when(&self) -> String {
+        if self.from == self.until {
+            self.from.format("%a %b %-d").to_string()
+        } else {
+            format!(
+                "{} - {}",
+                self.from.format("%b %-d"),
+                self.until.format("%b %-d")
+            )
+        }
+    }
+}
+
+pub fn plant_tasks(name: &str, info: &PlantInfo, frost_date: NaiveDate) -> Vec<Task> {
+    let sowing_strategy = determine_sowing_strategy(info, None);
+    let when_to_start = get_when_to_seed_start(info, None);
+    let dates = calculate_dates(info, sowing_strategy, &when_to_start, frost_date);
+    let task = |kind, from, until| Task {
+        kind,
+        plant: name.to_string(),
+        from,
+        until,
+    };
+    let mut tasks = Vec::new();
+
+    if let Some(start) = dates.start {
+        let kind = match sowing_strategy {
+            Some(SowingStrategy::Inside) => TaskKind::StartIndoors,
+            _ => TaskKind::SowOutside,
+        };
+        // Anywhere in the sowing window will do
+        let from = dates.earliest_start.unwrap_or(start);
+        let until = dates.latest_start.unwrap_or(start);
+        tasks.push(task(kind, from, until));
+    }
+
+    if let (Some(start), Some(transplant)) = (dates.start, dates.transplant) {
+        let pot_up = start + Days::new(POT_UP_AFTER_DAYS);
+        // Not worth it for seedlings going out within a week or so
+        if pot_up + Days::new(7) < transplant {
+            tasks.push(task(TaskKind::PotUp, pot_up, pot_up));
+        }
+        tasks.push(task(TaskKind::Transplant, transplant, transplant));
+    }
+
+    tasks
+}
+
+// The frost date moved to the given day's year, so a default or configured date from an
+// earlier season keeps working for cron jobs set up then. Feb 29 falls back to Feb 28.
+pub fn frost_date_in_year_of(frost_date: NaiveDate, day: NaiveDate) -> NaiveDate {
+    if frost_date.year() == day.year() {
+        return frost_date;
+    }
+    frost_date
+        .with_year(day.year())
+        .or_else(|| NaiveDate::from_ymd_opt(day.year(), frost_date.month(), 28))
+        .unwrap_or(frost_date)
+}
+
+// Tasks due at some point during the seven days from `week_start`, by kind then plant
+pub fn tasks_for_week(
+    plants: &[(String, PlantInfo)],
+    week_start: NaiveDate,
+    frost_date: NaiveDate,
+) -> Vec<Task> {
+    let frost_date = frost_date_in_year_of(frost_date, week_start);
+    let week_end = week_start + Days::new(6);
+    let mut tasks: Vec<Task> = plants
+        .iter()
+        .flat_map(|(name, info)| plant_tasks(name, info, frost_date))
+        .filter(|task| task.from <= week_end && task.until >= week_start)
+        .collect();
+    tasks.sort_by(|a, b| a.kind.cmp(&b.kind).then(a.from.cmp(&b.from)));
+    tasks
+}
+
+// Monday of the current week
+pub fn this_week() -> NaiveDate {
+    let today = Local::now().date_naive();
+    today - Days::new(u64::from(today.weekday().num_days_from_monday()))
+}
+
+// With a location, outdoor tasks are also checked against the weather forecast
+pub fn print_tasks(
+    json_dir: &str,
+    week_start: NaiveDate,
+    frost_date: NaiveDate,
+    location: Option<(f64, f64)>,
+) -> Result<()> {
+    let plants = load_json_dir(json_dir)?;
+    // The to-do list is still worth printing without it
+    let forecast = match location.map(weather::fetch_forecast) {
+        Some(Ok(forecast)) => forecast,
+        Some(Err(e)) => {
+            warn!("Failed to fetch the weather forecast: {:#}", e);
+            Vec::new()
+        }
+        None => Vec::new(),
+    };
+    let tasks = tasks_for_week(&plants, week_start, frost_date);
+    let week_end = week_start + Days::new(6);
+    println!(
+        "Week of {} to {}",
+        week_start.format("%b %-d"),
+        week_end.format("%b %-d, %Y")
+    );
+
+    if tasks.is_empty() {
+        println!("\nNothing to do this week");
+        return Ok(());
+    }
+
+    let mut current = None;
+    for task in &tasks {
+        if current != Some(task.kind) {
+            println!("\n{}:", task.kind);
+            current = Some(task.kind);
+        }
+        println!("  {} ({})", task.plant, task.when());
+        if let Some((_, info)) = plants.iter().find(|(name, _)| *name == task.plant) {
+            for warning in weather::task_warnings(task, info, &forecast) {
+                println!("    ! {}", warning);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tasks_for_week() {
+        let tomato = PlantInfo {
+            when_to_start_inside: Some(
+                "RECOMMENDED. Sow indoors 6 to 8 weeks before your average last frost date."
+                    .to_string(),
+            ),
+            ..Default::default()
+        };
+        let plants = vec![("Tomato".to_string(), tomato)];
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 15).unwrap();
+        let week = |m, d| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+
+        let kinds = |start| -> Vec<TaskKind> {
+            tasks_for_week(&plants, start, frost_date)
+                .iter()
+                .map(|task| task.kind)
+                .collect()
+        };
+        // Sown Mar 20 to Apr 3, potted up Apr 24, and transplanted three weeks after frost
+        assert_eq!(kinds(week(3, 17)), vec![TaskKind::StartIndoors]);
+        assert_eq!(kinds(week(4, 21)), vec![TaskKind::PotUp]);
+        assert_eq!(kinds(week(5, 12)), Vec::<TaskKind>::new());
+        assert_eq!(kinds(week(6, 2)), vec![TaskKind::Transplant]);
+
+        // A frost date from last season moves to this one
+        let next_week = |m, d| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+        let tasks = tasks_for_week(&plants, next_week(3, 16), frost_date);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].from.year(), 2026);
+        assert_eq!(
+            frost_date_in_year_of(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(), week(3, 1)),
+            week(2, 28)
+        );
+    }
+}
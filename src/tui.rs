@@ -0,0 +1,268 @@
+// Terminal browser for the collection: a searchable plant list with details or a sowing calendar
+use anyhow::Result;
+use chrono::{Datelike, NaiveDate};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::{load_json_dir, show, PlantExport};
+
+// Characters per month in the calendar view, so each is roughly a week
+const WEEKS_PER_MONTH: usize = 4;
+
+#[derive(Clone, Copy, PartialEq)]
+enum View {
+    Details,
+    Calendar,
+}
+
+struct App {
+    plants: Vec<(String, PlantExport)>,
+    frost_date: NaiveDate,
+    query: String,
+    searching: bool,
+    view: View,
+    // Indices into `plants` that match the query
+    visible: Vec<usize>,
+    list_state: ListState,
+}
+
+// Plants whose name, title, or family contains the query, ignoring case
+fn matching_plants(plants: &[(String, PlantExport)], query: &str) -> Vec<usize> {
+    let query = query.to_lowercase();
+    plants
+        .iter()
+        .enumerate()
+        .filter(|(_, (name, plant))| {
+            [
+                Some(name),
+                plant.info.title.as_ref(),
+                plant.info.family.as_ref(),
+            ]
+            .into_iter()
+            .flatten()
+            .any(|field| field.to_lowercase().contains(&query))
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+// Position of a date on the calendar's year-long timeline
+fn week_slot(date: NaiveDate, year: i32) -> Option<usize> {
+    (date.year() == year).then(|| {
+        date.month0() as usize * WEEKS_PER_MONTH
+            + ((date.day0() as usize * WEEKS_PER_MONTH) / 31).min(WEEKS_PER_MONTH - 1)
+    })
+}
+
+// One plant's year as a row of week cells: S to sow, T to transplant, H to harvest
+fn calendar_row(plant: &PlantExport, year: i32) -> String {
+    let mut cells = vec!['·'; 12 * WEEKS_PER_MONTH];
+    let mut mark = |from: Option<NaiveDate>, until: Option<NaiveDate>, symbol| {
+        if let (Some(from), Some(until)) = (from.or(until), until.or(from)) {
+            let first = week_slot(from, year).unwrap_or(0);
+            let last = week_slot(until, year).unwrap_or(cells.len() - 1);
+            if from.year() <= year && until.year() >= year {
+                cells[first..=last].fill(symbol);
+            }
+        }
+    };
+    let dates = &plant.dates;
+    mark(dates.first_harvest, dates.last_harvest, 'H');
+    mark(
+        dates.earliest_start.or(dates.start),
+        dates.latest_start.or(dates.start),
+        'S',
+    );
+    mark(dates.transplant, dates.transplant, 'T');
+    cells.into_iter().collect()
+}
+
+impl App {
+    fn new(plants: Vec<(String, PlantExport)>, frost_date: NaiveDate) -> Self {
+        let mut app = App {
+            plants,
+            frost_date,
+            query: String::new(),
+            searching: false,
+            view: View::Details,
+            visible: Vec::new(),
+            list_state: ListState::default(),
+        };
+        app.refilter();
+        app
+    }
+
+    fn refilter(&mut self) {
+        self.visible = matching_plants(&self.plants, &self.query);
+        self.list_state
+            .select((!self.visible.is_empty()).then_some(0));
+    }
+
+    fn selected(&self) -> Option<&(String, PlantExport)> {
+        let i = *self.visible.get(self.list_state.selected()?)?;
+        self.plants.get(i)
+    }
+
+    // Returns false once the user asks to quit
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        if self.searching {
+            match code {
+                KeyCode::Enter | KeyCode::Esc => self.searching = false,
+                KeyCode::Backspace => {
+                    self.query.pop();
+                    self.refilter();
+                }
+                KeyCode::Char(c) => {
+                    self.query.push(c);
+                    self.refilter();
+                }
+                _ => {}
+            }
+            return true;
+        }
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('/') => self.searching = true,
+            KeyCode::Down | KeyCode::Char('j') => self.list_state.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => self.list_state.select_previous(),
+            KeyCode::Tab | KeyCode::Char('c') => {
+                self.view = match self.view {
+                    View::Details => View::Calendar,
+                    View::Calendar => View::Details,
+                }
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, status] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+        let [list_area, detail_area] =
+            Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)])
+                .areas(main);
+
+        let items: Vec<ListItem> = self
+            .visible
+            .iter()
+            .map(|&i| ListItem::new(self.plants[i].0.as_str()))
+            .collect();
+        let title = format!("Plants ({}/{})", self.visible.len(), self.plants.len());
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, list_area, &mut self.list_state);
+
+        let detail = match self.view {
+            View::Details => {
+                let text = self
+                    .selected()
+                    .map(|(name, plant)| show::render_plain(name, plant, self.frost_date))
+                    .unwrap_or_else(|| "No plants match".to_string());
+                Paragraph::new(text)
+                    .wrap(Wrap { trim: false })
+                    .block(Block::default().borders(Borders::ALL).title("Details"))
+            }
+            View::Calendar => Paragraph::new(self.calendar_lines()).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("Calendar {}", self.frost_date.year())),
+            ),
+        };
+        frame.render_widget(detail, detail_area);
+
+        let status_text = if self.searching {
+            format!("Search: {}_", self.query)
+        } else {
+            "/ search  ↑↓ move  Tab details/calendar  q quit".to_string()
+        };
+        frame.render_widget(Paragraph::new(status_text), status);
+    }
+
+    fn calendar_lines(&self) -> Vec<Line<'_>> {
+        let year = self.frost_date.year();
+        let width = self
+            .visible
+            .iter()
+            .map(|&i| self.plants[i].0.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(24);
+        let months: String = "JFMAMJJASOND"
+            .chars()
+            .map(|m| format!("{:<1$}", m, WEEKS_PER_MONTH))
+            .collect();
+        let mut lines = vec![Line::from(format!("{:width$}  {}", "", months))];
+        for &i in &self.visible {
+            let (name, plant) = &self.plants[i];
+            let name: String = name.chars().take(width).collect();
+            lines.push(Line::from(format!(
+                "{:width$}  {}",
+                name,
+                calendar_row(plant, year)
+            )));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from("S sow or start  T transplant  H harvest"));
+        lines
+    }
+}
+
+fn run(terminal: &mut DefaultTerminal, app: &mut App) -> Result<()> {
+    loop {
+        terminal.draw(|frame| app.draw(frame))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && !app.handle_key(key.code) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+pub fn browse(json_dir: &str, frost_date: NaiveDate) -> Result<()> {
+    let plants = load_json_dir(json_dir)?
+        .into_iter()
+        .map(|(name, info)| (name, PlantExport::new(info, None, frost_date)))
+        .collect();
+    let mut app = App::new(plants, frost_date);
+
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PlantInfo;
+
+    #[test]
+    fn test_calendar_row() {
+        let info = PlantInfo {
+            title: Some("Danvers 126 Carrot Seeds".to_string()),
+            days_to_maturity: Some("65 days".to_string()),
+            when_to_sow_outside: Some(
+                "RECOMMENDED. Sow 2 to 4 weeks before your average last frost date.".to_string(),
+            ),
+            ..Default::default()
+        };
+        let frost_date = NaiveDate::from_ymd_opt(2025, 5, 15).unwrap();
+        let plant = PlantExport::new(info, None, frost_date);
+        // Sown Apr 17 to May 1 and harvested around Jul 5
+        assert_eq!(
+            calendar_row(&plant, 2025),
+            "··············SSS·······H·······················"
+        );
+
+        let plants = vec![("Carrot".to_string(), plant)];
+        assert_eq!(matching_plants(&plants, "danvers"), vec![0]);
+        assert!(matching_plants(&plants, "tomato").is_empty());
+    }
+}
@@ -0,0 +1,246 @@
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::{fs, thread};
+use tracing::{error, info, info_span};
+
+use crate::names::PlantIndex;
+use crate::{
+    create_http_client, fetch_page, load_json_dir, page_request, parse_fetched, request_delay,
+    OutputFormat, PlantInfo, PARSER_VERSION,
+};
+
+// Fields that change without the product itself changing, left out of the content hash
+const UNHASHED_FIELDS: [&str; 17] = [
+    "schema_version",
+    "scraped_at",
+    "source_domain",
+    "http_status",
+    "parser_version",
+    "content_hash",
+    "etag",
+    "last_modified",
+    "rating",
+    "votes",
+    "variants",
+    "price",
+    "compare_at_price",
+    "in_stock",
+    "related",
+    "discontinued",
+    "archived_snapshot",
+];
+
+// SHA-256 of the scraped product fields, as lowercase hex
+pub fn content_hash(info: &PlantInfo) -> String {
+    let mut fields = match serde_json::to_value(info) {
+        Ok(serde_json::Value::Object(fields)) => fields,
+        _ => serde_json::Map::new(),
+    };
+    for field in UNHASHED_FIELDS {
+        fields.remove(field);
+    }
+
+    // serde_json maps are sorted, so equal fields always serialize identically
+    let digest = Sha256::digest(serde_json::Value::Object(fields).to_string());
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+enum UpdateStatus {
+    Unchanged,
+    Changed,
+    // Parsed by an older parser, whose fields can't be compared with the current one's
+    OutdatedParser,
+    Failed(String),
+}
+
+fn check_plant(client: &reqwest::blocking::Client, info: &PlantInfo) -> UpdateStatus {
+    // Nothing from the page could be compared, so don't spend a request on it
+    if info.parsed_by_older_version() {
+        return UpdateStatus::OutdatedParser;
+    }
+
+    // Let the server answer 304 Not Modified instead of resending the page
+    let mut request = page_request(client, &info.url);
+    if let Some(etag) = &info.etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &info.last_modified {
+        request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+    }
+
+    let page = match fetch_page(request) {
+        Ok(page) => page,
+        Err(e) => return UpdateStatus::Failed(e.to_string()),
+    };
+    if page.status == 304 {
+        return UpdateStatus::Unchanged;
+    }
+    if !(200..300).contains(&page.status) {
+        return UpdateStatus::Failed(format!("HTTP {}", page.status));
+    }
+
+    let fresh = match PlantInfo::from_page(&page, info.url.clone()) {
+        Ok(fresh) => fresh,
+        Err(e) => return UpdateStatus::Failed(e.to_string()),
+    };
+    let stored_hash = info
+        .content_hash
+        .clone()
+        .unwrap_or_else(|| content_hash(info));
+    if content_hash(&fresh) == stored_hash {
+        UpdateStatus::Unchanged
+    } else {
+        UpdateStatus::Changed
+    }
+}
+
+// Report which plants' pages have changed, without rewriting any JSON files
+pub fn check_updates(json_dir: &str, output: OutputFormat) -> Result<()> {
+    let plants = load_json_dir(json_dir)?;
+    let client = create_http_client();
+    let mut changed = Vec::new();
+    let mut outdated = Vec::new();
+    let mut unchanged = 0;
+    let mut failed = 0;
+    let mut fetched = false;
+
+    for (name, info) in &plants {
+        let _span = info_span!("plant", plant = name.as_str(), url = info.url.as_str()).entered();
+        // Sleep between requests; outdated plants are reported without one
+        let fetches = !info.parsed_by_older_version();
+        if fetched && fetches {
+            thread::sleep(request_delay(&info.url));
+        }
+        fetched |= fetches;
+
+        match check_plant(&client, info) {
+            UpdateStatus::Unchanged => {
+                output.print_text(&format!("Unchanged: {}", name));
+                unchanged += 1;
+            }
+            UpdateStatus::Changed => {
+                output.print_text(&format!("Changed: {}", name));
+                changed.push(name.as_str());
+            }
+            UpdateStatus::OutdatedParser => {
+                output.print_text(&format!("Outdated parser: {}", name));
+                outdated.push(name.as_str());
+            }
+            UpdateStatus::Failed(reason) => {
+                error!("Failed to check {}: {}", name, reason);
+                failed += 1;
+            }
+        }
+    }
+
+    output.print_text(&format!(
+        "\nChecked {} plants: {} changed, {} unchanged, {} outdated, {} failed",
+        plants.len(),
+        changed.len(),
+        unchanged,
+        outdated.len(),
+        failed
+    ));
+    if !changed.is_empty() {
+        output.print_text("Re-scrape these plants to pick up their changes:");
+        for name in &changed {
+            output.print_text(&format!("- {}", name));
+        }
+    }
+    if !outdated.is_empty() {
+        output.print_text(&format!(
+            "{} plants were parsed by an older parser; run reparse --outdated-only to compare them",
+            outdated.len()
+        ));
+    }
+    output.print_json(&serde_json::json!({
+        "checked": plants.len(),
+        "changed": changed,
+        "outdated": outdated,
+        "unchanged": unchanged,
+        "failed": failed,
+    }))
+}
+
+// Scrape plants' pages again so their files pick up everything the current parser extracts
+pub fn reparse(json_dir: &str, outdated_only: bool, output: OutputFormat) -> Result<()> {
+    let index = PlantIndex::load(json_dir)?;
+    let plants: Vec<(String, PlantInfo)> = load_json_dir(json_dir)?
+        .into_iter()
+        .filter(|(_, info)| !outdated_only || info.parsed_by_older_version())
+        .collect();
+    let client = create_http_client();
+    let mut reparsed = 0;
+    let mut failed = 0;
+
+    for (i, (name, stored)) in plants.iter().enumerate() {
+        let _span = info_span!("plant", plant = name.as_str(), url = stored.url.as_str()).entered();
+        if i > 0 {
+            // Sleep between requests
+            thread::sleep(request_delay(&stored.url));
+        }
+
+        let page = match fetch_page(page_request(&client, &stored.url)) {
+            Ok(page) => page,
+            Err(e) => {
+                error!("Failed to fetch {}: {}", name, e);
+                failed += 1;
+                continue;
+            }
+        };
+        let mut info = match parse_fetched(&client, &page, stored.url.clone()) {
+            Ok(info) => info,
+            Err(e) => {
+                error!("Failed to parse {}: {}", name, e);
+                failed += 1;
+                continue;
+            }
+        };
+        info.fetch_related(&client, &page.body);
+        info.record_scrape(&page);
+
+        let json_path = index.path_for(name);
+        fs::write(&json_path, serde_json::to_string_pretty(&info)?)
+            .context(format!("Failed to write {}", json_path))?;
+        info!(
+            "Reparsed {} (parser version {} -> {})",
+            name,
+            stored.parser_version.unwrap_or(0),
+            PARSER_VERSION
+        );
+        reparsed += 1;
+    }
+
+    output.print_text(&format!("Reparsed {} plants, {} failed", reparsed, failed));
+    output.print_json(&serde_json::json!({
+        "reparsed": reparsed,
+        "failed": failed,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash_ignores_volatile_fields() {
+        let mut info = PlantInfo {
+            url: "http://example.com".to_string(),
+            days_to_maturity: Some("65 days".to_string()),
+            ..Default::default()
+        };
+        let hash = content_hash(&info);
+        assert_eq!(hash.len(), 64);
+
+        info.votes = Some(12);
+        info.etag = Some("\"abc\"".to_string());
+        assert_eq!(content_hash(&info), hash);
+
+        info.discontinued = Some(true);
+        info.archived_snapshot = Some("https://web.archive.org/web/2024id_/x".to_string());
+        assert_eq!(content_hash(&info), hash);
+
+        info.days_to_maturity = Some("70 days".to_string());
+        assert_ne!(content_hash(&info), hash);
+    }
+}
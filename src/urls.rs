@@ -0,0 +1,67 @@
+// One spelling for each product URL, so links that differ only in tracking parameters or a
+// trailing slash are recognized as the same product
+use reqwest::Url;
+
+// Query parameters that say how someone got to a page rather than which page it is; the
+// variant only picks a packet size on the same product page
+const TRACKING_PARAMS: [&str; 12] = [
+    "variant", "fbclid", "gclid", "msclkid", "mc_cid", "mc_eid", "ref", "_pos", "_sid", "_ss",
+    "_psq", "_fid",
+];
+
+fn is_tracking(name: &str) -> bool {
+    name.starts_with("utm_") || TRACKING_PARAMS.contains(&name)
+}
+
+// The URL without tracking parameters, fragment, or trailing slash; text that isn't a URL
+// is only trimmed
+pub fn canonical_url(url: &str) -> String {
+    let Ok(mut parsed) = Url::parse(url.trim()) else {
+        return url.trim().to_string();
+    };
+    parsed.set_fragment(None);
+
+    let kept: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(name, _)| !is_tracking(&name.to_lowercase()))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    if kept.is_empty() {
+        parsed.set_query(None);
+    } else {
+        parsed.query_pairs_mut().clear().extend_pairs(kept);
+    }
+
+    let path = parsed.path();
+    if path.len() > 1 && path.ends_with('/') {
+        let trimmed = path.trim_end_matches('/').to_string();
+        parsed.set_path(&trimmed);
+    }
+    parsed.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_url() {
+        let canonical = "https://www.botanicalinterests.com/products/carrot";
+        for url in [
+            "https://www.botanicalinterests.com/products/carrot",
+            "https://WWW.BotanicalInterests.com/products/carrot/",
+            " https://www.botanicalinterests.com/products/carrot?variant=4392&utm_source=email#reviews",
+        ] {
+            assert_eq!(canonical_url(url), canonical);
+        }
+        assert_eq!(
+            canonical_url("https://example.com/products/carrot?size=large&utm_medium=cpc"),
+            "https://example.com/products/carrot?size=large"
+        );
+        assert_eq!(
+            canonical_url("https://example.com/"),
+            "https://example.com/"
+        );
+        assert_eq!(canonical_url(" not a url "), "not a url");
+    }
+}
@@ -0,0 +1,145 @@
+// Varying the user agent between requests, and optionally sending the rest of the headers a
+// browser would, so a long crawl looks less like one client hammering a store
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum RotateBy {
+    /// The next user agent for every request
+    Request,
+    /// The same user agent for every request to a domain, different between domains
+    Domain,
+}
+
+pub struct Rotation {
+    pub user_agents: Vec<String>,
+    pub rotate_by: RotateBy,
+    pub browser_headers: bool,
+}
+
+static ROTATION: OnceLock<Rotation> = OnceLock::new();
+static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+// Set once from the global --user-agent, --rotate-by, and --browser-headers flags
+pub fn set_rotation(rotation: Rotation) {
+    let _ = ROTATION.set(rotation);
+}
+
+fn pick<'a>(user_agents: &'a [String], url: &str, rotate_by: RotateBy) -> Option<&'a str> {
+    if user_agents.is_empty() {
+        return None;
+    }
+    let i = match rotate_by {
+        RotateBy::Request => NEXT.fetch_add(1, Ordering::Relaxed),
+        RotateBy::Domain => {
+            let host = reqwest::Url::parse(url)
+                .ok()
+                .and_then(|url| url.host_str().map(String::from))
+                .unwrap_or_default();
+            let mut hasher = DefaultHasher::new();
+            host.hash(&mut hasher);
+            hasher.finish() as usize
+        }
+    };
+    Some(&user_agents[i % user_agents.len()])
+}
+
+// What Chrome sends for a page navigation beyond its user agent; Firefox and Safari don't
+// send client hints, so those only go with a Chrome user agent
+fn browser_headers(user_agent: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::UPGRADE_INSECURE_REQUESTS,
+        HeaderValue::from_static("1"),
+    );
+    headers.insert("sec-fetch-dest", HeaderValue::from_static("document"));
+    headers.insert("sec-fetch-mode", HeaderValue::from_static("navigate"));
+    headers.insert("sec-fetch-site", HeaderValue::from_static("none"));
+    headers.insert("sec-fetch-user", HeaderValue::from_static("?1"));
+
+    let chrome_version = user_agent
+        .split_once("Chrome/")
+        .filter(|_| !user_agent.contains("Edg/") && !user_agent.contains("OPR/"))
+        .and_then(|(_, rest)| rest.split('.').next());
+    if let Some(version) = chrome_version {
+        let brands = format!(
+            "\"Chromium\";v=\"{0}\", \"Google Chrome\";v=\"{0}\", \"Not-A.Brand\";v=\"99\"",
+            version
+        );
+        if let Ok(brands) = HeaderValue::try_from(brands) {
+            headers.insert("sec-ch-ua", brands);
+        }
+        let mobile = if user_agent.contains("Mobile") {
+            "?1"
+        } else {
+            "?0"
+        };
+        headers.insert("sec-ch-ua-mobile", HeaderValue::from_static(mobile));
+        let platform = if user_agent.contains("Windows") {
+            "\"Windows\""
+        } else if user_agent.contains("Android") {
+            "\"Android\""
+        } else if user_agent.contains("Mac OS X") {
+            "\"macOS\""
+        } else {
+            "\"Linux\""
+        };
+        headers.insert("sec-ch-ua-platform", HeaderValue::from_static(platform));
+    }
+    headers
+}
+
+// Headers for a page request: the user agent's turn, from the domain's own list if it has
+// one, and the browser headers to go with it if asked for
+pub fn headers_for(url: &str, domain_agents: &[String], default_agent: &str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    let Some(rotation) = ROTATION.get() else {
+        return headers;
+    };
+    let agents = if domain_agents.is_empty() {
+        &rotation.user_agents
+    } else {
+        domain_agents
+    };
+    let user_agent = pick(agents, url, rotation.rotate_by);
+    if let Some(value) = user_agent.and_then(|agent| HeaderValue::try_from(agent).ok()) {
+        headers.insert(header::USER_AGENT, value);
+    }
+    if rotation.browser_headers {
+        headers.extend(browser_headers(user_agent.unwrap_or(default_agent)));
+    }
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_user_agent() {
+        let agents = vec!["A".to_string(), "B".to_string(), "C".to_string()];
+        let url = "https://www.botanicalinterests.com/products/carrot";
+        let first = pick(&agents, url, RotateBy::Request).unwrap();
+        assert_ne!(pick(&agents, url, RotateBy::Request).unwrap(), first);
+        assert_eq!(
+            pick(&agents, url, RotateBy::Domain),
+            pick(
+                &agents,
+                "https://www.botanicalinterests.com/",
+                RotateBy::Domain
+            )
+        );
+        assert_eq!(pick(&[], url, RotateBy::Request), None);
+
+        let chrome = browser_headers("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36");
+        assert!(chrome["sec-ch-ua"].to_str().unwrap().contains("v=\"124\""));
+        assert_eq!(chrome["sec-ch-ua-platform"], "\"Windows\"");
+        let firefox = browser_headers(
+            "Mozilla/5.0 (X11; Linux x86_64; rv:125.0) Gecko/20100101 Firefox/125.0",
+        );
+        assert!(firefox.get("sec-ch-ua").is_none());
+    }
+}
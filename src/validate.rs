@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::columns::ColumnMap;
+use crate::error::ScrapingError;
+use crate::names::{PlantIndex, INDEX_FILE};
+use crate::{schema, InputRecord, OutputFormat, PlantInfo};
+
+// Fields a plant can't be scheduled without
+fn missing_fields(info: &PlantInfo) -> Vec<&'static str> {
+    let mut missing = Vec::new();
+    if info.url.trim().is_empty() {
+        missing.push("URL");
+    }
+    if info.when_to_sow_outside.is_none() && info.when_to_start_inside.is_none() {
+        missing.push("sowing info");
+    }
+    if info.maturity_days().is_none() {
+        missing.push("days to maturity");
+    }
+    missing
+}
+
+// Check every JSON file parses and has the fields exports rely on, and when an input CSV is
+// given, that it and the directory agree on which plants exist. Returns the number of problems.
+pub fn validate(json_dir: &str, input_file: Option<&str>, output: OutputFormat) -> Result<usize> {
+    let index = PlantIndex::load(json_dir)?;
+    let mut problems = Vec::new();
+    let mut checked = 0;
+
+    let mut paths: Vec<_> = fs::read_dir(json_dir)
+        .context(format!("Failed to read directory: {}", json_dir))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+        .filter(|path| !path.ends_with(INDEX_FILE))
+        .collect();
+    paths.sort();
+
+    for path in &paths {
+        checked += 1;
+        let file = path.display();
+        let info = fs::read_to_string(path)
+            .map_err(ScrapingError::io(file.to_string()))
+            .and_then(|content| schema::parse_plant_json(&content));
+        match info {
+            Ok(info) => {
+                let missing = missing_fields(&info);
+                if !missing.is_empty() {
+                    problems.push(format!("{}: missing {}", file, missing.join(", ")));
+                }
+            }
+            Err(e) => problems.push(format!("{}: {:#}", file, e)),
+        }
+    }
+
+    if let Some(input_file) = input_file {
+        let mut reader = csv::Reader::from_path(input_file)
+            .context(format!("Failed to read input CSV file: {}", input_file))?;
+        let columns = ColumnMap::from_headers(reader.headers()?);
+        let records: Vec<csv::StringRecord> = reader
+            .records()
+            .map(|record| record.map(|record| columns.normalize(&record)))
+            .collect::<Result<_, _>>()?;
+
+        let mut referenced = HashSet::new();
+        for record in &records {
+            let input = InputRecord::from_csv_record(record);
+            let json_path = index.path_for(input.plant_name);
+            if Path::new(&json_path).exists() {
+                referenced.insert(Path::new(&json_path).to_path_buf());
+            } else {
+                problems.push(format!(
+                    "{}: CSV row has no JSON file ({})",
+                    input.plant_name, json_path
+                ));
+            }
+        }
+        for path in &paths {
+            if !referenced.contains(path) {
+                problems.push(format!(
+                    "{}: not referenced by {}",
+                    path.display(),
+                    input_file
+                ));
+            }
+        }
+    }
+
+    for problem in &problems {
+        output.print_text(problem);
+    }
+    output.print_text(&format!(
+        "\nChecked {} plant files: {} problems found",
+        checked,
+        problems.len()
+    ));
+    output.print_json(&serde_json::json!({
+        "checked": checked,
+        "problems": problems,
+    }))?;
+    Ok(problems.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_fields() {
+        let info: PlantInfo = serde_json::from_str(
+            r#"{"url": "u", "when_to_start_inside": "6 to 8 weeks before transplanting"}"#,
+        )
+        .unwrap();
+        assert_eq!(missing_fields(&info), vec!["days to maturity"]);
+    }
+}
@@ -0,0 +1,85 @@
+// Estimating whether a packet is still worth sowing from its age and the plant's family
+use std::fmt;
+
+// Years seed of each family typically stays viable in cool, dry storage, taking the shorter
+// end for families whose crops differ (parsnip against carrot, pepper against tomato)
+const VIABLE_YEARS: [(&str, i32); 12] = [
+    ("Amaryllidaceae", 1),
+    ("Alliaceae", 1),
+    ("Apiaceae", 2),
+    ("Amaranthaceae", 4),
+    ("Asteraceae", 5),
+    ("Brassicaceae", 4),
+    ("Cucurbitaceae", 5),
+    ("Fabaceae", 3),
+    ("Lamiaceae", 5),
+    ("Malvaceae", 2),
+    ("Poaceae", 2),
+    ("Solanaceae", 3),
+];
+
+// Past its typical years, seed is worth a germination test for this many more before it's
+// likely too weak to bother with
+const TEST_FIRST_YEARS: i32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Viability {
+    Good,
+    TestFirst,
+    Replace,
+}
+
+impl fmt::Display for Viability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            Viability::Good => "Good",
+            Viability::TestFirst => "Test First",
+            Viability::Replace => "Replace",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+// Matched within the text, since some pages add the older name, e.g. "Apiaceae (Umbelliferae)"
+fn viable_years(family: &str) -> Option<i32> {
+    let family = family.to_lowercase();
+    VIABLE_YEARS
+        .iter()
+        .find(|(name, _)| family.contains(&name.to_lowercase()))
+        .map(|(_, years)| *years)
+}
+
+// How seed bought in purchase_year should hold up in this_year, when the family's typical
+// viability is known
+pub fn estimate(family: &str, purchase_year: &str, this_year: i32) -> Option<Viability> {
+    let years = viable_years(family)?;
+    let age = this_year - purchase_year.trim().parse::<i32>().ok()?;
+    Some(if age <= years {
+        Viability::Good
+    } else if age <= years + TEST_FIRST_YEARS {
+        Viability::TestFirst
+    } else {
+        Viability::Replace
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_viability() {
+        assert_eq!(estimate("Solanaceae", "2023", 2025), Some(Viability::Good));
+        assert_eq!(
+            estimate("Apiaceae (Umbelliferae)", "2021", 2025),
+            Some(Viability::TestFirst)
+        );
+        assert_eq!(
+            estimate("Amaryllidaceae", "2020", 2025),
+            Some(Viability::Replace)
+        );
+        assert_eq!(estimate("Rosaceae", "2020", 2025), None);
+        assert_eq!(estimate("Fabaceae", "", 2025), None);
+        assert_eq!(Viability::TestFirst.to_string(), "Test First");
+    }
+}
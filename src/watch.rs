@@ -0,0 +1,328 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::{thread, time::Duration as StdDuration};
+use tracing::{error, info_span, warn};
+
+use crate::feed;
+use crate::notify::{notify_all, Notification, Notifier};
+use crate::{
+    create_http_client, fetch_page, load_json_dir, page_request, request_delay, OutputFormat,
+    PlantInfo,
+};
+
+// One price and stock reading for a plant, stored one per line in the history file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceSnapshot {
+    pub name: String,
+    pub url: String,
+    pub checked_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compare_at_price: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_stock: Option<bool>,
+    // The sowing and maturity text, to notice when a store edits its growing instructions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sowing_info: Option<String>,
+}
+
+impl PriceSnapshot {
+    fn from_info(name: &str, info: &PlantInfo, checked_at: DateTime<Utc>) -> Self {
+        PriceSnapshot {
+            name: name.to_string(),
+            url: info.url.clone(),
+            checked_at,
+            price: info.price,
+            compare_at_price: info.compare_at_price,
+            in_stock: info.in_stock,
+            sowing_info: sowing_info(info),
+        }
+    }
+}
+
+fn sowing_info(info: &PlantInfo) -> Option<String> {
+    let fields: Vec<&str> = [
+        &info.when_to_sow_outside,
+        &info.when_to_start_inside,
+        &info.days_to_maturity,
+    ]
+    .into_iter()
+    .flatten()
+    .map(String::as_str)
+    .collect();
+    (!fields.is_empty()).then(|| fields.join("\n"))
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PriceChange {
+    Price { old: f32, new: f32 },
+    OnSale { price: f32, compare_at_price: f32 },
+    BackInStock,
+    OutOfStock,
+    SowingInfo,
+}
+
+impl std::fmt::Display for PriceChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PriceChange::Price { old, new } => write!(f, "price ${:.2} -> ${:.2}", old, new),
+            PriceChange::OnSale {
+                price,
+                compare_at_price,
+            } => write!(f, "on sale at ${:.2} (was ${:.2})", price, compare_at_price),
+            PriceChange::BackInStock => write!(f, "back in stock"),
+            PriceChange::OutOfStock => write!(f, "out of stock"),
+            PriceChange::SowingInfo => write!(f, "sowing instructions changed"),
+        }
+    }
+}
+
+// Compare a new reading against the previous one for the same plant
+pub fn detect_changes(previous: &PriceSnapshot, current: &PriceSnapshot) -> Vec<PriceChange> {
+    let mut changes = Vec::new();
+
+    if let (Some(old), Some(new)) = (previous.price, current.price) {
+        if (old - new).abs() >= 0.005 {
+            changes.push(PriceChange::Price { old, new });
+        }
+    }
+    if let (None, Some(compare_at_price), Some(price)) = (
+        previous.compare_at_price,
+        current.compare_at_price,
+        current.price,
+    ) {
+        changes.push(PriceChange::OnSale {
+            price,
+            compare_at_price,
+        });
+    }
+    match (previous.in_stock, current.in_stock) {
+        (Some(false), Some(true)) => changes.push(PriceChange::BackInStock),
+        (Some(true), Some(false)) => changes.push(PriceChange::OutOfStock),
+        _ => {}
+    }
+    if let (Some(old), Some(new)) = (&previous.sowing_info, &current.sowing_info) {
+        if old != new {
+            changes.push(PriceChange::SowingInfo);
+        }
+    }
+
+    changes
+}
+
+// Every snapshot in a history file, oldest first; a missing file is an empty history
+pub fn load_history(history_file: &str) -> Result<Vec<PriceSnapshot>> {
+    let mut snapshots = Vec::new();
+    if !Path::new(history_file).exists() {
+        return Ok(snapshots);
+    }
+
+    let content = fs::read_to_string(history_file)
+        .context(format!("Failed to read price history: {}", history_file))?;
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        match serde_json::from_str::<PriceSnapshot>(line) {
+            Ok(snapshot) => snapshots.push(snapshot),
+            Err(e) => warn!("Skipping unreadable price history line: {}", e),
+        }
+    }
+    Ok(snapshots)
+}
+
+// The most recent snapshot per plant in a history file
+fn load_latest_snapshots(history_file: &str) -> Result<HashMap<String, PriceSnapshot>> {
+    Ok(load_history(history_file)?
+        .into_iter()
+        .map(|snapshot| (snapshot.name.clone(), snapshot))
+        .collect())
+}
+
+fn append_snapshots(history_file: &str, snapshots: &[PriceSnapshot]) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(history_file)
+        .context(format!("Failed to open price history: {}", history_file))?;
+    for snapshot in snapshots {
+        writeln!(file, "{}", serde_json::to_string(snapshot)?)?;
+    }
+    Ok(())
+}
+
+// A plant's latest reading along with how it differs from the one before
+type PlantChanges = (PriceSnapshot, Vec<PriceChange>);
+
+// Re-check every tracked plant once, returning the changes found for each
+fn check_prices(
+    json_dir: &str,
+    history_file: &str,
+    output: OutputFormat,
+) -> Result<Vec<PlantChanges>> {
+    let plants = load_json_dir(json_dir)?;
+    let mut latest = load_latest_snapshots(history_file)?;
+    let client = create_http_client();
+    let mut snapshots = Vec::new();
+    let mut changed = Vec::new();
+
+    for (i, (name, stored)) in plants.iter().enumerate() {
+        let _span = info_span!("plant", plant = name.as_str(), url = stored.url.as_str()).entered();
+        if i > 0 {
+            // Sleep between requests
+            thread::sleep(request_delay(&stored.url));
+        }
+
+        let info = match fetch_page(page_request(&client, &stored.url)) {
+            Ok(page) => match PlantInfo::from_page(&page, stored.url.clone()) {
+                Ok(info) => info,
+                Err(e) => {
+                    error!("Failed to parse {}: {}", name, e);
+                    continue;
+                }
+            },
+            Err(e) => {
+                error!("Failed to fetch {}: {}", name, e);
+                continue;
+            }
+        };
+
+        let snapshot = PriceSnapshot::from_info(name, &info, Utc::now());
+        // Plants seen for the first time are compared against their scraped JSON
+        let previous = latest
+            .remove(name)
+            .unwrap_or_else(|| PriceSnapshot::from_info(name, stored, snapshot.checked_at));
+        let changes = detect_changes(&previous, &snapshot);
+        if !changes.is_empty() {
+            changed.push((snapshot.clone(), changes));
+        }
+        snapshots.push(snapshot);
+    }
+
+    append_snapshots(history_file, &snapshots)?;
+    output.print_text(&format!(
+        "Checked {} plants at {}, {} changed",
+        snapshots.len(),
+        Utc::now().format("%Y-%m-%d %H:%M UTC"),
+        changed.len()
+    ));
+    Ok(changed)
+}
+
+// Periodically record price and stock snapshots, reporting changes since the last check
+// and alerting when something comes back in stock
+pub fn watch(
+    json_dir: &str,
+    history_file: &str,
+    interval_hours: u64,
+    once: bool,
+    notifiers: &[Notifier],
+    feed_file: Option<&str>,
+    output: OutputFormat,
+) -> Result<()> {
+    loop {
+        let checked = check_prices(json_dir, history_file, output).and_then(|changed| {
+            report_changes(&changed, notifiers, output)?;
+            match feed_file {
+                Some(feed_file) => {
+                    let feed = feed::collection_feed(json_dir, history_file)?;
+                    fs::write(feed_file, feed)
+                        .context(format!("Failed to write feed: {}", feed_file))
+                }
+                None => Ok(()),
+            }
+        });
+
+        if once {
+            return checked;
+        }
+        // A bad check shouldn't end a watcher meant to run for months; try again next time
+        if let Err(e) = checked {
+            error!("Price check failed: {:#}", e);
+        }
+        thread::sleep(StdDuration::from_secs(interval_hours * 60 * 60));
+    }
+}
+
+// Print each plant's changes, as one JSON array per check in JSON mode, and alert on
+// anything back in stock
+fn report_changes(
+    changed: &[(PriceSnapshot, Vec<PriceChange>)],
+    notifiers: &[Notifier],
+    output: OutputFormat,
+) -> Result<()> {
+    let mut reports = Vec::new();
+    for (snapshot, changes) in changed {
+        for change in changes {
+            output.print_text(&format!("{}: {}", snapshot.name, change));
+        }
+        let mut report = serde_json::to_value(snapshot)?;
+        report["changes"] = changes.iter().map(ToString::to_string).collect();
+        reports.push(report);
+
+        if changes.contains(&PriceChange::BackInStock) {
+            let price = snapshot
+                .price
+                .map(|price| format!(" at ${:.2}", price))
+                .unwrap_or_default();
+            notify_all(
+                notifiers,
+                &Notification {
+                    title: format!("{} is back in stock", snapshot.name),
+                    message: format!("{} is available again{}.", snapshot.name, price),
+                    url: &snapshot.url,
+                },
+            );
+        }
+    }
+    output.print_json(&reports.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(price: f32, compare_at_price: Option<f32>, in_stock: bool) -> PriceSnapshot {
+        PriceSnapshot {
+            name: "Carrot".to_string(),
+            url: "http://example.com".to_string(),
+            checked_at: Utc::now(),
+            price: Some(price),
+            compare_at_price,
+            in_stock: Some(in_stock),
+            sowing_info: Some("Sow 2 to 4 weeks before your average last frost date.".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_detect_changes() {
+        let previous = snapshot(5.99, None, false);
+        assert_eq!(detect_changes(&previous, &previous), vec![]);
+
+        let current = snapshot(4.79, Some(5.99), true);
+        assert_eq!(
+            detect_changes(&previous, &current),
+            vec![
+                PriceChange::Price {
+                    old: 5.99,
+                    new: 4.79
+                },
+                PriceChange::OnSale {
+                    price: 4.79,
+                    compare_at_price: 5.99
+                },
+                PriceChange::BackInStock,
+            ]
+        );
+
+        let mut edited = previous.clone();
+        edited.sowing_info = Some("Sow after your average last frost date.".to_string());
+        assert_eq!(
+            detect_changes(&previous, &edited),
+            vec![PriceChange::SowingInfo]
+        );
+    }
+}
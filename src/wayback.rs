@@ -0,0 +1,113 @@
+// Reading a product from its latest archive.org snapshot when the store won't serve the page
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::error::ScrapingError;
+use crate::{fetch_page, PlantInfo};
+
+const AVAILABILITY_API_URL: &str = "https://archive.org/wayback/available";
+
+// The timestamp of the most recent snapshot that captured the page itself rather than an error
+fn latest_timestamp(availability: &Value) -> Option<String> {
+    let closest = &availability["archived_snapshots"]["closest"];
+    if closest["available"] != true || closest["status"] != "200" {
+        return None;
+    }
+    closest["timestamp"].as_str().map(String::from)
+}
+
+// The snapshot as it was archived; the id_ flag leaves out the Wayback Machine's toolbar and
+// link rewriting, so the page parses like the live one
+fn snapshot_url(timestamp: &str, url: &str) -> String {
+    format!("https://web.archive.org/web/{}id_/{}", timestamp, url)
+}
+
+// The plant as the latest snapshot of its page shows it, or None when nothing was archived
+fn parse_snapshot(
+    client: &reqwest::blocking::Client,
+    url: &str,
+) -> Result<Option<PlantInfo>, ScrapingError> {
+    let availability: Value = client
+        .get(AVAILABILITY_API_URL)
+        .query(&[("url", url)])
+        .send()?
+        .json()?;
+    let Some(timestamp) = latest_timestamp(&availability) else {
+        return Ok(None);
+    };
+    let snapshot = snapshot_url(&timestamp, url);
+    let page = fetch_page(client.get(&snapshot))?;
+    if !(200..300).contains(&page.status) {
+        return Ok(None);
+    }
+    let mut info = PlantInfo::from_html(&page.body, url.to_string())?;
+    info.archived_snapshot = Some(snapshot);
+    Ok(Some(info))
+}
+
+// The parsed page, or when the store blocked it or no longer has it, the plant as archive.org
+// last saw it. A snapshot of a removed product still marks it discontinued.
+pub fn or_archived(
+    client: &reqwest::blocking::Client,
+    parsed: Result<PlantInfo, ScrapingError>,
+    url: &str,
+) -> Result<PlantInfo, ScrapingError> {
+    let discontinued = match &parsed {
+        Ok(info) if info.discontinued == Some(true) => true,
+        Err(ScrapingError::Blocked) => false,
+        _ => return parsed,
+    };
+    match parse_snapshot(client, url) {
+        Ok(Some(mut info)) => {
+            info!(
+                "Read {} from {}",
+                url,
+                info.archived_snapshot.as_deref().unwrap_or("")
+            );
+            if discontinued {
+                info.discontinued = Some(true);
+            }
+            Ok(info)
+        }
+        Ok(None) => {
+            info!("archive.org has no snapshot of {}", url);
+            parsed
+        }
+        Err(e) => {
+            warn!("Failed to read {} from archive.org: {}", url, e);
+            parsed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_snapshot() {
+        let url = "https://www.botanicalinterests.com/products/danvers-126-carrot-seeds";
+        let availability = serde_json::json!({"archived_snapshots": {"closest": {
+            "available": true,
+            "status": "200",
+            "timestamp": "20230115083000",
+            "url": "http://web.archive.org/web/20230115083000/https://www.botanicalinterests.com/products/danvers-126-carrot-seeds",
+        }}});
+        let timestamp = latest_timestamp(&availability).unwrap();
+        assert_eq!(
+            snapshot_url(&timestamp, url),
+            "https://web.archive.org/web/20230115083000id_/https://www.botanicalinterests.com/products/danvers-126-carrot-seeds"
+        );
+
+        assert_eq!(
+            latest_timestamp(&serde_json::json!({"archived_snapshots": {}})),
+            None
+        );
+        let archived_404 = serde_json::json!({"archived_snapshots": {"closest": {
+            "available": true,
+            "status": "404",
+            "timestamp": "20230115083000",
+        }}});
+        assert_eq!(latest_timestamp(&archived_404), None);
+    }
+}
@@ -0,0 +1,186 @@
+// Checking the coming days' tasks against Open-Meteo's frost and soil temperature forecast
+use anyhow::{anyhow, Context, Result};
+use chrono::{Days, NaiveDate};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::tasks::{Task, TaskKind};
+use crate::{create_http_client, PlantInfo};
+
+const FORECAST_API_URL: &str = "https://api.open-meteo.com/v1/forecast";
+// The most days ahead the free forecast covers
+const FORECAST_DAYS: u32 = 16;
+const FROST_F: f32 = 32.0;
+// A frost this soon after sowing or transplanting can still kill what went in
+const FROST_RISK_DAYS: u64 = 7;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayForecast {
+    pub date: NaiveDate,
+    pub min_temp_f: f32,
+    // Daily mean of the hourly soil temperature 6cm down, about seed depth
+    pub soil_temp_f: Option<f32>,
+}
+
+pub fn parse_location(text: &str) -> Result<(f64, f64), String> {
+    let (latitude, longitude) = text
+        .split_once(',')
+        .ok_or_else(|| format!("expected LATITUDE,LONGITUDE, got \"{}\"", text))?;
+    let latitude: f64 = latitude.trim().parse().map_err(|_| "invalid latitude")?;
+    let longitude: f64 = longitude.trim().parse().map_err(|_| "invalid longitude")?;
+    if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+        return Err("latitude or longitude out of range".to_string());
+    }
+    Ok((latitude, longitude))
+}
+
+fn parse_forecast(data: &Value) -> Result<Vec<DayForecast>> {
+    let numbers = |values: &Value| -> Vec<Option<f32>> {
+        values
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|value| value.as_f64().map(|value| value as f32))
+            .collect()
+    };
+
+    // Hours are given as e.g. "2025-04-21T06:00", grouped here by their date
+    let mut soil: BTreeMap<&str, Vec<f32>> = BTreeMap::new();
+    let hours = data["hourly"]["time"].as_array().into_iter().flatten();
+    for (hour, temp) in hours.zip(numbers(&data["hourly"]["soil_temperature_6cm"])) {
+        if let (Some(day), Some(temp)) = (hour.as_str().and_then(|hour| hour.get(..10)), temp) {
+            soil.entry(day).or_default().push(temp);
+        }
+    }
+
+    let days = data["daily"]["time"]
+        .as_array()
+        .ok_or_else(|| anyhow!("The forecast has no daily temperatures"))?;
+    Ok(days
+        .iter()
+        .zip(numbers(&data["daily"]["temperature_2m_min"]))
+        .filter_map(|(day, min_temp)| {
+            let day = day.as_str()?;
+            Some(DayForecast {
+                date: day.parse().ok()?,
+                min_temp_f: min_temp?,
+                soil_temp_f: soil
+                    .get(day)
+                    .map(|temps| temps.iter().sum::<f32>() / temps.len() as f32),
+            })
+        })
+        .collect())
+}
+
+pub fn fetch_forecast((latitude, longitude): (f64, f64)) -> Result<Vec<DayForecast>> {
+    let response = create_http_client()
+        .get(FORECAST_API_URL)
+        .query(&[
+            ("latitude", latitude.to_string()),
+            ("longitude", longitude.to_string()),
+            ("daily", "temperature_2m_min".to_string()),
+            ("hourly", "soil_temperature_6cm".to_string()),
+            ("temperature_unit", "fahrenheit".to_string()),
+            ("timezone", "auto".to_string()),
+            ("forecast_days", FORECAST_DAYS.to_string()),
+        ])
+        .send()
+        .context("Failed to reach Open-Meteo")?;
+    if !response.status().is_success() {
+        return Err(anyhow!(
+            "Forecast request failed with {}: {}",
+            response.status(),
+            response.text().unwrap_or_default()
+        ));
+    }
+    parse_forecast(&response.json()?)
+}
+
+// Why the forecast says to hold off on an outdoor task, if it does
+pub fn task_warnings(task: &Task, info: &PlantInfo, forecast: &[DayForecast]) -> Vec<String> {
+    if !matches!(task.kind, TaskKind::SowOutside | TaskKind::Transplant) {
+        return Vec::new();
+    }
+    let mut warnings = Vec::new();
+
+    let frost_tolerant = info.has_tags(&["Frost Tolerant".to_string()]);
+    let risk_until = task.until + Days::new(FROST_RISK_DAYS);
+    let frost = forecast
+        .iter()
+        .find(|day| day.date >= task.from && day.date <= risk_until && day.min_temp_f <= FROST_F);
+    if let (false, Some(frost)) = (frost_tolerant, frost) {
+        warnings.push(format!(
+            "frost forecast {} ({:.0}°F)",
+            frost.date.format("%b %-d"),
+            frost.min_temp_f
+        ));
+    }
+
+    if task.kind == TaskKind::SowOutside {
+        let soil: Vec<f32> = forecast
+            .iter()
+            .filter(|day| day.date >= task.from && day.date <= task.until)
+            .filter_map(|day| day.soil_temp_f)
+            .collect();
+        let warmest = soil.iter().copied().reduce(f32::max);
+        if let (Some(needed), Some(warmest)) = (info.min_soil_temp_f, warmest) {
+            if warmest < needed as f32 {
+                warnings.push(format!(
+                    "soil forecast to reach only {:.0}°F, below the {}°F it needs to germinate",
+                    warmest, needed
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_warnings() {
+        let forecast = parse_forecast(&serde_json::json!({
+            "daily": {
+                "time": ["2025-04-21", "2025-04-22", "2025-04-23"],
+                "temperature_2m_min": [38.1, 30.4, 41.0],
+            },
+            "hourly": {
+                "time": ["2025-04-21T00:00", "2025-04-21T12:00", "2025-04-22T00:00"],
+                "soil_temperature_6cm": [44.0, 48.0, 45.0],
+            },
+        }))
+        .unwrap();
+        assert_eq!(forecast[0].soil_temp_f, Some(46.0));
+        assert_eq!(forecast[2].soil_temp_f, None);
+
+        let date = |d| NaiveDate::from_ymd_opt(2025, 4, d).unwrap();
+        let task = Task {
+            kind: TaskKind::SowOutside,
+            plant: "Bean".to_string(),
+            from: date(21),
+            until: date(21),
+        };
+        let bean = PlantInfo {
+            min_soil_temp_f: Some(60),
+            ..Default::default()
+        };
+        assert_eq!(
+            task_warnings(&task, &bean, &forecast),
+            vec![
+                "frost forecast Apr 22 (30°F)",
+                "soil forecast to reach only 46°F, below the 60°F it needs to germinate"
+            ]
+        );
+
+        let carrot = PlantInfo {
+            tags: vec!["Frost Tolerant".to_string()],
+            min_soil_temp_f: Some(40),
+            ..Default::default()
+        };
+        assert!(task_warnings(&task, &carrot, &forecast).is_empty());
+        assert_eq!(parse_location("45.5, -122.6"), Ok((45.5, -122.6)));
+        assert!(parse_location("95,0").is_err());
+    }
+}